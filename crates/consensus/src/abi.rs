@@ -0,0 +1,30 @@
+//! Helpers for the ABI encoding of contract calls and events.
+
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+
+/// Appends the ABI encoding of the tail of a dynamic `bytes` value: its length, followed by the
+/// bytes padded to a multiple of 32 bytes.
+pub(crate) fn encode_bytes(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend_from_slice(&U256::from(data.len()).to_be_bytes::<32>());
+    out.extend_from_slice(data);
+    out.resize(out.len() + data.len().next_multiple_of(32) - data.len(), 0);
+}
+
+/// Reads the `i`-th 32-byte word of the data.
+pub(crate) fn word(data: &[u8], i: usize) -> Option<U256> {
+    data.get(32 * i..32 * (i + 1)).map(U256::from_be_slice)
+}
+
+/// Reads the address in the `i`-th 32-byte word of the data, which must be zero-padded.
+pub(crate) fn address(data: &[u8], i: usize) -> Option<Address> {
+    let word = data.get(32 * i..32 * (i + 1))?;
+    word[..12].iter().all(|byte| *byte == 0).then(|| Address::from_slice(&word[12..]))
+}
+
+/// Reads the dynamic `bytes` value whose offset in the data is held by the `i`-th 32-byte word.
+pub(crate) fn bytes(data: &[u8], i: usize) -> Option<&[u8]> {
+    let offset: usize = word(data, i)?.try_into().ok()?;
+    let len: usize = data.get(offset..)?.get(..32).map(U256::from_be_slice)?.try_into().ok()?;
+    data.get(offset.checked_add(32)?..)?.get(..len)
+}
@@ -0,0 +1,426 @@
+//! ETH and ERC-20 transfers through the `L1StandardBridge` and `L2StandardBridge`.
+
+use crate::{OpTxEnvelope, TxDeposit, abi, predeploys::L2_STANDARD_BRIDGE_ADDRESS};
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, Bytes, Selector, TxKind, U256, address, fixed_bytes};
+
+/// The legacy ERC-20 representation of ETH on L2, used by the legacy bridge functions.
+pub const LEGACY_ERC20_ETH: Address = address!("0xDeadDeAddeAddEAddeadDEaDDEAdDeaDDeAD0000");
+
+/// The selector of `finalizeBridgeETH(address,address,uint256,bytes)`.
+pub const FINALIZE_BRIDGE_ETH_SELECTOR: Selector = fixed_bytes!("0x1635f5fd");
+
+/// The selector of `finalizeBridgeERC20(address,address,address,address,uint256,bytes)`.
+pub const FINALIZE_BRIDGE_ERC20_SELECTOR: Selector = fixed_bytes!("0x0166a07a");
+
+/// The selector of the legacy `finalizeDeposit(address,address,address,address,uint256,bytes)`.
+pub const FINALIZE_DEPOSIT_SELECTOR: Selector = fixed_bytes!("0x662a633a");
+
+/// The selector of the legacy `withdraw(address,uint256,uint32,bytes)`.
+pub const WITHDRAW_SELECTOR: Selector = fixed_bytes!("0x32b7006d");
+
+/// The selector of the legacy `withdrawTo(address,address,uint256,uint32,bytes)`.
+pub const WITHDRAW_TO_SELECTOR: Selector = fixed_bytes!("0xa3a79548");
+
+/// The selector of `bridgeETH(uint32,bytes)`.
+pub const BRIDGE_ETH_SELECTOR: Selector = fixed_bytes!("0x09fc8843");
+
+/// The selector of `bridgeETHTo(address,uint32,bytes)`.
+pub const BRIDGE_ETH_TO_SELECTOR: Selector = fixed_bytes!("0xe11013dd");
+
+/// The selector of `bridgeERC20(address,address,uint256,uint32,bytes)`.
+pub const BRIDGE_ERC20_SELECTOR: Selector = fixed_bytes!("0x87087623");
+
+/// The selector of `bridgeERC20To(address,address,address,uint256,uint32,bytes)`.
+pub const BRIDGE_ERC20_TO_SELECTOR: Selector = fixed_bytes!("0x540abf73");
+
+/// The direction of a bridge transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum BridgeDirection {
+    /// A transfer from L1 to L2, finalized on L2 by a deposit.
+    Deposit,
+    /// A transfer from L2 to L1, initiated on L2 by a user transaction.
+    Withdrawal,
+}
+
+/// The token of a bridge transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum BridgeToken {
+    /// ETH.
+    Eth,
+    /// An ERC-20 token.
+    Erc20 {
+        /// The address of the token on L2.
+        local_token: Address,
+        /// The address of the token on L1, if known. The legacy withdrawal functions only name
+        /// the L2 token.
+        remote_token: Option<Address>,
+    },
+}
+
+/// A transfer through the standard bridge, decoded from the L2 side of the bridge.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BridgeAction {
+    /// The direction of the transfer.
+    pub direction: BridgeDirection,
+    /// The transferred token.
+    pub token: BridgeToken,
+    /// The transferred amount.
+    pub amount: U256,
+    /// The address that sent the tokens, on the source chain.
+    pub sender: Address,
+    /// The address that receives the tokens, on the destination chain.
+    pub recipient: Address,
+    /// The extra data of the transfer, which the bridge forwards without interpreting it.
+    pub extra_data: Bytes,
+}
+
+impl BridgeAction {
+    /// Decodes a deposit from the calldata of a call to the `L2StandardBridge` relayed by the
+    /// `L2CrossDomainMessenger`: `finalizeBridgeETH`, `finalizeBridgeERC20` or the legacy
+    /// `finalizeDeposit`.
+    ///
+    /// Returns `None` if the calldata is not a valid call to one of these functions.
+    pub fn decode_deposit(input: &[u8]) -> Option<Self> {
+        let (selector, data) = input.split_at_checked(4)?;
+        let (token, args) = match Selector::from_slice(selector) {
+            FINALIZE_BRIDGE_ETH_SELECTOR => (BridgeToken::Eth, 0),
+            FINALIZE_BRIDGE_ERC20_SELECTOR => {
+                (erc20(abi::address(data, 0)?, abi::address(data, 1)?), 2)
+            }
+            FINALIZE_DEPOSIT_SELECTOR => {
+                let (l1_token, l2_token) = (abi::address(data, 0)?, abi::address(data, 1)?);
+                (
+                    if l2_token == LEGACY_ERC20_ETH {
+                        BridgeToken::Eth
+                    } else {
+                        erc20(l2_token, l1_token)
+                    },
+                    2,
+                )
+            }
+            _ => return None,
+        };
+        Some(Self {
+            direction: BridgeDirection::Deposit,
+            token,
+            sender: abi::address(data, args)?,
+            recipient: abi::address(data, args + 1)?,
+            amount: abi::word(data, args + 2)?,
+            extra_data: Bytes::copy_from_slice(abi::bytes(data, args + 3)?),
+        })
+    }
+
+    /// Decodes a withdrawal from a call to the `L2StandardBridge` by `sender` with the given ETH
+    /// `value`: `bridgeETH`, `bridgeETHTo`, `bridgeERC20`, `bridgeERC20To`, the legacy
+    /// `withdraw` and `withdrawTo`, or a plain ETH transfer.
+    ///
+    /// Returns `None` if the calldata is not a valid call to one of these functions.
+    pub fn decode_withdrawal(input: &[u8], value: U256, sender: Address) -> Option<Self> {
+        let withdrawal = |token, amount, recipient, extra_data: &[u8]| Self {
+            direction: BridgeDirection::Withdrawal,
+            token,
+            amount,
+            sender,
+            recipient,
+            extra_data: Bytes::copy_from_slice(extra_data),
+        };
+        if input.is_empty() {
+            return Some(withdrawal(BridgeToken::Eth, value, sender, &[]));
+        }
+
+        let (selector, data) = input.split_at_checked(4)?;
+        let action = match Selector::from_slice(selector) {
+            BRIDGE_ETH_SELECTOR => {
+                withdrawal(BridgeToken::Eth, value, sender, abi::bytes(data, 1)?)
+            }
+            BRIDGE_ETH_TO_SELECTOR => {
+                withdrawal(BridgeToken::Eth, value, abi::address(data, 0)?, abi::bytes(data, 2)?)
+            }
+            BRIDGE_ERC20_SELECTOR => withdrawal(
+                erc20(abi::address(data, 0)?, abi::address(data, 1)?),
+                abi::word(data, 2)?,
+                sender,
+                abi::bytes(data, 4)?,
+            ),
+            BRIDGE_ERC20_TO_SELECTOR => withdrawal(
+                erc20(abi::address(data, 0)?, abi::address(data, 1)?),
+                abi::word(data, 3)?,
+                abi::address(data, 2)?,
+                abi::bytes(data, 5)?,
+            ),
+            WITHDRAW_SELECTOR => withdrawal(
+                legacy_token(abi::address(data, 0)?),
+                abi::word(data, 1)?,
+                sender,
+                abi::bytes(data, 3)?,
+            ),
+            WITHDRAW_TO_SELECTOR => withdrawal(
+                legacy_token(abi::address(data, 0)?),
+                abi::word(data, 2)?,
+                abi::address(data, 1)?,
+                abi::bytes(data, 4)?,
+            ),
+            _ => return None,
+        };
+        Some(action)
+    }
+}
+
+/// Returns the ERC-20 token with the given L2 and L1 addresses.
+const fn erc20(local_token: Address, remote_token: Address) -> BridgeToken {
+    BridgeToken::Erc20 { local_token, remote_token: Some(remote_token) }
+}
+
+/// Returns the token of a legacy withdrawal, which names ETH by [`LEGACY_ERC20_ETH`].
+fn legacy_token(l2_token: Address) -> BridgeToken {
+    if l2_token == LEGACY_ERC20_ETH {
+        BridgeToken::Eth
+    } else {
+        BridgeToken::Erc20 { local_token: l2_token, remote_token: None }
+    }
+}
+
+impl TxDeposit {
+    /// Returns the bridge deposit finalized by the deposit, if it relays a message from the
+    /// `L1StandardBridge` of the chain at `l1_standard_bridge` to the `L2StandardBridge` through
+    /// the `L2CrossDomainMessenger`.
+    ///
+    /// Messages from any other sender are rejected by the `L2StandardBridge`, so they are not
+    /// bridge deposits even if their calldata is.
+    pub fn bridge_action(&self, l1_standard_bridge: Address) -> Option<BridgeAction> {
+        let message = self.cross_domain_message()?;
+        if message.sender != l1_standard_bridge || message.target != L2_STANDARD_BRIDGE_ADDRESS {
+            return None;
+        }
+        BridgeAction::decode_deposit(&message.message)
+    }
+}
+
+impl OpTxEnvelope {
+    /// Returns the bridge transfer of the transaction: the deposit it finalizes for deposit
+    /// transactions, and the withdrawal it initiates for user transactions to the
+    /// `L2StandardBridge`, sent by the recovered `sender`.
+    ///
+    /// The `sender` is ignored for deposit transactions, which must relay a message from the
+    /// `L1StandardBridge` of the chain at `l1_standard_bridge`, see [`TxDeposit::bridge_action`].
+    pub fn bridge_action(
+        &self,
+        sender: Address,
+        l1_standard_bridge: Address,
+    ) -> Option<BridgeAction> {
+        if let Some(deposit) = self.as_deposit() {
+            return deposit.bridge_action(l1_standard_bridge);
+        }
+        if self.kind() != TxKind::Call(L2_STANDARD_BRIDGE_ADDRESS) {
+            return None;
+        }
+        BridgeAction::decode_withdrawal(self.input(), self.value(), sender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CrossDomainMessage, messenger::encode_versioned_nonce,
+        predeploys::L2_CROSS_DOMAIN_MESSENGER_ADDRESS,
+    };
+    use alloy_consensus::{Signed, TxEip1559};
+    use alloy_primitives::{B256, Signature, hex};
+
+    const FROM: Address = address!("0x1111111111111111111111111111111111111111");
+    const TO: Address = address!("0x2222222222222222222222222222222222222222");
+    const L1_TOKEN: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const L2_TOKEN: Address = address!("0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85");
+    const L1_STANDARD_BRIDGE: Address = address!("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1");
+
+    /// Encodes a call whose arguments are static words followed by a trailing `bytes` argument.
+    fn call(selector: Selector, words: &[B256], extra_data: &[u8]) -> Bytes {
+        let mut out = selector.to_vec();
+        for word in words {
+            out.extend_from_slice(word.as_slice());
+        }
+        out.extend_from_slice(&U256::from(32 * (words.len() + 1)).to_be_bytes::<32>());
+        abi::encode_bytes(&mut out, extra_data);
+        out.into()
+    }
+
+    fn uint(value: u64) -> B256 {
+        U256::from(value).into()
+    }
+
+    fn action(direction: BridgeDirection, token: BridgeToken, amount: u64) -> BridgeAction {
+        BridgeAction {
+            direction,
+            token,
+            amount: U256::from(amount),
+            sender: FROM,
+            recipient: TO,
+            extra_data: Bytes::from_static(&hex!("beef")),
+        }
+    }
+
+    #[test]
+    fn test_deposit_bridge_action() {
+        let input = call(
+            FINALIZE_BRIDGE_ETH_SELECTOR,
+            &[FROM.into_word(), TO.into_word(), uint(1000)],
+            &hex!("beef"),
+        );
+        let message = CrossDomainMessage {
+            nonce: encode_versioned_nonce(U256::from(1), 1),
+            sender: L1_STANDARD_BRIDGE,
+            target: L2_STANDARD_BRIDGE_ADDRESS,
+            value: U256::from(1000),
+            message: input,
+            ..Default::default()
+        };
+        let deposit = TxDeposit {
+            to: TxKind::Call(L2_CROSS_DOMAIN_MESSENGER_ADDRESS),
+            input: message.encode_relay_message().unwrap(),
+            ..Default::default()
+        };
+        let expected = action(BridgeDirection::Deposit, BridgeToken::Eth, 1000);
+        assert_eq!(deposit.bridge_action(L1_STANDARD_BRIDGE), Some(expected.clone()));
+        let tx = OpTxEnvelope::Deposit(alloy_consensus::Sealable::seal_slow(deposit));
+        assert_eq!(tx.bridge_action(Address::ZERO, L1_STANDARD_BRIDGE), Some(expected));
+
+        // Messages to other targets are not bridge deposits.
+        let relay = |message: &CrossDomainMessage| TxDeposit {
+            to: TxKind::Call(L2_CROSS_DOMAIN_MESSENGER_ADDRESS),
+            input: message.encode_relay_message().unwrap(),
+            ..Default::default()
+        };
+        let other_target = CrossDomainMessage { target: TO, ..message.clone() };
+        assert_eq!(relay(&other_target).bridge_action(L1_STANDARD_BRIDGE), None);
+
+        // Neither are messages with bridge calldata from any account but the L1 bridge, which the
+        // L2 bridge rejects.
+        let spoofed = CrossDomainMessage { sender: FROM, ..message };
+        assert_eq!(relay(&spoofed).bridge_action(L1_STANDARD_BRIDGE), None);
+        assert_eq!(
+            relay(&spoofed).bridge_action(FROM).map(|action| action.amount),
+            Some(U256::from(1000))
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_erc20() {
+        let input = call(
+            FINALIZE_BRIDGE_ERC20_SELECTOR,
+            &[
+                L2_TOKEN.into_word(),
+                L1_TOKEN.into_word(),
+                FROM.into_word(),
+                TO.into_word(),
+                uint(5),
+            ],
+            &hex!("beef"),
+        );
+        let token = BridgeToken::Erc20 { local_token: L2_TOKEN, remote_token: Some(L1_TOKEN) };
+        assert_eq!(
+            BridgeAction::decode_deposit(&input),
+            Some(action(BridgeDirection::Deposit, token, 5))
+        );
+
+        // The legacy function lists the L1 token first.
+        let input = call(
+            FINALIZE_DEPOSIT_SELECTOR,
+            &[
+                L1_TOKEN.into_word(),
+                L2_TOKEN.into_word(),
+                FROM.into_word(),
+                TO.into_word(),
+                uint(5),
+            ],
+            &hex!("beef"),
+        );
+        assert_eq!(
+            BridgeAction::decode_deposit(&input),
+            Some(action(BridgeDirection::Deposit, token, 5))
+        );
+        let input = call(
+            FINALIZE_DEPOSIT_SELECTOR,
+            &[B256::ZERO, LEGACY_ERC20_ETH.into_word(), FROM.into_word(), TO.into_word(), uint(5)],
+            &hex!("beef"),
+        );
+        assert_eq!(
+            BridgeAction::decode_deposit(&input),
+            Some(action(BridgeDirection::Deposit, BridgeToken::Eth, 5))
+        );
+
+        assert_eq!(BridgeAction::decode_deposit(&input[..input.len() - 64]), None);
+        assert_eq!(BridgeAction::decode_deposit(&hex!("deadbeef")), None);
+    }
+
+    #[test]
+    fn test_decode_withdrawal() {
+        let value = U256::from(1000);
+        let eth = action(BridgeDirection::Withdrawal, BridgeToken::Eth, 1000);
+        let to_self = BridgeAction { recipient: FROM, ..eth.clone() };
+
+        let input = call(BRIDGE_ETH_SELECTOR, &[uint(200_000)], &hex!("beef"));
+        assert_eq!(BridgeAction::decode_withdrawal(&input, value, FROM), Some(to_self.clone()));
+        let input = call(BRIDGE_ETH_TO_SELECTOR, &[TO.into_word(), uint(200_000)], &hex!("beef"));
+        assert_eq!(BridgeAction::decode_withdrawal(&input, value, FROM), Some(eth.clone()));
+        let input = call(
+            WITHDRAW_TO_SELECTOR,
+            &[LEGACY_ERC20_ETH.into_word(), TO.into_word(), uint(1000), uint(200_000)],
+            &hex!("beef"),
+        );
+        assert_eq!(BridgeAction::decode_withdrawal(&input, value, FROM), Some(eth));
+        assert_eq!(
+            BridgeAction::decode_withdrawal(&[], value, FROM),
+            Some(BridgeAction { extra_data: Bytes::new(), ..to_self })
+        );
+
+        let token = BridgeToken::Erc20 { local_token: L2_TOKEN, remote_token: Some(L1_TOKEN) };
+        let input = call(
+            BRIDGE_ERC20_TO_SELECTOR,
+            &[L2_TOKEN.into_word(), L1_TOKEN.into_word(), TO.into_word(), uint(5), uint(200_000)],
+            &hex!("beef"),
+        );
+        assert_eq!(
+            BridgeAction::decode_withdrawal(&input, U256::ZERO, FROM),
+            Some(action(BridgeDirection::Withdrawal, token, 5))
+        );
+        let input =
+            call(WITHDRAW_SELECTOR, &[L2_TOKEN.into_word(), uint(5), uint(200_000)], &hex!("beef"));
+        let token = BridgeToken::Erc20 { local_token: L2_TOKEN, remote_token: None };
+        assert_eq!(
+            BridgeAction::decode_withdrawal(&input, U256::ZERO, FROM),
+            Some(BridgeAction { recipient: FROM, ..action(BridgeDirection::Withdrawal, token, 5) })
+        );
+    }
+
+    #[test]
+    fn test_tx_bridge_action() {
+        let tx = |to| {
+            OpTxEnvelope::Eip1559(Signed::new_unchecked(
+                TxEip1559 {
+                    to: TxKind::Call(to),
+                    value: U256::from(1000),
+                    input: call(BRIDGE_ETH_TO_SELECTOR, &[TO.into_word(), uint(0)], &hex!("beef")),
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                B256::ZERO,
+            ))
+        };
+        assert_eq!(
+            tx(L2_STANDARD_BRIDGE_ADDRESS).bridge_action(FROM, L1_STANDARD_BRIDGE),
+            Some(action(BridgeDirection::Withdrawal, BridgeToken::Eth, 1000))
+        );
+        assert_eq!(tx(TO).bridge_action(FROM, L1_STANDARD_BRIDGE), None);
+    }
+}
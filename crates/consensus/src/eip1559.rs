@@ -1,6 +1,8 @@
 //! Support for EIP-1559 parameters after holocene.
 
-use alloy_eips::eip1559::BaseFeeParams;
+use crate::OpHardforks;
+use alloy_consensus::{BlockHeader, Header};
+use alloy_eips::eip1559::{self, BaseFeeParams};
 use alloy_primitives::{B64, Bytes};
 
 /// Extracts the Holocene 1599 parameters from the encoded form:
@@ -61,6 +63,91 @@ pub fn encode_holocene_extra_data(
     Ok(Bytes::copy_from_slice(&extra_data))
 }
 
+/// Decodes the `eip1559` parameters and the minimum base fee from the Jovian `extradata` bytes.
+///
+/// Returns (`elasticity`, `denominator`, `min_base_fee`)
+pub fn decode_jovian_extra_data(extra_data: &[u8]) -> Result<(u32, u32, u64), EIP1559ParamError> {
+    if extra_data.len() < 17 {
+        return Err(EIP1559ParamError::NoEIP1559Params);
+    }
+
+    if extra_data[0] != 1 {
+        // version must be 1: https://github.com/ethereum-optimism/specs/blob/main/specs/protocol/jovian/exec-engine.md#minimum-base-fee-in-block-header
+        return Err(EIP1559ParamError::InvalidVersion(extra_data[0]));
+    }
+    let (elasticity, denominator) = decode_eip_1559_params(B64::from_slice(&extra_data[1..9]));
+    let min_base_fee: [u8; 8] = extra_data[9..17].try_into().expect("sufficient length");
+
+    Ok((elasticity, denominator, u64::from_be_bytes(min_base_fee)))
+}
+
+/// Encodes the `eip1559` parameters and the minimum base fee for the payload after Jovian.
+pub fn encode_jovian_extra_data(
+    eip_1559_params: B64,
+    default_base_fee_params: BaseFeeParams,
+    min_base_fee: u64,
+) -> Result<Bytes, EIP1559ParamError> {
+    // 17 bytes: 1 byte for version (1), 8 bytes for eip1559 params and 8 bytes for min base fee
+    let holocene_extra_data = encode_holocene_extra_data(eip_1559_params, default_base_fee_params)?;
+    let mut extra_data = [0u8; 17];
+    extra_data[0] = 1;
+    extra_data[1..9].copy_from_slice(&holocene_extra_data[1..9]);
+    extra_data[9..17].copy_from_slice(&min_base_fee.to_be_bytes());
+    Ok(Bytes::copy_from_slice(&extra_data))
+}
+
+/// Computes the base fee of the child block of `parent`.
+///
+/// If Holocene is active for the `parent` block, the EIP-1559 parameters are read from its
+/// `extra_data`, and `default_base_fee_params` are only used if they are zero. Otherwise,
+/// `default_base_fee_params` are used, which must be the Canyon parameters if Canyon is active for
+/// the child block.
+///
+/// If Jovian is active for the `parent` block, the gas usage of the parent is the maximum of its
+/// gas used and its DA footprint, stored in the blob gas used field, and the base fee is at least
+/// the minimum base fee of its `extra_data`.
+pub fn calc_next_block_base_fee(
+    hardforks: &impl OpHardforks,
+    parent: &Header,
+    default_base_fee_params: BaseFeeParams,
+) -> Result<u64, EIP1559ParamError> {
+    let base_fee = parent.base_fee_per_gas().ok_or(EIP1559ParamError::MissingBaseFee)?;
+    let base_fee_params = |elasticity: u32, denominator: u32| match (elasticity, denominator) {
+        (0, 0) => Ok(default_base_fee_params),
+        (_, 0) => Err(EIP1559ParamError::ZeroDenominator),
+        (elasticity, denominator) => {
+            Ok(BaseFeeParams::new(denominator as u128, elasticity as u128))
+        }
+    };
+
+    if hardforks.is_jovian_active_at_timestamp(parent.timestamp()) {
+        let (elasticity, denominator, min_base_fee) =
+            decode_jovian_extra_data(parent.extra_data())?;
+        let gas_used = parent.gas_used().max(parent.blob_gas_used().unwrap_or_default());
+        let next_base_fee = eip1559::calc_next_block_base_fee(
+            gas_used,
+            parent.gas_limit(),
+            base_fee,
+            base_fee_params(elasticity, denominator)?,
+        );
+        return Ok(next_base_fee.max(min_base_fee));
+    }
+
+    let base_fee_params = if hardforks.is_holocene_active_at_timestamp(parent.timestamp()) {
+        let (elasticity, denominator) = decode_holocene_extra_data(parent.extra_data())?;
+        base_fee_params(elasticity, denominator)?
+    } else {
+        default_base_fee_params
+    };
+
+    Ok(eip1559::calc_next_block_base_fee(
+        parent.gas_used(),
+        parent.gas_limit(),
+        base_fee,
+        base_fee_params,
+    ))
+}
+
 /// Error type for EIP-1559 parameters
 #[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
 pub enum EIP1559ParamError {
@@ -76,11 +163,21 @@ pub enum EIP1559ParamError {
     /// Elasticity overflow.
     #[error("Elasticity overflow")]
     ElasticityOverflow,
+    /// The extra data sets a zero denominator with a non-zero elasticity.
+    #[error("Zero EIP1559 denominator")]
+    ZeroDenominator,
+    /// The parent header has no base fee.
+    #[error("Missing parent base fee")]
+    MissingBaseFee,
+    /// No minimum base fee provided after Jovian.
+    #[error("Minimum base fee not set")]
+    MinBaseFeeNotSet,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{ForkCondition, OpHardfork, OpHardforkSchedule};
     use core::str::FromStr;
 
     #[test]
@@ -96,4 +193,178 @@ mod tests {
         let extra_data = encode_holocene_extra_data(eip_1559_params, BaseFeeParams::new(80, 60));
         assert_eq!(extra_data.unwrap(), Bytes::copy_from_slice(&[0, 0, 0, 0, 80, 0, 0, 0, 60]));
     }
+
+    fn hardforks(holocene_time: u64, jovian_time: u64) -> OpHardforkSchedule {
+        let forks = [
+            OpHardfork::Regolith,
+            OpHardfork::Canyon,
+            OpHardfork::Delta,
+            OpHardfork::Ecotone,
+            OpHardfork::Fjord,
+            OpHardfork::Granite,
+        ];
+        OpHardforkSchedule::new(
+            [(OpHardfork::Bedrock, ForkCondition::Block(0))]
+                .into_iter()
+                .chain(forks.map(|fork| (fork, ForkCondition::Timestamp(0))))
+                .chain([
+                    (OpHardfork::Holocene, ForkCondition::Timestamp(holocene_time)),
+                    (OpHardfork::Isthmus, ForkCondition::Timestamp(holocene_time)),
+                    (OpHardfork::Jovian, ForkCondition::Timestamp(jovian_time)),
+                ]),
+        )
+        .unwrap()
+    }
+
+    fn parent_header(extra_data: &[u8], gas_used: u64) -> Header {
+        Header {
+            timestamp: 100,
+            gas_limit: 30_000_000,
+            gas_used,
+            base_fee_per_gas: Some(1_000_000_000),
+            extra_data: Bytes::copy_from_slice(extra_data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_next_block_base_fee_pre_holocene() {
+        // The extra data is ignored before Holocene.
+        let parent = parent_header(b"garbage", 30_000_000);
+        // 1e9 + 1e9 * (30e6 - 5e6) / 5e6 / 250
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(u64::MAX, u64::MAX),
+                &parent,
+                BaseFeeParams::optimism_canyon()
+            ),
+            Ok(1_020_000_000)
+        );
+        // 1e9 + 1e9 * (30e6 - 5e6) / 5e6 / 50
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(u64::MAX, u64::MAX),
+                &parent,
+                BaseFeeParams::optimism()
+            ),
+            Ok(1_100_000_000)
+        );
+    }
+
+    #[test]
+    fn test_next_block_base_fee_holocene() {
+        // Denominator 8, elasticity 2.
+        let parent = parent_header(&[0, 0, 0, 0, 8, 0, 0, 0, 2], 30_000_000);
+        // 1e9 + 1e9 * (30e6 - 15e6) / 15e6 / 8
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(0, u64::MAX),
+                &parent,
+                BaseFeeParams::optimism_canyon()
+            ),
+            Ok(1_125_000_000)
+        );
+
+        // Zero parameters fall back to the defaults.
+        let parent = parent_header(&[0; 9], 30_000_000);
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(0, u64::MAX),
+                &parent,
+                BaseFeeParams::optimism_canyon()
+            ),
+            Ok(1_020_000_000)
+        );
+    }
+
+    #[test]
+    fn test_next_block_base_fee_invalid() {
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(0, u64::MAX),
+                &parent_header(&[], 0),
+                BaseFeeParams::optimism_canyon()
+            ),
+            Err(EIP1559ParamError::NoEIP1559Params)
+        );
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(0, u64::MAX),
+                &parent_header(&[0, 0, 0, 0, 0, 0, 0, 0, 2], 0),
+                BaseFeeParams::optimism_canyon()
+            ),
+            Err(EIP1559ParamError::ZeroDenominator)
+        );
+        let parent = Header { base_fee_per_gas: None, ..parent_header(&[], 0) };
+        assert_eq!(
+            calc_next_block_base_fee(
+                &hardforks(u64::MAX, u64::MAX),
+                &parent,
+                BaseFeeParams::optimism_canyon()
+            ),
+            Err(EIP1559ParamError::MissingBaseFee)
+        );
+    }
+
+    #[test]
+    fn test_jovian_extra_data() {
+        let eip_1559_params = B64::from_str("0x0000000800000002").unwrap();
+        let extra_data = encode_jovian_extra_data(eip_1559_params, BaseFeeParams::new(80, 60), 257);
+        assert_eq!(
+            extra_data.unwrap(),
+            Bytes::copy_from_slice(&[1, 0, 0, 0, 8, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 1])
+        );
+        let extra_data =
+            encode_jovian_extra_data(B64::ZERO, BaseFeeParams::new(80, 60), 0).unwrap();
+        assert_eq!(decode_jovian_extra_data(&extra_data), Ok((60, 80, 0)));
+
+        assert_eq!(
+            decode_jovian_extra_data(&[0, 0, 0, 0, 8, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 1]),
+            Err(EIP1559ParamError::InvalidVersion(0))
+        );
+        assert_eq!(
+            decode_jovian_extra_data(&[1, 0, 0, 0, 8, 0, 0, 0, 2]),
+            Err(EIP1559ParamError::NoEIP1559Params)
+        );
+    }
+
+    #[test]
+    fn test_next_block_base_fee_jovian() {
+        let schedule = hardforks(0, 0);
+        let extra_data = [1, 0, 0, 0, 8, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0];
+        // 1e9 + 1e9 * (30e6 - 15e6) / 15e6 / 8
+        let parent = parent_header(&extra_data, 30_000_000);
+        assert_eq!(
+            calc_next_block_base_fee(&schedule, &parent, BaseFeeParams::optimism_canyon()),
+            Ok(1_125_000_000)
+        );
+
+        // The DA footprint counts as gas usage if it is higher.
+        let parent = Header { blob_gas_used: Some(30_000_000), ..parent_header(&extra_data, 0) };
+        assert_eq!(
+            calc_next_block_base_fee(&schedule, &parent, BaseFeeParams::optimism_canyon()),
+            Ok(1_125_000_000)
+        );
+
+        // The base fee doesn't drop below the minimum base fee.
+        let mut extra_data = extra_data;
+        extra_data[9..].copy_from_slice(&1_000_000_000u64.to_be_bytes());
+        let parent = parent_header(&extra_data, 0);
+        assert_eq!(
+            calc_next_block_base_fee(&schedule, &parent, BaseFeeParams::optimism_canyon()),
+            Ok(1_000_000_000)
+        );
+
+        // Holocene extra data is rejected after Jovian.
+        let parent = parent_header(&[0, 0, 0, 0, 8, 0, 0, 0, 2], 30_000_000);
+        assert_eq!(
+            calc_next_block_base_fee(&schedule, &parent, BaseFeeParams::optimism_canyon()),
+            Err(EIP1559ParamError::NoEIP1559Params)
+        );
+        // Jovian activation block: the parent still has Holocene extra data.
+        assert_eq!(
+            calc_next_block_base_fee(&hardforks(0, 101), &parent, BaseFeeParams::optimism_canyon()),
+            Ok(1_125_000_000)
+        );
+    }
 }
@@ -0,0 +1,144 @@
+//! FastLZ compressed length estimation.
+//!
+//! Port of the `flzCompress` length computation in [solady], which the `GasPriceOracle`
+//! predeploy uses to estimate the compressed size of transactions since Fjord.
+//!
+//! Only the length of the compressed output is computed, so nothing is allocated and the hash
+//! table is the only state.
+//!
+//! [solady]: https://github.com/Vectorized/solady/blob/5315d937d79b335c668896d7533ac603adac5315/js/solady.js
+
+/// Size of the hash table of the compressor.
+const HASH_TABLE_LEN: usize = 8192;
+
+/// Maximum distance of a match.
+const MAX_DISTANCE: u32 = 8192;
+
+/// Returns the length of `input` after FastLZ (level 1) compression, as computed by the
+/// `GasPriceOracle` and op-geth.
+pub fn flz_compress_len(input: &[u8]) -> u32 {
+    let len = input.len() as u32;
+    let idx_limit = len.saturating_sub(13);
+    let mut htab = [0u32; HASH_TABLE_LEN];
+    let mut idx = 2;
+    let mut anchor = 0;
+    let mut size = 0;
+
+    while idx < idx_limit {
+        let mut reference;
+        loop {
+            let seq = u24(input, idx);
+            let hash = hash(seq);
+            reference = htab[hash];
+            htab[hash] = idx;
+            let distance = idx - reference;
+            if idx >= idx_limit {
+                break;
+            }
+            idx += 1;
+            if distance < MAX_DISTANCE && seq == u24(input, reference) {
+                break;
+            }
+        }
+
+        if idx >= idx_limit {
+            break;
+        }
+        idx -= 1;
+
+        if idx > anchor {
+            size = literals(idx - anchor, size);
+        }
+
+        let match_len = match_len(input, reference + 3, idx + 3, idx_limit + 9);
+        size = matches(match_len, size);
+
+        idx = set_next_hash(&mut htab, input, idx + match_len);
+        idx = set_next_hash(&mut htab, input, idx);
+        anchor = idx;
+    }
+
+    literals(len - anchor, size)
+}
+
+/// Adds the size of a run of `count` literals.
+const fn literals(count: u32, size: u32) -> u32 {
+    let size = size + 0x21 * (count / 0x20);
+    let rest = count % 0x20;
+    if rest != 0 { size + rest + 1 } else { size }
+}
+
+/// Adds the size of a match of length `len`.
+const fn matches(len: u32, size: u32) -> u32 {
+    let len = len - 1;
+    let size = size + 3 * (len / 262);
+    if len % 262 >= 6 { size + 3 } else { size + 2 }
+}
+
+/// Returns the length of the match between the sequences at `p` and `q`, bounded by `end`.
+///
+/// Like the reference implementation, a mismatch only ends the scan after the mismatching byte.
+fn match_len(input: &[u8], p: u32, q: u32, end: u32) -> u32 {
+    let mut len = 0;
+    let mut limit = end - q;
+    while len < limit {
+        if input[(p + len) as usize] != input[(q + len) as usize] {
+            limit = 0;
+        }
+        len += 1;
+    }
+    len
+}
+
+fn set_next_hash(htab: &mut [u32; HASH_TABLE_LEN], input: &[u8], idx: u32) -> u32 {
+    htab[hash(u24(input, idx))] = idx;
+    idx + 1
+}
+
+const fn hash(seq: u32) -> usize {
+    (((seq as u64 * 2654435769) >> 19) & 0x1fff) as usize
+}
+
+fn u24(input: &[u8], idx: u32) -> u32 {
+    let idx = idx as usize;
+    u32::from(input[idx]) | (u32::from(input[idx + 1]) << 8) | (u32::from(input[idx + 2]) << 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SAMPLE_CONTRACT_CALL;
+    use alloc::vec::Vec;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_flz_compress_len() {
+        assert_eq!(flz_compress_len(&[]), 0);
+        assert_eq!(flz_compress_len(&[0; 1000]), 21);
+        assert_eq!(flz_compress_len(&[42; 1000]), 21);
+        assert_eq!(flz_compress_len(&hex!("FACADE")), 4);
+    }
+
+    #[test]
+    fn test_flz_compress_len_transactions() {
+        assert_eq!(flz_compress_len(SAMPLE_CONTRACT_CALL), 202);
+
+        // Base transaction 0x5dadeb52979f29fc7a7494c43fdabc5be1d8ff404f3aafe93d729fa8e5d00769.
+        let base_tx = hex!(
+            "b9047c02f904788221050883036ee48409c6c87383037f6f941195cf65f83b3a5768f3c496d3a05ad6412c64b78644364c5bb000b90404d123b4d80000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000038000000000000000000000000000000000f6476f90447748c19248ccaa31e6b8bfda4eb9d830f5f47df7f0998f7c2123d9e6137761b75d3184efb0f788e3b14516000000000000000000000000000000000000000000000000000044364c5bb000000000000000000000000000f38e53bd45c8225a7c94b513beadaa7afe5d222d0000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000024000000000000000000000000000000000000000000000000000000000000002a000000000000000000000000000000000000000000000000000000000000002c000000000000000000000000000000000000000000000000000000000000002e0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000084d6574614d61736b0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000035697066733a2f2f516d656852577a743347745961776343347564745657557233454c587261436746434259416b66507331696f48610000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000cd0d83d9e840f8e27d5c2e365fd365ff1c05b2480000000000000000000000000000000000000000000000000000000000000ce40000000000000000000000000000000000000000000000000000000000000041e4480d358dbae20880960a0a464d63b06565a0c9f9b1b37aa94b522247b23ce149c81359bf4239d1a879eeb41047ec710c15f5c0f67453da59a383e6abd742971c00000000000000000000000000000000000000000000000000000000000000c001a0b57f0ff8516ea29cb26a44ac5055a5420847d1e16a8e7b03b70f0c02291ff2d5a00ad3771e5f39ccacfff0faa8c5d25ef7a1c179f79e66e828ffddcb994c8b512e"
+        );
+        assert_eq!(flz_compress_len(&base_tx), 471);
+    }
+
+    #[test]
+    fn test_flz_compress_len_no_repeats() {
+        let mut input = Vec::new();
+        let mut len = 0;
+        for i in 0..=255u8 {
+            input.push(i);
+            let prev_len = len;
+            len = flz_compress_len(&input);
+            assert!(len > prev_len);
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! OP Stack hardforks and their activation schedule.
+//!
+//! See: <https://specs.optimism.io/protocol/superchain-upgrades.html>
+
+use crate::upgrades::{
+    ECOTONE_UPGRADE_TXS, FJORD_UPGRADE_TXS, INTEROP_UPGRADE_TXS, ISTHMUS_UPGRADE_TXS,
+    UpgradeTransaction,
+};
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+use derive_more::Display;
+
+/// An OP Stack hardfork.
+///
+/// Variants are ordered by activation: a hardfork can only activate at or after all hardforks
+/// that precede it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OpHardfork {
+    /// Bedrock, the first OP Stack release. Activated by block number.
+    Bedrock,
+    /// Regolith.
+    Regolith,
+    /// Canyon.
+    Canyon,
+    /// Delta, which introduced span batches. It only changed derivation, so execution layer
+    /// configs do not always carry its activation.
+    Delta,
+    /// Ecotone.
+    Ecotone,
+    /// Fjord.
+    Fjord,
+    /// Granite.
+    Granite,
+    /// Holocene.
+    Holocene,
+    /// Isthmus.
+    Isthmus,
+    /// Jovian.
+    Jovian,
+    /// Interop.
+    Interop,
+}
+
+impl OpHardfork {
+    /// All hardforks, in activation order.
+    pub const ALL: [Self; 11] = [
+        Self::Bedrock,
+        Self::Regolith,
+        Self::Canyon,
+        Self::Delta,
+        Self::Ecotone,
+        Self::Fjord,
+        Self::Granite,
+        Self::Holocene,
+        Self::Isthmus,
+        Self::Jovian,
+        Self::Interop,
+    ];
+
+    /// Returns `true` if the hardfork is activated by block number rather than by timestamp.
+    pub const fn is_block_based(&self) -> bool {
+        matches!(self, Self::Bedrock)
+    }
+
+    /// Returns the hardfork that directly precedes this one, if any.
+    pub const fn previous(&self) -> Option<Self> {
+        match *self as usize {
+            0 => None,
+            i => Some(Self::ALL[i - 1]),
+        }
+    }
+
+    /// Returns the upgrade transactions that are included in the activation block of the
+    /// hardfork, in order.
+    ///
+    /// See [`crate::upgrades`].
+    pub const fn upgrade_transactions(&self) -> &'static [UpgradeTransaction] {
+        match self {
+            Self::Ecotone => ECOTONE_UPGRADE_TXS,
+            Self::Fjord => FJORD_UPGRADE_TXS,
+            Self::Isthmus => ISTHMUS_UPGRADE_TXS,
+            Self::Interop => INTEROP_UPGRADE_TXS,
+            _ => &[],
+        }
+    }
+}
+
+impl FromStr for OpHardfork {
+    type Err = OpHardforkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bedrock" => Ok(Self::Bedrock),
+            "regolith" => Ok(Self::Regolith),
+            "canyon" => Ok(Self::Canyon),
+            "delta" => Ok(Self::Delta),
+            "ecotone" => Ok(Self::Ecotone),
+            "fjord" => Ok(Self::Fjord),
+            "granite" => Ok(Self::Granite),
+            "holocene" => Ok(Self::Holocene),
+            "isthmus" => Ok(Self::Isthmus),
+            "jovian" => Ok(Self::Jovian),
+            "interop" => Ok(Self::Interop),
+            _ => Err(OpHardforkParseError(s.to_string())),
+        }
+    }
+}
+
+/// Error when parsing [`OpHardfork`] from string.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown OP Stack hardfork: {0}")]
+pub struct OpHardforkParseError(pub String);
+
+/// The condition under which a hardfork activates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ForkCondition {
+    /// The hardfork activates at the given block number.
+    Block(u64),
+    /// The hardfork activates with the first block whose timestamp is at or after the given
+    /// timestamp.
+    Timestamp(u64),
+    /// The hardfork is not scheduled.
+    #[default]
+    Never,
+}
+
+impl ForkCondition {
+    /// Returns `true` if the hardfork is active at the given block number.
+    pub const fn active_at_block(&self, block: u64) -> bool {
+        matches!(self, Self::Block(activation) if block >= *activation)
+    }
+
+    /// Returns `true` if the hardfork is active at the given timestamp.
+    pub const fn active_at_timestamp(&self, timestamp: u64) -> bool {
+        matches!(self, Self::Timestamp(activation) if timestamp >= *activation)
+    }
+
+    /// Returns the activation timestamp, if the hardfork is activated by timestamp.
+    pub const fn as_timestamp(&self) -> Option<u64> {
+        match self {
+            Self::Timestamp(timestamp) => Some(*timestamp),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the hardfork is not scheduled.
+    pub const fn is_never(&self) -> bool {
+        matches!(self, Self::Never)
+    }
+}
+
+/// Queries the activation of OP Stack hardforks.
+pub trait OpHardforks {
+    /// Returns the activation condition of the given hardfork.
+    fn op_fork_activation(&self, fork: OpHardfork) -> ForkCondition;
+
+    /// Returns `true` if the given timestamp-activated hardfork is active at the timestamp.
+    fn is_op_fork_active_at_timestamp(&self, fork: OpHardfork, timestamp: u64) -> bool {
+        self.op_fork_activation(fork).active_at_timestamp(timestamp)
+    }
+
+    /// Returns `true` if the given block-activated hardfork is active at the block number.
+    fn is_op_fork_active_at_block(&self, fork: OpHardfork, block: u64) -> bool {
+        self.op_fork_activation(fork).active_at_block(block)
+    }
+
+    /// Returns `true` if [`OpHardfork::Bedrock`] is active at the given block number.
+    fn is_bedrock_active_at_block(&self, block: u64) -> bool {
+        self.is_op_fork_active_at_block(OpHardfork::Bedrock, block)
+    }
+
+    /// Returns `true` if [`OpHardfork::Regolith`] is active at the given timestamp.
+    fn is_regolith_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Regolith, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Canyon`] is active at the given timestamp.
+    fn is_canyon_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Canyon, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Delta`] is active at the given timestamp.
+    fn is_delta_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Delta, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Ecotone`] is active at the given timestamp.
+    fn is_ecotone_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Ecotone, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Fjord`] is active at the given timestamp.
+    fn is_fjord_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Fjord, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Granite`] is active at the given timestamp.
+    fn is_granite_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Granite, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Holocene`] is active at the given timestamp.
+    fn is_holocene_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Holocene, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Isthmus`] is active at the given timestamp.
+    fn is_isthmus_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Isthmus, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Jovian`] is active at the given timestamp.
+    fn is_jovian_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Jovian, timestamp)
+    }
+
+    /// Returns `true` if [`OpHardfork::Interop`] is active at the given timestamp.
+    fn is_interop_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_op_fork_active_at_timestamp(OpHardfork::Interop, timestamp)
+    }
+
+    /// Returns `true` if the block with the given timestamp is the first block in which the
+    /// timestamp-activated hardfork is active, i.e. the block that carries its upgrade
+    /// transactions.
+    fn is_op_fork_activation_block(
+        &self,
+        fork: OpHardfork,
+        timestamp: u64,
+        parent_timestamp: u64,
+    ) -> bool {
+        self.is_op_fork_active_at_timestamp(fork, timestamp)
+            && !self.is_op_fork_active_at_timestamp(fork, parent_timestamp)
+    }
+}
+
+/// An error in an [`OpHardforkSchedule`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum OpHardforkScheduleError {
+    /// Thrown if a hardfork is scheduled with the wrong kind of [`ForkCondition`].
+    #[error("{0} cannot be activated by {1:?}")]
+    InvalidCondition(OpHardfork, ForkCondition),
+    /// Thrown if a hardfork is scheduled while a hardfork preceding it is not.
+    #[error("{fork} is scheduled but the preceding {missing} is not")]
+    MissingPredecessor {
+        /// The scheduled hardfork.
+        fork: OpHardfork,
+        /// The preceding hardfork that is not scheduled.
+        missing: OpHardfork,
+    },
+    /// Thrown if a hardfork is scheduled before the hardfork preceding it.
+    #[error(
+        "{fork} at {activation} activates before the preceding {previous} at {previous_activation}"
+    )]
+    OutOfOrder {
+        /// The misordered hardfork.
+        fork: OpHardfork,
+        /// The activation timestamp of the misordered hardfork.
+        activation: u64,
+        /// The preceding hardfork.
+        previous: OpHardfork,
+        /// The activation timestamp of the preceding hardfork.
+        previous_activation: u64,
+    },
+}
+
+/// The validated activation schedule of all [`OpHardfork`]s of a chain.
+///
+/// [`OpHardfork::Bedrock`] is activated by block number and all later hardforks by timestamp.
+/// Scheduled hardforks form a prefix of [`OpHardfork::ALL`] with non-decreasing activation
+/// timestamps. [`OpHardfork::Delta`] only changed derivation, so execution layer configurations
+/// may leave it out of the prefix, in which case it is never active.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OpHardforkSchedule {
+    activations: [ForkCondition; OpHardfork::ALL.len()],
+}
+
+impl OpHardforkSchedule {
+    /// Builds a schedule from the activation conditions of the given hardforks, checking that
+    /// they are consistently ordered. Hardforks that are not listed are not scheduled.
+    pub fn new(
+        activations: impl IntoIterator<Item = (OpHardfork, ForkCondition)>,
+    ) -> Result<Self, OpHardforkScheduleError> {
+        let mut schedule = Self::default();
+        for (fork, condition) in activations {
+            schedule.activations[fork as usize] = condition;
+        }
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    /// Returns an iterator over the scheduled hardforks and their activation conditions, in
+    /// activation order.
+    pub fn iter(&self) -> impl Iterator<Item = (OpHardfork, ForkCondition)> + '_ {
+        OpHardfork::ALL
+            .into_iter()
+            .zip(self.activations)
+            .filter(|(_, condition)| !condition.is_never())
+    }
+
+    /// Returns the latest hardfork that is active at the given block number and timestamp.
+    ///
+    /// A hardfork only counts as active if all hardforks preceding it are active as well.
+    pub fn active_fork(&self, block: u64, timestamp: u64) -> Option<OpHardfork> {
+        self.iter()
+            .take_while(|(_, condition)| {
+                condition.active_at_block(block) || condition.active_at_timestamp(timestamp)
+            })
+            .map(|(fork, _)| fork)
+            .last()
+    }
+
+    fn validate(&self) -> Result<(), OpHardforkScheduleError> {
+        for (fork, condition) in OpHardfork::ALL.into_iter().zip(self.activations) {
+            let valid = match condition {
+                ForkCondition::Block(_) => fork.is_block_based(),
+                ForkCondition::Timestamp(_) => !fork.is_block_based(),
+                ForkCondition::Never => true,
+            };
+            if !valid {
+                return Err(OpHardforkScheduleError::InvalidCondition(fork, condition));
+            }
+
+            let Some(mut previous) = fork.previous() else { continue };
+            if condition.is_never() {
+                continue;
+            }
+            if previous == OpHardfork::Delta && self.activations[previous as usize].is_never() {
+                previous = OpHardfork::Canyon;
+            }
+            let previous_condition = self.activations[previous as usize];
+            if previous_condition.is_never() {
+                return Err(OpHardforkScheduleError::MissingPredecessor {
+                    fork,
+                    missing: previous,
+                });
+            }
+            if let (Some(activation), Some(previous_activation)) =
+                (condition.as_timestamp(), previous_condition.as_timestamp())
+            {
+                if activation < previous_activation {
+                    return Err(OpHardforkScheduleError::OutOfOrder {
+                        fork,
+                        activation,
+                        previous,
+                        previous_activation,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OpHardforks for OpHardforkSchedule {
+    fn op_fork_activation(&self, fork: OpHardfork) -> ForkCondition {
+        self.activations[fork as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn schedule(times: &[u64]) -> Result<OpHardforkSchedule, OpHardforkScheduleError> {
+        OpHardforkSchedule::new(
+            [(OpHardfork::Bedrock, ForkCondition::Block(100))].into_iter().chain(
+                OpHardfork::ALL[1..]
+                    .iter()
+                    .zip(times)
+                    .map(|(fork, time)| (*fork, ForkCondition::Timestamp(*time))),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_hardfork_order() {
+        assert!(OpHardfork::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(OpHardfork::Bedrock.previous(), None);
+        assert_eq!(OpHardfork::Holocene.previous(), Some(OpHardfork::Granite));
+    }
+
+    #[test]
+    fn test_hardfork_from_str() {
+        for fork in OpHardfork::ALL {
+            assert_eq!(fork.to_string().parse::<OpHardfork>(), Ok(fork));
+        }
+        assert_eq!("ISTHMUS".parse::<OpHardfork>(), Ok(OpHardfork::Isthmus));
+        assert_eq!("jovian".parse::<OpHardfork>(), Ok(OpHardfork::Jovian));
+        assert_eq!("Delta".parse::<OpHardfork>(), Ok(OpHardfork::Delta));
+        assert!("bogota".parse::<OpHardfork>().is_err());
+    }
+
+    #[test]
+    fn test_schedule_queries() {
+        let schedule = schedule(&[0, 10, 15, 20, 30, 40, 50]).unwrap();
+
+        assert!(!schedule.is_bedrock_active_at_block(99));
+        assert!(schedule.is_bedrock_active_at_block(100));
+        assert!(schedule.is_regolith_active_at_timestamp(0));
+        assert!(!schedule.is_delta_active_at_timestamp(14));
+        assert!(schedule.is_delta_active_at_timestamp(15));
+        assert!(!schedule.is_ecotone_active_at_timestamp(19));
+        assert!(schedule.is_ecotone_active_at_timestamp(20));
+        assert!(schedule.is_holocene_active_at_timestamp(50));
+        assert!(!schedule.is_isthmus_active_at_timestamp(u64::MAX));
+        assert!(!schedule.is_interop_active_at_timestamp(u64::MAX));
+
+        assert!(schedule.is_op_fork_activation_block(OpHardfork::Fjord, 32, 28));
+        assert!(!schedule.is_op_fork_activation_block(OpHardfork::Fjord, 34, 32));
+
+        assert_eq!(schedule.active_fork(0, 0), None);
+        assert_eq!(schedule.active_fork(100, 0), Some(OpHardfork::Regolith));
+        assert_eq!(schedule.active_fork(100, 45), Some(OpHardfork::Granite));
+        assert_eq!(schedule.iter().map(|(fork, _)| fork).collect::<Vec<_>>(), OpHardfork::ALL[..8]);
+    }
+
+    #[test]
+    fn test_schedule_same_timestamp() {
+        let schedule = schedule(&[0; 10]).unwrap();
+        assert!(schedule.is_interop_active_at_timestamp(0));
+    }
+
+    #[test]
+    fn test_schedule_out_of_order() {
+        assert_eq!(
+            schedule(&[0, 10, 5]),
+            Err(OpHardforkScheduleError::OutOfOrder {
+                fork: OpHardfork::Delta,
+                activation: 5,
+                previous: OpHardfork::Canyon,
+                previous_activation: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn test_schedule_missing_predecessor() {
+        let err = OpHardforkSchedule::new([
+            (OpHardfork::Bedrock, ForkCondition::Block(0)),
+            (OpHardfork::Regolith, ForkCondition::Timestamp(0)),
+            (OpHardfork::Canyon, ForkCondition::Timestamp(0)),
+            (OpHardfork::Fjord, ForkCondition::Timestamp(0)),
+        ]);
+        assert_eq!(
+            err,
+            Err(OpHardforkScheduleError::MissingPredecessor {
+                fork: OpHardfork::Fjord,
+                missing: OpHardfork::Ecotone,
+            })
+        );
+        let err = OpHardforkSchedule::new([
+            (OpHardfork::Bedrock, ForkCondition::Block(0)),
+            (OpHardfork::Regolith, ForkCondition::Timestamp(0)),
+            (OpHardfork::Ecotone, ForkCondition::Timestamp(0)),
+        ]);
+        assert_eq!(
+            err,
+            Err(OpHardforkScheduleError::MissingPredecessor {
+                fork: OpHardfork::Ecotone,
+                missing: OpHardfork::Canyon,
+            })
+        );
+
+        let err = OpHardforkSchedule::new([(OpHardfork::Regolith, ForkCondition::Timestamp(0))]);
+        assert_eq!(
+            err,
+            Err(OpHardforkScheduleError::MissingPredecessor {
+                fork: OpHardfork::Regolith,
+                missing: OpHardfork::Bedrock,
+            })
+        );
+    }
+
+    #[test]
+    fn test_schedule_without_delta() {
+        let forks = |ecotone_time| {
+            OpHardforkSchedule::new([
+                (OpHardfork::Bedrock, ForkCondition::Block(0)),
+                (OpHardfork::Regolith, ForkCondition::Timestamp(0)),
+                (OpHardfork::Canyon, ForkCondition::Timestamp(10)),
+                (OpHardfork::Ecotone, ForkCondition::Timestamp(ecotone_time)),
+            ])
+        };
+        let schedule = forks(20).unwrap();
+        assert!(schedule.is_ecotone_active_at_timestamp(20));
+        assert!(!schedule.is_delta_active_at_timestamp(u64::MAX));
+        assert_eq!(schedule.active_fork(0, 20), Some(OpHardfork::Ecotone));
+        assert_eq!(
+            forks(5),
+            Err(OpHardforkScheduleError::OutOfOrder {
+                fork: OpHardfork::Ecotone,
+                activation: 5,
+                previous: OpHardfork::Canyon,
+                previous_activation: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn test_schedule_invalid_condition() {
+        let err = OpHardforkSchedule::new([(OpHardfork::Bedrock, ForkCondition::Timestamp(0))]);
+        assert_eq!(
+            err,
+            Err(OpHardforkScheduleError::InvalidCondition(
+                OpHardfork::Bedrock,
+                ForkCondition::Timestamp(0)
+            ))
+        );
+
+        let err = OpHardforkSchedule::new([
+            (OpHardfork::Bedrock, ForkCondition::Block(0)),
+            (OpHardfork::Regolith, ForkCondition::Block(0)),
+        ]);
+        assert_eq!(
+            err,
+            Err(OpHardforkScheduleError::InvalidCondition(
+                OpHardfork::Regolith,
+                ForkCondition::Block(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_upgrade_transactions() {
+        assert!(OpHardfork::Canyon.upgrade_transactions().is_empty());
+        assert!(OpHardfork::Delta.upgrade_transactions().is_empty());
+        assert_eq!(OpHardfork::Ecotone.upgrade_transactions().len(), 6);
+        assert_eq!(OpHardfork::Fjord.upgrade_transactions().len(), 3);
+        assert_eq!(OpHardfork::Isthmus.upgrade_transactions().len(), 8);
+        assert!(OpHardfork::Jovian.upgrade_transactions().is_empty());
+        assert_eq!(OpHardfork::Interop.upgrade_transactions().len(), 4);
+    }
+}
@@ -1,13 +1,191 @@
 //! Commonly used types for interop.
 
-use alloc::string::{String, ToString};
-use alloy_primitives::{Address, address};
+use crate::{OpReceiptEnvelope, OpTxEnvelope};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_consensus::Transaction;
+use alloy_eips::eip2930::{AccessList, AccessListItem};
+use alloy_primitives::{Address, B256, Log, LogData, U256, address, b256, keccak256};
 use core::str::FromStr;
 use derive_more::Display;
 
 /// The address of the L2 cross chain inbox predeploy proxy.
 pub const CROSS_L2_INBOX_ADDRESS: Address = address!("0x4200000000000000000000000000000000000022");
 
+/// The topic of the `ExecutingMessage(bytes32 indexed msgHash, Identifier id)` event emitted by
+/// the [`CROSS_L2_INBOX_ADDRESS`].
+pub const EXECUTING_MESSAGE_EVENT_TOPIC: B256 =
+    b256!("0x5c37832d2e8d10e346e55ad62071a6a2f9fa5130614ef2ec6617555c6f467ba7");
+
+/// The length of the ABI encoding of an [`Identifier`].
+pub const IDENTIFIER_LEN: usize = 32 * 5;
+
+/// An error that can occur when decoding an `ExecutingMessage` event.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutingMessageError {
+    /// The log is not emitted by the [`CROSS_L2_INBOX_ADDRESS`].
+    #[error("log is not emitted by the CrossL2Inbox: {0}")]
+    InvalidAddress(Address),
+    /// The log does not have the `ExecutingMessage` event topics.
+    #[error("invalid ExecutingMessage topics")]
+    InvalidTopics,
+    /// The log data does not have the length of an encoded [`Identifier`].
+    #[error("invalid ExecutingMessage data length: expected {expected}, got {got}")]
+    InvalidDataLength {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        got: usize,
+    },
+    /// A field of the identifier does not fit in its Rust type.
+    #[error("identifier field `{0}` overflows")]
+    FieldOverflow(&'static str),
+}
+
+/// The identifier of an initiating message: a log emitted on the chain with ID `chain_id`, at
+/// `log_index` in the block `block_number` with timestamp `timestamp`, by the `origin` address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Identifier {
+    /// The address that emitted the log.
+    pub origin: Address,
+    /// The number of the block that contains the log.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: u64,
+    /// The index of the log in the block.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub log_index: u32,
+    /// The timestamp of the block that contains the log.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub timestamp: u64,
+    /// The chain ID of the chain that emitted the log.
+    #[cfg_attr(feature = "serde", serde(rename = "chainID", with = "alloy_serde::quantity"))]
+    pub chain_id: u64,
+}
+
+impl Identifier {
+    /// Decodes an identifier from its ABI encoding.
+    pub fn abi_decode(data: &[u8]) -> Result<Self, ExecutingMessageError> {
+        if data.len() != IDENTIFIER_LEN {
+            return Err(ExecutingMessageError::InvalidDataLength {
+                expected: IDENTIFIER_LEN,
+                got: data.len(),
+            });
+        }
+        if data[..12].iter().any(|byte| *byte != 0) {
+            return Err(ExecutingMessageError::FieldOverflow("origin"));
+        }
+        Ok(Self {
+            origin: Address::from_slice(&data[12..32]),
+            block_number: field_from_word(&data[32..64], "block_number")?,
+            log_index: field_from_word(&data[64..96], "log_index")?,
+            timestamp: field_from_word(&data[96..128], "timestamp")?,
+            chain_id: field_from_word(&data[128..160], "chain_id")?,
+        })
+    }
+
+    /// Returns the ABI encoding of the identifier.
+    pub fn abi_encode(&self) -> [u8; IDENTIFIER_LEN] {
+        let mut data = [0; IDENTIFIER_LEN];
+        data[12..32].copy_from_slice(self.origin.as_slice());
+        data[56..64].copy_from_slice(&self.block_number.to_be_bytes());
+        data[92..96].copy_from_slice(&self.log_index.to_be_bytes());
+        data[120..128].copy_from_slice(&self.timestamp.to_be_bytes());
+        data[152..160].copy_from_slice(&self.chain_id.to_be_bytes());
+        data
+    }
+}
+
+/// An executing message, emitted by the [`CROSS_L2_INBOX_ADDRESS`] when a message initiated on
+/// another chain is consumed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ExecutingMessage {
+    /// The payload hash of the initiating message, see [`message_payload_hash`].
+    pub payload_hash: B256,
+    /// The identifier of the initiating message.
+    pub identifier: Identifier,
+}
+
+impl ExecutingMessage {
+    /// Decodes an executing message from an `ExecutingMessage` event log.
+    pub fn decode_log(log: &Log) -> Result<Self, ExecutingMessageError> {
+        if log.address != CROSS_L2_INBOX_ADDRESS {
+            return Err(ExecutingMessageError::InvalidAddress(log.address));
+        }
+        let [topic, payload_hash] = log.topics() else {
+            return Err(ExecutingMessageError::InvalidTopics);
+        };
+        if *topic != EXECUTING_MESSAGE_EVENT_TOPIC {
+            return Err(ExecutingMessageError::InvalidTopics);
+        }
+        Ok(Self {
+            payload_hash: *payload_hash,
+            identifier: Identifier::abi_decode(&log.data.data)?,
+        })
+    }
+
+    /// Returns the `ExecutingMessage` event log of the executing message.
+    pub fn to_log(&self) -> Log {
+        Log {
+            address: CROSS_L2_INBOX_ADDRESS,
+            data: LogData::new_unchecked(
+                alloc::vec![EXECUTING_MESSAGE_EVENT_TOPIC, self.payload_hash],
+                self.identifier.abi_encode().to_vec().into(),
+            ),
+        }
+    }
+}
+
+/// Returns the message payload of an initiating log: the concatenation of its topics and data.
+pub fn message_payload(log: &Log) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(32 * log.topics().len() + log.data.data.len());
+    for topic in log.topics() {
+        payload.extend_from_slice(topic.as_slice());
+    }
+    payload.extend_from_slice(&log.data.data);
+    payload
+}
+
+/// Returns the hash of the message payload of an initiating log, which is referenced by the
+/// executing messages that consume it.
+pub fn message_payload_hash(log: &Log) -> B256 {
+    keccak256(message_payload(log))
+}
+
+/// Reads an integer field from a big-endian 32-byte word.
+fn field_from_word<T: TryFrom<U256>>(
+    word: &[u8],
+    field: &'static str,
+) -> Result<T, ExecutingMessageError> {
+    U256::from_be_slice(word).try_into().map_err(|_| ExecutingMessageError::FieldOverflow(field))
+}
+
+impl OpReceiptEnvelope {
+    /// Returns the executing messages of the `ExecutingMessage` events in the logs of the
+    /// receipt, decoded in log order.
+    ///
+    /// Logs of the [`CROSS_L2_INBOX_ADDRESS`] with other topics are skipped, while malformed
+    /// `ExecutingMessage` events are returned as errors.
+    pub fn executing_messages(
+        &self,
+    ) -> impl Iterator<Item = Result<ExecutingMessage, ExecutingMessageError>> + '_ {
+        self.logs()
+            .iter()
+            .filter(|log| {
+                log.address == CROSS_L2_INBOX_ADDRESS
+                    && log.topics().first() == Some(&EXECUTING_MESSAGE_EVENT_TOPIC)
+            })
+            .map(ExecutingMessage::decode_log)
+    }
+}
+
 /// The safety level of a message.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -50,9 +228,412 @@ impl FromStr for SafetyLevel {
 #[error("Invalid SafetyLevel, error: {0}")]
 pub struct SafetyLevelParseError(pub String);
 
+/// The type prefix of an access-list entry that looks up an initiating message by its chain ID,
+/// block number, timestamp and log index.
+pub const ACCESS_ENTRY_LOOKUP: u8 = 1;
+
+/// The type prefix of an access-list entry that extends the chain ID of the preceding lookup entry
+/// beyond 64 bits.
+pub const ACCESS_ENTRY_CHAIN_ID_EXTENSION: u8 = 2;
+
+/// The type prefix of an access-list entry that holds the checksum of a message.
+pub const ACCESS_ENTRY_CHECKSUM: u8 = 3;
+
+/// An error that can occur when parsing the [`CROSS_L2_INBOX_ADDRESS`] entries of an access list.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum MessageAccessError {
+    /// An entry has an unexpected type prefix.
+    #[error("unexpected access-list entry type {prefix} at index {index}")]
+    UnexpectedEntry {
+        /// The index of the entry among the inbox storage keys.
+        index: usize,
+        /// The type prefix of the entry.
+        prefix: u8,
+    },
+    /// The reserved bytes of an entry are not zero.
+    #[error("non-zero reserved bytes in access-list entry at index {index}")]
+    InvalidPadding {
+        /// The index of the entry among the inbox storage keys.
+        index: usize,
+    },
+    /// A lookup entry is not followed by a checksum entry.
+    #[error("missing checksum for lookup entry at index {index}")]
+    MissingChecksum {
+        /// The index of the lookup entry among the inbox storage keys.
+        index: usize,
+    },
+    /// The chain ID extension sets a chain ID that does not fit in 64 bits.
+    #[error("chain ID of access-list entry at index {index} overflows")]
+    ChainIdOverflow {
+        /// The index of the chain ID extension entry among the inbox storage keys.
+        index: usize,
+    },
+}
+
+/// The declaration of an executing message in the access list of a transaction: the lookup fields
+/// of the [`Identifier`] of the initiating message, and the checksum of the message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MessageAccess {
+    /// The chain ID of the initiating message.
+    pub chain_id: u64,
+    /// The block number of the initiating message.
+    pub block_number: u64,
+    /// The timestamp of the initiating message.
+    pub timestamp: u64,
+    /// The log index of the initiating message.
+    pub log_index: u32,
+    /// The checksum of the message, see [`ExecutingMessage::checksum`].
+    pub checksum: B256,
+}
+
+impl MessageAccess {
+    /// Returns the lookup entry of the access.
+    pub fn lookup_entry(&self) -> B256 {
+        let mut entry = B256::ZERO;
+        entry[0] = ACCESS_ENTRY_LOOKUP;
+        entry[4..12].copy_from_slice(&self.chain_id.to_be_bytes());
+        entry[12..20].copy_from_slice(&self.block_number.to_be_bytes());
+        entry[20..28].copy_from_slice(&self.timestamp.to_be_bytes());
+        entry[28..32].copy_from_slice(&self.log_index.to_be_bytes());
+        entry
+    }
+
+    /// Returns the access-list storage keys of the access: the lookup entry followed by the
+    /// checksum. A chain ID extension entry is never needed for 64-bit chain IDs.
+    pub fn storage_keys(&self) -> [B256; 2] {
+        [self.lookup_entry(), self.checksum]
+    }
+}
+
+impl From<&ExecutingMessage> for MessageAccess {
+    fn from(message: &ExecutingMessage) -> Self {
+        let Identifier { block_number, log_index, timestamp, chain_id, .. } = message.identifier;
+        Self { chain_id, block_number, timestamp, log_index, checksum: message.checksum() }
+    }
+}
+
+impl ExecutingMessage {
+    /// Returns the checksum of the message, which commits to its full identifier and payload hash.
+    ///
+    /// The checksum is `keccak256(keccak256(logHash ++ idPacked) ++ chainId)` with its first byte
+    /// replaced by [`ACCESS_ENTRY_CHECKSUM`], where `logHash` is `keccak256(origin ++ payloadHash)`
+    /// and `idPacked` is the block number, timestamp and log index packed at the end of a 32-byte
+    /// word.
+    pub fn checksum(&self) -> B256 {
+        let id = &self.identifier;
+        let log_hash = keccak256([id.origin.as_slice(), self.payload_hash.as_slice()].concat());
+
+        let mut id_packed = [0u8; 32];
+        id_packed[12..20].copy_from_slice(&id.block_number.to_be_bytes());
+        id_packed[20..28].copy_from_slice(&id.timestamp.to_be_bytes());
+        id_packed[28..32].copy_from_slice(&id.log_index.to_be_bytes());
+        let id_log_hash = keccak256([log_hash.as_slice(), &id_packed].concat());
+
+        let chain_id = U256::from(id.chain_id).to_be_bytes::<32>();
+        let mut checksum = keccak256([id_log_hash.as_slice(), &chain_id].concat());
+        checksum[0] = ACCESS_ENTRY_CHECKSUM;
+        checksum
+    }
+}
+
+/// Builds the [`CROSS_L2_INBOX_ADDRESS`] access-list item that declares the given executing
+/// messages.
+pub fn message_access_list_item<'a>(
+    messages: impl IntoIterator<Item = &'a ExecutingMessage>,
+) -> AccessListItem {
+    AccessListItem {
+        address: CROSS_L2_INBOX_ADDRESS,
+        storage_keys: messages
+            .into_iter()
+            .flat_map(|message| MessageAccess::from(message).storage_keys())
+            .collect(),
+    }
+}
+
+/// Parses the message accesses declared by the [`CROSS_L2_INBOX_ADDRESS`] items of an access list.
+///
+/// The storage keys of all inbox items are parsed in order, as a sequence of lookup entries, each
+/// optionally followed by a chain ID extension and always followed by a checksum.
+pub fn parse_message_access_list(
+    access_list: &AccessList,
+) -> Result<Vec<MessageAccess>, MessageAccessError> {
+    let mut entries = access_list
+        .iter()
+        .filter(|item| item.address == CROSS_L2_INBOX_ADDRESS)
+        .flat_map(|item| &item.storage_keys)
+        .enumerate()
+        .peekable();
+
+    let mut accesses = Vec::new();
+    while let Some((index, entry)) = entries.next() {
+        if entry[0] != ACCESS_ENTRY_LOOKUP {
+            return Err(MessageAccessError::UnexpectedEntry { index, prefix: entry[0] });
+        }
+        if entry[1..4].iter().any(|byte| *byte != 0) {
+            return Err(MessageAccessError::InvalidPadding { index });
+        }
+        let word = |range: core::ops::Range<usize>| {
+            u64::from_be_bytes(entry[range].try_into().expect("sufficient length"))
+        };
+        let (chain_id, block_number, timestamp) = (word(4..12), word(12..20), word(20..28));
+        let log_index = u32::from_be_bytes(entry[28..32].try_into().expect("sufficient length"));
+
+        if let Some((extension_index, extension)) =
+            entries.next_if(|(_, entry)| entry[0] == ACCESS_ENTRY_CHAIN_ID_EXTENSION)
+        {
+            if extension[1..8].iter().any(|byte| *byte != 0) {
+                return Err(MessageAccessError::InvalidPadding { index: extension_index });
+            }
+            if extension[8..].iter().any(|byte| *byte != 0) {
+                return Err(MessageAccessError::ChainIdOverflow { index: extension_index });
+            }
+        }
+
+        let checksum = match entries.next() {
+            Some((_, checksum)) if checksum[0] == ACCESS_ENTRY_CHECKSUM => *checksum,
+            _ => return Err(MessageAccessError::MissingChecksum { index }),
+        };
+        accesses.push(MessageAccess { chain_id, block_number, timestamp, log_index, checksum });
+    }
+    Ok(accesses)
+}
+
+impl OpTxEnvelope {
+    /// Returns the executing message accesses declared in the access list of the transaction, or
+    /// an empty list for transactions without an access list.
+    pub fn message_accesses(&self) -> Result<Vec<MessageAccess>, MessageAccessError> {
+        self.access_list().map_or(Ok(Vec::new()), parse_message_access_list)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{Bytes, hex};
+
+    fn identifier() -> Identifier {
+        Identifier {
+            origin: address!("0x4200000000000000000000000000000000000023"),
+            block_number: 123_456,
+            log_index: 7,
+            timestamp: 1_735_000_000,
+            chain_id: 10,
+        }
+    }
+
+    fn initiating_log() -> Log {
+        Log {
+            address: address!("0x4200000000000000000000000000000000000016"),
+            data: LogData::new_unchecked(
+                vec![
+                    b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+                    b256!("0x0000000000000000000000004200000000000000000000000000000000000016"),
+                ],
+                Bytes::copy_from_slice(&U256::from(1000).to_be_bytes::<32>()),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_message_payload_hash() {
+        let log = initiating_log();
+        assert_eq!(message_payload(&log).len(), 96);
+        assert_eq!(
+            message_payload_hash(&log),
+            b256!("0xf11ce0039512420857bc54c33e2806945798aad26532a3f89bc43797e3e6e2cf")
+        );
+    }
+
+    #[test]
+    fn test_identifier_abi_roundtrip() {
+        let encoded = identifier().abi_encode();
+        assert_eq!(
+            encoded,
+            hex!(
+                "0000000000000000000000004200000000000000000000000000000000000023"
+                "000000000000000000000000000000000000000000000000000000000001e240"
+                "0000000000000000000000000000000000000000000000000000000000000007"
+                "000000000000000000000000000000000000000000000000000000006769ffc0"
+                "000000000000000000000000000000000000000000000000000000000000000a"
+            )
+        );
+        assert_eq!(Identifier::abi_decode(&encoded), Ok(identifier()));
+
+        let mut overflow = encoded;
+        overflow[64] = 1;
+        assert_eq!(
+            Identifier::abi_decode(&overflow),
+            Err(ExecutingMessageError::FieldOverflow("log_index"))
+        );
+        let mut dirty = encoded;
+        dirty[0] = 1;
+        assert_eq!(
+            Identifier::abi_decode(&dirty),
+            Err(ExecutingMessageError::FieldOverflow("origin"))
+        );
+        assert_eq!(
+            Identifier::abi_decode(&encoded[1..]),
+            Err(ExecutingMessageError::InvalidDataLength { expected: IDENTIFIER_LEN, got: 159 })
+        );
+    }
+
+    #[test]
+    fn test_decode_executing_message() {
+        let message = ExecutingMessage {
+            payload_hash: message_payload_hash(&initiating_log()),
+            identifier: identifier(),
+        };
+        let log = message.to_log();
+        assert_eq!(ExecutingMessage::decode_log(&log), Ok(message));
+
+        let mut invalid = log.clone();
+        invalid.address = Address::ZERO;
+        assert_eq!(
+            ExecutingMessage::decode_log(&invalid),
+            Err(ExecutingMessageError::InvalidAddress(Address::ZERO))
+        );
+        let mut invalid = log;
+        invalid.data.set_topics_unchecked(vec![EXECUTING_MESSAGE_EVENT_TOPIC]);
+        assert_eq!(
+            ExecutingMessage::decode_log(&invalid),
+            Err(ExecutingMessageError::InvalidTopics)
+        );
+    }
+
+    #[test]
+    fn test_receipt_executing_messages() {
+        let message =
+            ExecutingMessage { payload_hash: B256::repeat_byte(1), identifier: identifier() };
+        let mut malformed = message.to_log();
+        malformed.data.data = Bytes::new();
+        let mut other_inbox_log = initiating_log();
+        other_inbox_log.address = CROSS_L2_INBOX_ADDRESS;
+        let receipt = OpReceiptEnvelope::Eip1559(ReceiptWithBloom::from(Receipt {
+            status: true.into(),
+            cumulative_gas_used: 100_000,
+            logs: vec![initiating_log(), message.to_log(), other_inbox_log, malformed],
+        }));
+
+        let messages: Vec<_> = receipt.executing_messages().collect();
+        assert_eq!(
+            messages,
+            vec![
+                Ok(message),
+                Err(ExecutingMessageError::InvalidDataLength { expected: IDENTIFIER_LEN, got: 0 })
+            ]
+        );
+    }
+
+    fn executing_message() -> ExecutingMessage {
+        ExecutingMessage {
+            payload_hash: message_payload_hash(&initiating_log()),
+            identifier: identifier(),
+        }
+    }
+
+    #[test]
+    fn test_message_checksum() {
+        let access = MessageAccess::from(&executing_message());
+        assert_eq!(
+            access.checksum,
+            b256!("0x03f4c4f26ec7f5890de4e24fba6fc85acc8f9d6b0aef66bed56e4294ab1858d6")
+        );
+        assert_eq!(
+            access.lookup_entry(),
+            b256!("0x01000000000000000000000a000000000001e240000000006769ffc000000007")
+        );
+    }
+
+    #[test]
+    fn test_message_access_list_roundtrip() {
+        let messages = [
+            executing_message(),
+            ExecutingMessage {
+                payload_hash: B256::repeat_byte(1),
+                identifier: Identifier { chain_id: 8453, ..identifier() },
+            },
+        ];
+        let item = message_access_list_item(&messages);
+        assert_eq!(item.storage_keys.len(), 4);
+
+        let access_list = AccessList(vec![
+            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
+            item,
+        ]);
+        let expected: Vec<_> = messages.iter().map(MessageAccess::from).collect();
+        assert_eq!(parse_message_access_list(&access_list), Ok(expected.clone()));
+
+        let tx = OpTxEnvelope::Eip1559(alloy_consensus::Signed::new_unchecked(
+            alloy_consensus::TxEip1559 { access_list, ..Default::default() },
+            alloy_primitives::Signature::test_signature(),
+            B256::ZERO,
+        ));
+        assert_eq!(tx.message_accesses(), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_message_access_list_chain_id_extension() {
+        let access = MessageAccess::from(&executing_message());
+        let mut extension = B256::ZERO;
+        extension[0] = ACCESS_ENTRY_CHAIN_ID_EXTENSION;
+        let parse = |storage_keys| {
+            parse_message_access_list(&AccessList(vec![AccessListItem {
+                address: CROSS_L2_INBOX_ADDRESS,
+                storage_keys,
+            }]))
+        };
+
+        assert_eq!(
+            parse(vec![access.lookup_entry(), extension, access.checksum]),
+            Ok(vec![access])
+        );
+        extension[31] = 1;
+        assert_eq!(
+            parse(vec![access.lookup_entry(), extension, access.checksum]),
+            Err(MessageAccessError::ChainIdOverflow { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_parse_message_access_list_errors() {
+        let access = MessageAccess::from(&executing_message());
+        let parse = |storage_keys| {
+            parse_message_access_list(&AccessList(vec![AccessListItem {
+                address: CROSS_L2_INBOX_ADDRESS,
+                storage_keys,
+            }]))
+        };
+
+        assert_eq!(
+            parse(vec![access.checksum]),
+            Err(MessageAccessError::UnexpectedEntry { index: 0, prefix: ACCESS_ENTRY_CHECKSUM })
+        );
+        assert_eq!(
+            parse(vec![access.lookup_entry()]),
+            Err(MessageAccessError::MissingChecksum { index: 0 })
+        );
+        assert_eq!(
+            parse(vec![access.lookup_entry(), access.lookup_entry()]),
+            Err(MessageAccessError::MissingChecksum { index: 0 })
+        );
+        let mut lookup = access.lookup_entry();
+        lookup[2] = 1;
+        assert_eq!(
+            parse(vec![access.lookup_entry(), access.checksum, lookup, access.checksum]),
+            Err(MessageAccessError::InvalidPadding { index: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_identifier_serde() {
+        let json = r#"{"origin":"0x4200000000000000000000000000000000000023","blockNumber":"0x1e240","logIndex":"0x7","timestamp":"0x6769ffc0","chainID":"0xa"}"#;
+        assert_eq!(serde_json::from_str::<Identifier>(json).unwrap(), identifier());
+        assert_eq!(serde_json::to_string(&identifier()).unwrap(), json);
+    }
 
     #[test]
     #[cfg(feature = "serde")]
@@ -0,0 +1,792 @@
+//! Calldata of the L1 attributes deposit transaction, which is the first transaction of every
+//! L2 block.
+//!
+//! See: <https://specs.optimism.io/protocol/deposits.html#l1-attributes-deposited-transaction>
+
+use crate::{L1InfoDepositSource, TxDeposit, abi, predeploys::L1_BLOCK_ADDRESS};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, Bytes, Selector, TxKind, U256, address, fixed_bytes};
+
+/// The address of the account that sends the L1 attributes deposit transaction.
+pub const L1_INFO_DEPOSITOR_ADDRESS: Address =
+    address!("0xDeaDDEaDDeAdDeAdDEAdDEaddeAddEAdDEAd0001");
+
+/// The selector of the Bedrock `setL1BlockValues(uint64,uint64,uint256,bytes32,uint64,bytes32,
+/// uint256,uint256)` function.
+pub const L1_INFO_SELECTOR_BEDROCK: Selector = fixed_bytes!("0x015d8eb9");
+
+/// The selector of the Ecotone `setL1BlockValuesEcotone()` function.
+pub const L1_INFO_SELECTOR_ECOTONE: Selector = fixed_bytes!("0x440a5e20");
+
+/// The selector of the Isthmus `setL1BlockValuesIsthmus()` function.
+pub const L1_INFO_SELECTOR_ISTHMUS: Selector = fixed_bytes!("0x098999be");
+
+/// The selector of the Jovian `setL1BlockValuesJovian()` function.
+pub const L1_INFO_SELECTOR_JOVIAN: Selector = fixed_bytes!("0x3db6be2b");
+
+/// The length of the Bedrock L1 attributes calldata: a selector and 8 ABI-encoded words.
+pub const L1_INFO_LEN_BEDROCK: usize = 4 + 32 * 8;
+
+/// The length of the tightly packed Ecotone L1 attributes calldata.
+pub const L1_INFO_LEN_ECOTONE: usize = 4 + 4 + 4 + 8 + 8 + 8 + 32 * 4;
+
+/// The length of the tightly packed Isthmus L1 attributes calldata, which extends the Ecotone
+/// layout with the operator fee parameters.
+pub const L1_INFO_LEN_ISTHMUS: usize = L1_INFO_LEN_ECOTONE + 4 + 8;
+
+/// The length of the tightly packed Jovian L1 attributes calldata, which extends the Isthmus
+/// layout with the DA footprint gas scalar.
+pub const L1_INFO_LEN_JOVIAN: usize = L1_INFO_LEN_ISTHMUS + 2;
+
+/// The DA footprint gas scalar that is used since Jovian if the system config sets none.
+pub const DEFAULT_DA_FOOTPRINT_GAS_SCALAR: u16 = 400;
+
+/// The gas limit of the L1 attributes deposit transaction since Regolith.
+pub const REGOLITH_SYSTEM_TX_GAS: u64 = 1_000_000;
+
+/// The gas limit of the L1 attributes deposit transaction before Regolith, where it was a system
+/// transaction.
+pub const BEDROCK_SYSTEM_TX_GAS: u64 = 150_000_000;
+
+/// An error that can occur when decoding L1 attributes calldata.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum L1BlockInfoError {
+    /// The calldata does not have the expected length.
+    #[error("invalid L1 info calldata length: expected {expected}, got {got}")]
+    InvalidLength {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        got: usize,
+    },
+    /// The calldata starts with an unknown function selector.
+    #[error("unknown L1 info function selector: {0}")]
+    UnknownSelector(Selector),
+    /// A field does not fit in its Rust type.
+    #[error("L1 info field `{0}` overflows")]
+    FieldOverflow(&'static str),
+}
+
+/// The L1 attributes of a block, decoded from the calldata of its L1 attributes deposit
+/// transaction. The calldata layout depends on the active hardfork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum L1BlockInfoTx {
+    /// The Bedrock `setL1BlockValues` calldata.
+    Bedrock(L1BlockInfoBedrock),
+    /// The Ecotone `setL1BlockValuesEcotone` calldata.
+    Ecotone(L1BlockInfoEcotone),
+    /// The Isthmus `setL1BlockValuesIsthmus` calldata.
+    Isthmus(L1BlockInfoIsthmus),
+    /// The Jovian `setL1BlockValuesJovian` calldata.
+    Jovian(L1BlockInfoJovian),
+}
+
+impl L1BlockInfoTx {
+    /// Decodes the L1 attributes from the calldata of an L1 attributes deposit transaction,
+    /// selecting the layout by its function selector.
+    pub fn decode_calldata(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        let selector = selector(data)?;
+        match selector {
+            L1_INFO_SELECTOR_BEDROCK => {
+                L1BlockInfoBedrock::decode_calldata(data).map(Self::Bedrock)
+            }
+            L1_INFO_SELECTOR_ECOTONE => {
+                L1BlockInfoEcotone::decode_calldata(data).map(Self::Ecotone)
+            }
+            L1_INFO_SELECTOR_ISTHMUS => {
+                L1BlockInfoIsthmus::decode_calldata(data).map(Self::Isthmus)
+            }
+            L1_INFO_SELECTOR_JOVIAN => L1BlockInfoJovian::decode_calldata(data).map(Self::Jovian),
+            _ => Err(L1BlockInfoError::UnknownSelector(selector)),
+        }
+    }
+
+    /// Encodes the L1 attributes into the calldata of an L1 attributes deposit transaction.
+    pub fn encode_calldata(&self) -> Bytes {
+        match self {
+            Self::Bedrock(info) => info.encode_calldata(),
+            Self::Ecotone(info) => info.encode_calldata(),
+            Self::Isthmus(info) => info.encode_calldata(),
+            Self::Jovian(info) => info.encode_calldata(),
+        }
+    }
+
+    /// Builds the L1 attributes deposit transaction that carries these attributes.
+    ///
+    /// Before Regolith, the transaction is a system transaction with a large gas limit that is not
+    /// charged to the block. Since Regolith, it is a regular deposit with a fixed gas limit.
+    pub fn to_deposit(&self, is_regolith_active: bool) -> TxDeposit {
+        let source = L1InfoDepositSource::new(self.block_hash(), self.sequence_number());
+        TxDeposit {
+            source_hash: source.source_hash(),
+            from: L1_INFO_DEPOSITOR_ADDRESS,
+            to: TxKind::Call(L1_BLOCK_ADDRESS),
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit: if is_regolith_active {
+                REGOLITH_SYSTEM_TX_GAS
+            } else {
+                BEDROCK_SYSTEM_TX_GAS
+            },
+            is_system_transaction: !is_regolith_active,
+            input: self.encode_calldata(),
+        }
+    }
+
+    /// Returns the L1 origin block number.
+    pub const fn number(&self) -> u64 {
+        match self {
+            Self::Bedrock(info) => info.number,
+            Self::Ecotone(info) => info.number,
+            Self::Isthmus(info) => info.number,
+            Self::Jovian(info) => info.number,
+        }
+    }
+
+    /// Returns the L1 origin block timestamp.
+    pub const fn time(&self) -> u64 {
+        match self {
+            Self::Bedrock(info) => info.time,
+            Self::Ecotone(info) => info.time,
+            Self::Isthmus(info) => info.time,
+            Self::Jovian(info) => info.time,
+        }
+    }
+
+    /// Returns the L1 origin block base fee.
+    pub const fn base_fee(&self) -> u64 {
+        match self {
+            Self::Bedrock(info) => info.base_fee,
+            Self::Ecotone(info) => info.base_fee,
+            Self::Isthmus(info) => info.base_fee,
+            Self::Jovian(info) => info.base_fee,
+        }
+    }
+
+    /// Returns the L1 origin block hash.
+    pub const fn block_hash(&self) -> B256 {
+        match self {
+            Self::Bedrock(info) => info.block_hash,
+            Self::Ecotone(info) => info.block_hash,
+            Self::Isthmus(info) => info.block_hash,
+            Self::Jovian(info) => info.block_hash,
+        }
+    }
+
+    /// Returns the number of L2 blocks since the start of the epoch.
+    pub const fn sequence_number(&self) -> u64 {
+        match self {
+            Self::Bedrock(info) => info.sequence_number,
+            Self::Ecotone(info) => info.sequence_number,
+            Self::Isthmus(info) => info.sequence_number,
+            Self::Jovian(info) => info.sequence_number,
+        }
+    }
+
+    /// Returns the batcher address of the system config.
+    pub const fn batcher_address(&self) -> Address {
+        match self {
+            Self::Bedrock(info) => info.batcher_address,
+            Self::Ecotone(info) => info.batcher_address,
+            Self::Isthmus(info) => info.batcher_address,
+            Self::Jovian(info) => info.batcher_address,
+        }
+    }
+
+    /// Returns the L1 origin block blob base fee, if present.
+    ///
+    /// Always `None` before Ecotone.
+    pub const fn blob_base_fee(&self) -> Option<u128> {
+        match self {
+            Self::Bedrock(_) => None,
+            Self::Ecotone(info) => Some(info.blob_base_fee),
+            Self::Isthmus(info) => Some(info.blob_base_fee),
+            Self::Jovian(info) => Some(info.blob_base_fee),
+        }
+    }
+
+    /// Returns the operator fee scalar and constant, if present.
+    ///
+    /// Always `None` before Isthmus.
+    pub const fn operator_fee_params(&self) -> Option<(u32, u64)> {
+        match self {
+            Self::Bedrock(_) | Self::Ecotone(_) => None,
+            Self::Isthmus(info) => Some((info.operator_fee_scalar, info.operator_fee_constant)),
+            Self::Jovian(info) => Some((info.operator_fee_scalar, info.operator_fee_constant)),
+        }
+    }
+
+    /// Returns the DA footprint gas scalar, if present.
+    ///
+    /// Always `None` before Jovian.
+    pub const fn da_footprint_gas_scalar(&self) -> Option<u16> {
+        match self {
+            Self::Jovian(info) => Some(info.da_footprint_gas_scalar),
+            _ => None,
+        }
+    }
+
+    /// Returns the Ecotone subset of the L1 attributes, if present.
+    ///
+    /// Always `None` before Ecotone.
+    pub const fn as_ecotone(&self) -> Option<L1BlockInfoEcotone> {
+        match self {
+            Self::Bedrock(_) => None,
+            Self::Ecotone(info) => Some(*info),
+            Self::Isthmus(info) => Some(info.as_ecotone()),
+            Self::Jovian(info) => Some(info.as_isthmus().as_ecotone()),
+        }
+    }
+}
+
+/// The Bedrock L1 attributes, ABI-encoded as arguments of `setL1BlockValues`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct L1BlockInfoBedrock {
+    /// The L1 origin block number.
+    pub number: u64,
+    /// The L1 origin block timestamp.
+    pub time: u64,
+    /// The L1 origin block base fee.
+    pub base_fee: u64,
+    /// The L1 origin block hash.
+    pub block_hash: B256,
+    /// The number of L2 blocks since the start of the epoch.
+    pub sequence_number: u64,
+    /// The batcher address of the system config.
+    pub batcher_address: Address,
+    /// The L1 fee overhead of the system config.
+    pub l1_fee_overhead: U256,
+    /// The L1 fee scalar of the system config.
+    pub l1_fee_scalar: U256,
+}
+
+impl L1BlockInfoBedrock {
+    /// Decodes the Bedrock L1 attributes calldata, including the function selector.
+    pub fn decode_calldata(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        check_calldata(data, L1_INFO_SELECTOR_BEDROCK, L1_INFO_LEN_BEDROCK)?;
+        let word = |i: usize| &data[4 + 32 * i..4 + 32 * (i + 1)];
+        Ok(Self {
+            number: u64_from_word(word(0), "number")?,
+            time: u64_from_word(word(1), "time")?,
+            base_fee: u64_from_word(word(2), "base_fee")?,
+            block_hash: B256::from_slice(word(3)),
+            sequence_number: u64_from_word(word(4), "sequence_number")?,
+            batcher_address: address_from_word(word(5), "batcher_address")?,
+            l1_fee_overhead: U256::from_be_slice(word(6)),
+            l1_fee_scalar: U256::from_be_slice(word(7)),
+        })
+    }
+
+    /// Encodes the Bedrock L1 attributes calldata, including the function selector.
+    pub fn encode_calldata(&self) -> Bytes {
+        let mut buf = Vec::with_capacity(L1_INFO_LEN_BEDROCK);
+        buf.extend_from_slice(L1_INFO_SELECTOR_BEDROCK.as_slice());
+        buf.extend_from_slice(&U256::from(self.number).to_be_bytes::<32>());
+        buf.extend_from_slice(&U256::from(self.time).to_be_bytes::<32>());
+        buf.extend_from_slice(&U256::from(self.base_fee).to_be_bytes::<32>());
+        buf.extend_from_slice(self.block_hash.as_slice());
+        buf.extend_from_slice(&U256::from(self.sequence_number).to_be_bytes::<32>());
+        buf.extend_from_slice(self.batcher_address.into_word().as_slice());
+        buf.extend_from_slice(&self.l1_fee_overhead.to_be_bytes::<32>());
+        buf.extend_from_slice(&self.l1_fee_scalar.to_be_bytes::<32>());
+        buf.into()
+    }
+}
+
+/// The Ecotone L1 attributes, tightly packed as the calldata of `setL1BlockValuesEcotone`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct L1BlockInfoEcotone {
+    /// The L1 origin block number.
+    pub number: u64,
+    /// The L1 origin block timestamp.
+    pub time: u64,
+    /// The L1 origin block base fee.
+    pub base_fee: u64,
+    /// The L1 origin block hash.
+    pub block_hash: B256,
+    /// The number of L2 blocks since the start of the epoch.
+    pub sequence_number: u64,
+    /// The batcher address of the system config.
+    pub batcher_address: Address,
+    /// The L1 origin block blob base fee.
+    pub blob_base_fee: u128,
+    /// The base fee scalar of the system config.
+    pub base_fee_scalar: u32,
+    /// The blob base fee scalar of the system config.
+    pub blob_base_fee_scalar: u32,
+}
+
+impl L1BlockInfoEcotone {
+    /// Decodes the Ecotone L1 attributes calldata, including the function selector.
+    pub fn decode_calldata(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        check_calldata(data, L1_INFO_SELECTOR_ECOTONE, L1_INFO_LEN_ECOTONE)?;
+        Self::decode_packed(data)
+    }
+
+    /// Decodes the packed fields shared by the Ecotone and Isthmus layouts, skipping the selector.
+    fn decode_packed(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        Ok(Self {
+            base_fee_scalar: u32::from_be_bytes(data[4..8].try_into().expect("sufficient length")),
+            blob_base_fee_scalar: u32::from_be_bytes(
+                data[8..12].try_into().expect("sufficient length"),
+            ),
+            sequence_number: u64::from_be_bytes(
+                data[12..20].try_into().expect("sufficient length"),
+            ),
+            time: u64::from_be_bytes(data[20..28].try_into().expect("sufficient length")),
+            number: u64::from_be_bytes(data[28..36].try_into().expect("sufficient length")),
+            base_fee: u64_from_word(&data[36..68], "base_fee")?,
+            blob_base_fee: u128_from_word(&data[68..100], "blob_base_fee")?,
+            block_hash: B256::from_slice(&data[100..132]),
+            batcher_address: address_from_word(&data[132..164], "batcher_address")?,
+        })
+    }
+
+    /// Encodes the Ecotone L1 attributes calldata, including the function selector.
+    pub fn encode_calldata(&self) -> Bytes {
+        let mut buf = Vec::with_capacity(L1_INFO_LEN_ECOTONE);
+        buf.extend_from_slice(L1_INFO_SELECTOR_ECOTONE.as_slice());
+        self.encode_packed(&mut buf);
+        buf.into()
+    }
+
+    /// Encodes the packed fields shared by the Ecotone and Isthmus layouts, without a selector.
+    fn encode_packed(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.base_fee_scalar.to_be_bytes());
+        buf.extend_from_slice(&self.blob_base_fee_scalar.to_be_bytes());
+        buf.extend_from_slice(&self.sequence_number.to_be_bytes());
+        buf.extend_from_slice(&self.time.to_be_bytes());
+        buf.extend_from_slice(&self.number.to_be_bytes());
+        buf.extend_from_slice(&U256::from(self.base_fee).to_be_bytes::<32>());
+        buf.extend_from_slice(&U256::from(self.blob_base_fee).to_be_bytes::<32>());
+        buf.extend_from_slice(self.block_hash.as_slice());
+        buf.extend_from_slice(self.batcher_address.into_word().as_slice());
+    }
+}
+
+/// The Isthmus L1 attributes, tightly packed as the calldata of `setL1BlockValuesIsthmus`.
+///
+/// Extends the Ecotone layout with the operator fee parameters of the system config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct L1BlockInfoIsthmus {
+    /// The L1 origin block number.
+    pub number: u64,
+    /// The L1 origin block timestamp.
+    pub time: u64,
+    /// The L1 origin block base fee.
+    pub base_fee: u64,
+    /// The L1 origin block hash.
+    pub block_hash: B256,
+    /// The number of L2 blocks since the start of the epoch.
+    pub sequence_number: u64,
+    /// The batcher address of the system config.
+    pub batcher_address: Address,
+    /// The L1 origin block blob base fee.
+    pub blob_base_fee: u128,
+    /// The base fee scalar of the system config.
+    pub base_fee_scalar: u32,
+    /// The blob base fee scalar of the system config.
+    pub blob_base_fee_scalar: u32,
+    /// The operator fee scalar of the system config.
+    pub operator_fee_scalar: u32,
+    /// The operator fee constant of the system config.
+    pub operator_fee_constant: u64,
+}
+
+impl L1BlockInfoIsthmus {
+    /// Decodes the Isthmus L1 attributes calldata, including the function selector.
+    pub fn decode_calldata(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        check_calldata(data, L1_INFO_SELECTOR_ISTHMUS, L1_INFO_LEN_ISTHMUS)?;
+        let ecotone = L1BlockInfoEcotone::decode_packed(data)?;
+        let operator_fee_scalar = u32::from_be_bytes(
+            data[L1_INFO_LEN_ECOTONE..L1_INFO_LEN_ECOTONE + 4]
+                .try_into()
+                .expect("sufficient length"),
+        );
+        let operator_fee_constant = u64::from_be_bytes(
+            data[L1_INFO_LEN_ECOTONE + 4..].try_into().expect("sufficient length"),
+        );
+        Ok(Self::from_ecotone(ecotone, operator_fee_scalar, operator_fee_constant))
+    }
+
+    /// Encodes the Isthmus L1 attributes calldata, including the function selector.
+    pub fn encode_calldata(&self) -> Bytes {
+        let mut buf = Vec::with_capacity(L1_INFO_LEN_ISTHMUS);
+        buf.extend_from_slice(L1_INFO_SELECTOR_ISTHMUS.as_slice());
+        self.as_ecotone().encode_packed(&mut buf);
+        buf.extend_from_slice(&self.operator_fee_scalar.to_be_bytes());
+        buf.extend_from_slice(&self.operator_fee_constant.to_be_bytes());
+        buf.into()
+    }
+
+    /// Creates the Isthmus L1 attributes from the Ecotone attributes and the operator fee
+    /// parameters.
+    pub const fn from_ecotone(
+        info: L1BlockInfoEcotone,
+        operator_fee_scalar: u32,
+        operator_fee_constant: u64,
+    ) -> Self {
+        Self {
+            number: info.number,
+            time: info.time,
+            base_fee: info.base_fee,
+            block_hash: info.block_hash,
+            sequence_number: info.sequence_number,
+            batcher_address: info.batcher_address,
+            blob_base_fee: info.blob_base_fee,
+            base_fee_scalar: info.base_fee_scalar,
+            blob_base_fee_scalar: info.blob_base_fee_scalar,
+            operator_fee_scalar,
+            operator_fee_constant,
+        }
+    }
+
+    /// Returns the Ecotone subset of the Isthmus L1 attributes.
+    pub const fn as_ecotone(&self) -> L1BlockInfoEcotone {
+        L1BlockInfoEcotone {
+            number: self.number,
+            time: self.time,
+            base_fee: self.base_fee,
+            block_hash: self.block_hash,
+            sequence_number: self.sequence_number,
+            batcher_address: self.batcher_address,
+            blob_base_fee: self.blob_base_fee,
+            base_fee_scalar: self.base_fee_scalar,
+            blob_base_fee_scalar: self.blob_base_fee_scalar,
+        }
+    }
+}
+
+/// The Jovian L1 attributes, tightly packed as the calldata of `setL1BlockValuesJovian`.
+///
+/// Extends the Isthmus layout with the DA footprint gas scalar of the system config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct L1BlockInfoJovian {
+    /// The L1 origin block number.
+    pub number: u64,
+    /// The L1 origin block timestamp.
+    pub time: u64,
+    /// The L1 origin block base fee.
+    pub base_fee: u64,
+    /// The L1 origin block hash.
+    pub block_hash: B256,
+    /// The number of L2 blocks since the start of the epoch.
+    pub sequence_number: u64,
+    /// The batcher address of the system config.
+    pub batcher_address: Address,
+    /// The L1 origin block blob base fee.
+    pub blob_base_fee: u128,
+    /// The base fee scalar of the system config.
+    pub base_fee_scalar: u32,
+    /// The blob base fee scalar of the system config.
+    pub blob_base_fee_scalar: u32,
+    /// The operator fee scalar of the system config.
+    pub operator_fee_scalar: u32,
+    /// The operator fee constant of the system config.
+    pub operator_fee_constant: u64,
+    /// The DA footprint gas scalar of the system config.
+    pub da_footprint_gas_scalar: u16,
+}
+
+impl L1BlockInfoJovian {
+    /// Decodes the Jovian L1 attributes calldata, including the function selector.
+    pub fn decode_calldata(data: &[u8]) -> Result<Self, L1BlockInfoError> {
+        check_calldata(data, L1_INFO_SELECTOR_JOVIAN, L1_INFO_LEN_JOVIAN)?;
+        let ecotone = L1BlockInfoEcotone::decode_packed(data)?;
+        let operator_fee_scalar = u32::from_be_bytes(
+            data[L1_INFO_LEN_ECOTONE..L1_INFO_LEN_ECOTONE + 4]
+                .try_into()
+                .expect("sufficient length"),
+        );
+        let operator_fee_constant = u64::from_be_bytes(
+            data[L1_INFO_LEN_ECOTONE + 4..L1_INFO_LEN_ISTHMUS]
+                .try_into()
+                .expect("sufficient length"),
+        );
+        let da_footprint_gas_scalar =
+            u16::from_be_bytes(data[L1_INFO_LEN_ISTHMUS..].try_into().expect("sufficient length"));
+        Ok(Self::from_isthmus(
+            L1BlockInfoIsthmus::from_ecotone(ecotone, operator_fee_scalar, operator_fee_constant),
+            da_footprint_gas_scalar,
+        ))
+    }
+
+    /// Encodes the Jovian L1 attributes calldata, including the function selector.
+    pub fn encode_calldata(&self) -> Bytes {
+        let mut buf = Vec::with_capacity(L1_INFO_LEN_JOVIAN);
+        buf.extend_from_slice(L1_INFO_SELECTOR_JOVIAN.as_slice());
+        self.as_isthmus().as_ecotone().encode_packed(&mut buf);
+        buf.extend_from_slice(&self.operator_fee_scalar.to_be_bytes());
+        buf.extend_from_slice(&self.operator_fee_constant.to_be_bytes());
+        buf.extend_from_slice(&self.da_footprint_gas_scalar.to_be_bytes());
+        buf.into()
+    }
+
+    /// Creates the Jovian L1 attributes from the Isthmus attributes and the DA footprint gas
+    /// scalar.
+    pub const fn from_isthmus(info: L1BlockInfoIsthmus, da_footprint_gas_scalar: u16) -> Self {
+        Self {
+            number: info.number,
+            time: info.time,
+            base_fee: info.base_fee,
+            block_hash: info.block_hash,
+            sequence_number: info.sequence_number,
+            batcher_address: info.batcher_address,
+            blob_base_fee: info.blob_base_fee,
+            base_fee_scalar: info.base_fee_scalar,
+            blob_base_fee_scalar: info.blob_base_fee_scalar,
+            operator_fee_scalar: info.operator_fee_scalar,
+            operator_fee_constant: info.operator_fee_constant,
+            da_footprint_gas_scalar,
+        }
+    }
+
+    /// Returns the Isthmus subset of the Jovian L1 attributes.
+    pub const fn as_isthmus(&self) -> L1BlockInfoIsthmus {
+        L1BlockInfoIsthmus {
+            number: self.number,
+            time: self.time,
+            base_fee: self.base_fee,
+            block_hash: self.block_hash,
+            sequence_number: self.sequence_number,
+            batcher_address: self.batcher_address,
+            blob_base_fee: self.blob_base_fee,
+            base_fee_scalar: self.base_fee_scalar,
+            blob_base_fee_scalar: self.blob_base_fee_scalar,
+            operator_fee_scalar: self.operator_fee_scalar,
+            operator_fee_constant: self.operator_fee_constant,
+        }
+    }
+}
+
+/// Returns the function selector of the calldata.
+fn selector(data: &[u8]) -> Result<Selector, L1BlockInfoError> {
+    data.get(..4)
+        .map(Selector::from_slice)
+        .ok_or(L1BlockInfoError::InvalidLength { expected: 4, got: data.len() })
+}
+
+/// Checks the selector and length of the calldata.
+fn check_calldata(data: &[u8], expected: Selector, len: usize) -> Result<(), L1BlockInfoError> {
+    let selector = selector(data)?;
+    if selector != expected {
+        return Err(L1BlockInfoError::UnknownSelector(selector));
+    }
+    if data.len() != len {
+        return Err(L1BlockInfoError::InvalidLength { expected: len, got: data.len() });
+    }
+    Ok(())
+}
+
+/// Reads a `u64` from a big-endian 32-byte word.
+fn u64_from_word(word: &[u8], field: &'static str) -> Result<u64, L1BlockInfoError> {
+    U256::from_be_slice(word).try_into().map_err(|_| L1BlockInfoError::FieldOverflow(field))
+}
+
+/// Reads an address from a 32-byte word, which must be zero-padded.
+fn address_from_word(word: &[u8], field: &'static str) -> Result<Address, L1BlockInfoError> {
+    abi::address(word, 0).ok_or(L1BlockInfoError::FieldOverflow(field))
+}
+
+/// Reads a `u128` from a big-endian 32-byte word.
+fn u128_from_word(word: &[u8], field: &'static str) -> Result<u128, L1BlockInfoError> {
+    U256::from_be_slice(word).try_into().map_err(|_| L1BlockInfoError::FieldOverflow(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{b256, hex, keccak256};
+
+    // A pre-Regolith L1 attributes deposit transaction from OP Goerli.
+    const RAW_BEDROCK_INFO_TX: &[u8] = &hex!(
+        "7ef9015aa044bae9d41b8380d781187b426c6fe43df5fb2fb57bd4466ef6a701e1f01e015694deaddeaddeaddeaddeaddeaddeaddeaddead000194420000000000000000000000000000000000001580808408f0d18001b90104015d8eb900000000000000000000000000000000000000000000000000000000008057650000000000000000000000000000000000000000000000000000000063d96d10000000000000000000000000000000000000000000000000000000000009f35273d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d1900000000000000000000000000000000000000000000000000000000000000010000000000000000000000002d679b567db6187c0c8323fa982cfb88b74dbcc7000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240"
+    );
+
+    // An Ecotone L1 attributes deposit transaction from Base Mainnet.
+    const RAW_ECOTONE_INFO_TX: &[u8] = &hex!(
+        "7ef8f8a0871ec5fb6afe7e5ae950bbb4cfd7d7cb277b413e67da806d50834a814b14c9f494deaddeaddeaddeaddeaddeaddeaddeaddead00019442000000000000000000000000000000000000158080830f424080b8a4440a5e20000008dd00101c12000000000000000400000000681c941f0000000001566261000000000000000000000000000000000000000000000000000000005f629c020000000000000000000000000000000000000000000000000000000000000001937badfbcce566e0ba932a3f7659644aa0c6ef019541d3134a1d8cb9f84d45c70000000000000000000000005050f69a9786f081509234f1a7f4684b5e5b76c9"
+    );
+
+    #[test]
+    fn test_l1_info_selectors() {
+        let selector = |signature: &str| Selector::from_slice(&keccak256(signature)[..4]);
+        assert_eq!(
+            selector(
+                "setL1BlockValues(uint64,uint64,uint256,bytes32,uint64,bytes32,uint256,uint256)"
+            ),
+            L1_INFO_SELECTOR_BEDROCK
+        );
+        assert_eq!(selector("setL1BlockValuesEcotone()"), L1_INFO_SELECTOR_ECOTONE);
+        assert_eq!(selector("setL1BlockValuesIsthmus()"), L1_INFO_SELECTOR_ISTHMUS);
+        assert_eq!(selector("setL1BlockValuesJovian()"), L1_INFO_SELECTOR_JOVIAN);
+    }
+
+    #[test]
+    fn test_decode_bedrock_info_tx() {
+        let tx = TxDeposit::decode_2718(&mut &RAW_BEDROCK_INFO_TX[..]).unwrap();
+        let info = L1BlockInfoTx::decode_calldata(&tx.input).unwrap();
+
+        assert_eq!(
+            info,
+            L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+                number: 0x805765,
+                time: 0x63d96d10,
+                base_fee: 0x9f352,
+                block_hash: b256!(
+                    "0x73d89754a1e0387b89520d989d3be9c37c1f32495a88faf1ea05c61121ab0d19"
+                ),
+                sequence_number: 1,
+                batcher_address: address!("0x2d679b567db6187c0c8323fa982cfb88b74dbcc7"),
+                l1_fee_overhead: U256::from(0x834),
+                l1_fee_scalar: U256::from(0xf4240),
+            })
+        );
+        assert_eq!(info.encode_calldata(), tx.input);
+        assert_eq!(info.to_deposit(false), tx);
+    }
+
+    #[test]
+    fn test_decode_ecotone_info_tx() {
+        let tx = TxDeposit::decode_2718(&mut &RAW_ECOTONE_INFO_TX[..]).unwrap();
+        let info = L1BlockInfoTx::decode_calldata(&tx.input).unwrap();
+
+        assert_eq!(
+            info,
+            L1BlockInfoTx::Ecotone(L1BlockInfoEcotone {
+                number: 0x1566261,
+                time: 0x681c941f,
+                base_fee: 0x5f629c02,
+                block_hash: b256!(
+                    "0x937badfbcce566e0ba932a3f7659644aa0c6ef019541d3134a1d8cb9f84d45c7"
+                ),
+                sequence_number: 4,
+                batcher_address: address!("0x5050f69a9786f081509234f1a7f4684b5e5b76c9"),
+                blob_base_fee: 1,
+                base_fee_scalar: 0x8dd,
+                blob_base_fee_scalar: 0x101c12,
+            })
+        );
+        assert_eq!(info.blob_base_fee(), Some(1));
+        assert_eq!(info.encode_calldata(), tx.input);
+        assert_eq!(info.to_deposit(true), tx);
+    }
+
+    #[test]
+    fn test_isthmus_info_roundtrip() {
+        let L1BlockInfoTx::Ecotone(ecotone) =
+            L1BlockInfoTx::decode_calldata(&hex!("440a5e20000008dd00101c12000000000000000400000000681c941f0000000001566261000000000000000000000000000000000000000000000000000000005f629c020000000000000000000000000000000000000000000000000000000000000001937badfbcce566e0ba932a3f7659644aa0c6ef019541d3134a1d8cb9f84d45c70000000000000000000000005050f69a9786f081509234f1a7f4684b5e5b76c9")).unwrap()
+        else {
+            panic!("expected Ecotone L1 info");
+        };
+        let info = L1BlockInfoIsthmus::from_ecotone(ecotone, 0xaabbccdd, 0x1122334455667788);
+
+        let calldata = info.encode_calldata();
+        assert_eq!(calldata.len(), L1_INFO_LEN_ISTHMUS);
+        assert_eq!(&calldata[..4], L1_INFO_SELECTOR_ISTHMUS.as_slice());
+        assert_eq!(&calldata[4..L1_INFO_LEN_ECOTONE], &ecotone.encode_calldata()[4..]);
+        assert_eq!(&calldata[L1_INFO_LEN_ECOTONE..], &hex!("aabbccdd1122334455667788"));
+
+        let decoded = L1BlockInfoTx::decode_calldata(&calldata).unwrap();
+        assert_eq!(decoded, L1BlockInfoTx::Isthmus(info));
+        assert_eq!(info.as_ecotone(), ecotone);
+    }
+
+    #[test]
+    fn test_jovian_info_roundtrip() {
+        let isthmus = L1BlockInfoIsthmus {
+            number: 0x1566261,
+            base_fee: 0x5f629c02,
+            blob_base_fee: 1,
+            operator_fee_scalar: 0xaabbccdd,
+            operator_fee_constant: 0x1122334455667788,
+            ..Default::default()
+        };
+        let info = L1BlockInfoJovian::from_isthmus(isthmus, 0x0190);
+
+        let calldata = info.encode_calldata();
+        assert_eq!(calldata.len(), L1_INFO_LEN_JOVIAN);
+        assert_eq!(&calldata[..4], L1_INFO_SELECTOR_JOVIAN.as_slice());
+        assert_eq!(&calldata[4..L1_INFO_LEN_ISTHMUS], &isthmus.encode_calldata()[4..]);
+        assert_eq!(&calldata[L1_INFO_LEN_ISTHMUS..], &hex!("0190"));
+
+        let decoded = L1BlockInfoTx::decode_calldata(&calldata).unwrap();
+        assert_eq!(decoded, L1BlockInfoTx::Jovian(info));
+        assert_eq!(decoded.da_footprint_gas_scalar(), Some(400));
+        assert_eq!(decoded.operator_fee_params(), Some((0xaabbccdd, 0x1122334455667788)));
+        assert_eq!(info.as_isthmus(), isthmus);
+    }
+
+    #[test]
+    fn test_pre_regolith_info_deposit() {
+        let info = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock::default());
+        let tx = info.to_deposit(false);
+        assert_eq!(tx.gas_limit, BEDROCK_SYSTEM_TX_GAS);
+        assert!(tx.is_system_transaction);
+        assert_eq!(tx.source_hash, L1InfoDepositSource::new(B256::ZERO, 0).source_hash());
+    }
+
+    #[test]
+    fn test_decode_invalid_calldata() {
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&[0x01, 0x5d]),
+            Err(L1BlockInfoError::InvalidLength { expected: 4, got: 2 })
+        );
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&[0xde, 0xad, 0xbe, 0xef]),
+            Err(L1BlockInfoError::UnknownSelector(fixed_bytes!("0xdeadbeef")))
+        );
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(L1_INFO_SELECTOR_ECOTONE.as_slice()),
+            Err(L1BlockInfoError::InvalidLength { expected: L1_INFO_LEN_ECOTONE, got: 4 })
+        );
+
+        let mut overflow = L1BlockInfoBedrock::default().encode_calldata().to_vec();
+        overflow[4] = 1;
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&overflow),
+            Err(L1BlockInfoError::FieldOverflow("number"))
+        );
+
+        // The padding in front of the batcher address must be zero.
+        let mut padded = L1BlockInfoBedrock::default().encode_calldata().to_vec();
+        padded[4 + 32 * 5] = 1;
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&padded),
+            Err(L1BlockInfoError::FieldOverflow("batcher_address"))
+        );
+        let mut padded = L1BlockInfoEcotone::default().encode_calldata().to_vec();
+        padded[143] = 1;
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&padded),
+            Err(L1BlockInfoError::FieldOverflow("batcher_address"))
+        );
+        let mut padded = L1BlockInfoJovian::default().encode_calldata().to_vec();
+        padded[132] = 1;
+        assert_eq!(
+            L1BlockInfoTx::decode_calldata(&padded),
+            Err(L1BlockInfoError::FieldOverflow("batcher_address"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_info_roundtrip() {
+        use arbitrary::Arbitrary;
+        use rand::Rng;
+
+        let mut bytes = [0u8; 1024];
+        rand::rng().fill(bytes.as_mut_slice());
+        let mut u = arbitrary::Unstructured::new(&bytes);
+        for _ in 0..8 {
+            let info = L1BlockInfoTx::arbitrary(&mut u).unwrap();
+            assert_eq!(L1BlockInfoTx::decode_calldata(&info.encode_calldata()).unwrap(), info);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! L1 data fee and operator fee of L2 transactions.
+//!
+//! Every non-deposit transaction pays for the L1 data availability of its EIP-2718 encoding. The
+//! fee is computed from the L1 attributes of the block, with a formula that changed in Ecotone
+//! and Fjord. Since Isthmus, non-deposit transactions also pay an operator fee that scales with
+//! their gas usage. Since Jovian, the estimated compressed size of the transactions of a block
+//! also counts towards its DA footprint, which is stored in the blob gas used field of the header.
+//!
+//! See: <https://specs.optimism.io/protocol/exec-engine.html#l1-cost-fees-l1-fee-vault>,
+//! <https://specs.optimism.io/protocol/isthmus/exec-engine.html#operator-fee>
+//! and <https://specs.optimism.io/protocol/jovian/exec-engine.html#da-footprint-block-limit>
+
+use crate::{
+    DEPOSIT_TX_TYPE_ID, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoTx, OpHardfork,
+    OpTxEnvelope, fast_lz::flz_compress_len, l1_block_info::DEFAULT_DA_FOOTPRINT_GAS_SCALAR,
+};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::U256;
+
+/// Calldata gas charged per zero byte.
+pub const ZERO_BYTE_COST: u64 = 4;
+
+/// Calldata gas charged per non-zero byte.
+pub const NON_ZERO_BYTE_COST: u64 = 16;
+
+/// The number of non-zero bytes charged on top of the transaction before Regolith, accounting for
+/// the signature.
+pub const PRE_REGOLITH_SIGNATURE_BYTES: u64 = 68;
+
+/// The minimum estimated compressed size of a transaction since Fjord, scaled by 1e6.
+pub const FJORD_MIN_TX_SIZE_SCALED: u64 = 100_000_000;
+
+/// The intercept of the Fjord compressed size regression, scaled by 1e6.
+pub const FJORD_L1_COST_INTERCEPT: u64 = 42_585_600;
+
+/// The FastLZ coefficient of the Fjord compressed size regression, scaled by 1e6.
+pub const FJORD_L1_COST_FASTLZ_COEF: u64 = 836_500;
+
+/// Returns the L1 data fee of the EIP-2718 encoded transaction `tx`.
+///
+/// `fork` is the latest hardfork active at the L2 block, which selects the fee formula together
+/// with the layout of the L1 attributes: the Bedrock formula applies as long as the L1 attributes
+/// use the Bedrock layout, which is still the case in the Ecotone activation block.
+///
+/// Deposit transactions and empty inputs do not pay an L1 data fee.
+pub fn l1_data_fee(tx: &[u8], l1_info: &L1BlockInfoTx, fork: OpHardfork) -> U256 {
+    if tx.is_empty() || tx[0] == DEPOSIT_TX_TYPE_ID {
+        return U256::ZERO;
+    }
+
+    match l1_info {
+        L1BlockInfoTx::Bedrock(info) => l1_data_fee_bedrock(tx, info, fork),
+        L1BlockInfoTx::Ecotone(info) => l1_data_fee_ecotone_or_fjord(tx, info, fork),
+        L1BlockInfoTx::Isthmus(info) => l1_data_fee_ecotone_or_fjord(tx, &info.as_ecotone(), fork),
+        L1BlockInfoTx::Jovian(info) => {
+            l1_data_fee_ecotone_or_fjord(tx, &info.as_isthmus().as_ecotone(), fork)
+        }
+    }
+}
+
+/// Returns the calldata gas of `tx` used by the Bedrock and Ecotone fee formulas.
+///
+/// Before Regolith, 68 non-zero bytes are added to account for the signature.
+pub fn l1_data_gas(tx: &[u8], fork: OpHardfork) -> U256 {
+    let mut gas = tx
+        .iter()
+        .fold(0u64, |gas, byte| gas + if *byte == 0 { ZERO_BYTE_COST } else { NON_ZERO_BYTE_COST });
+    if fork < OpHardfork::Regolith {
+        gas += NON_ZERO_BYTE_COST * PRE_REGOLITH_SIGNATURE_BYTES;
+    }
+    U256::from(gas)
+}
+
+/// Returns the estimated compressed size of `tx` since Fjord, scaled by 1e6:
+/// `max(minTransactionSize, intercept + fastlzCoef * fastlzSize)`.
+pub fn fjord_estimated_size(tx: &[u8]) -> U256 {
+    U256::from(flz_compress_len(tx))
+        .saturating_mul(U256::from(FJORD_L1_COST_FASTLZ_COEF))
+        .saturating_sub(U256::from(FJORD_L1_COST_INTERCEPT))
+        .max(U256::from(FJORD_MIN_TX_SIZE_SCALED))
+}
+
+/// Bedrock: `(l1DataGas + overhead) * l1BaseFee * scalar / 1e6`.
+fn l1_data_fee_bedrock(tx: &[u8], info: &L1BlockInfoBedrock, fork: OpHardfork) -> U256 {
+    l1_data_gas(tx, fork)
+        .saturating_add(info.l1_fee_overhead)
+        .saturating_mul(U256::from(info.base_fee))
+        .saturating_mul(info.l1_fee_scalar)
+        / U256::from(1_000_000)
+}
+
+/// Ecotone: `l1DataGas * l1FeeScaled / (16 * 1e6)`.
+///
+/// Fjord: `estimatedSize * l1FeeScaled / 1e12`.
+fn l1_data_fee_ecotone_or_fjord(tx: &[u8], info: &L1BlockInfoEcotone, fork: OpHardfork) -> U256 {
+    // l1BaseFee * 16 * baseFeeScalar + blobBaseFee * blobBaseFeeScalar
+    let l1_fee_scaled = U256::from(info.base_fee)
+        .saturating_mul(U256::from(NON_ZERO_BYTE_COST))
+        .saturating_mul(U256::from(info.base_fee_scalar))
+        .saturating_add(
+            U256::from(info.blob_base_fee).saturating_mul(U256::from(info.blob_base_fee_scalar)),
+        );
+
+    if fork >= OpHardfork::Fjord {
+        fjord_estimated_size(tx).saturating_mul(l1_fee_scaled) / U256::from(1_000_000_000_000u64)
+    } else {
+        l1_data_gas(tx, fork).saturating_mul(l1_fee_scaled)
+            / U256::from(NON_ZERO_BYTE_COST * 1_000_000)
+    }
+}
+
+/// Returns the L1 gas used by the EIP-2718 encoded transaction `tx`, as reported in its receipt.
+///
+/// This is the calldata gas, plus the fee overhead for the Bedrock formula, or the estimated
+/// compressed size in calldata gas since Fjord.
+pub fn l1_gas_used(tx: &[u8], l1_info: &L1BlockInfoTx, fork: OpHardfork) -> U256 {
+    if tx.is_empty() || tx[0] == DEPOSIT_TX_TYPE_ID {
+        return U256::ZERO;
+    }
+
+    match l1_info {
+        L1BlockInfoTx::Bedrock(info) => l1_data_gas(tx, fork).saturating_add(info.l1_fee_overhead),
+        _ if fork >= OpHardfork::Fjord => {
+            fjord_estimated_size(tx).saturating_mul(U256::from(NON_ZERO_BYTE_COST))
+                / U256::from(1_000_000)
+        }
+        _ => l1_data_gas(tx, fork),
+    }
+}
+
+/// Returns the DA footprint of the EIP-2718 encoded transaction `tx` since Jovian:
+/// `max(minTransactionSize, intercept + fastlzCoef * fastlzSize) / 1e6 * daFootprintGasScalar`.
+///
+/// A `da_footprint_gas_scalar` of zero means the system config sets none, in which case
+/// [`DEFAULT_DA_FOOTPRINT_GAS_SCALAR`] is used.
+///
+/// Deposit transactions and empty inputs have no DA footprint.
+pub fn da_footprint(tx: &[u8], da_footprint_gas_scalar: u16) -> u64 {
+    if tx.is_empty() || tx[0] == DEPOSIT_TX_TYPE_ID {
+        return 0;
+    }
+    let da_footprint_gas_scalar = match da_footprint_gas_scalar {
+        0 => DEFAULT_DA_FOOTPRINT_GAS_SCALAR,
+        scalar => scalar,
+    };
+    (fjord_estimated_size(tx) / U256::from(1_000_000))
+        .saturating_to::<u64>()
+        .saturating_mul(da_footprint_gas_scalar.into())
+}
+
+/// Returns the DA footprint of a block with the EIP-2718 encoded transactions `txs`, which is
+/// stored in the blob gas used field of its header since Jovian.
+///
+/// See [`da_footprint`].
+pub fn block_da_footprint<T: AsRef<[u8]>>(
+    txs: impl IntoIterator<Item = T>,
+    da_footprint_gas_scalar: u16,
+) -> u64 {
+    txs.into_iter().fold(0u64, |footprint, tx| {
+        footprint.saturating_add(da_footprint(tx.as_ref(), da_footprint_gas_scalar))
+    })
+}
+
+impl L1BlockInfoTx {
+    /// Returns the L1 data fee of the EIP-2718 encoded transaction `tx`.
+    ///
+    /// See [`l1_data_fee`].
+    pub fn l1_data_fee(&self, tx: &[u8], fork: OpHardfork) -> U256 {
+        l1_data_fee(tx, self, fork)
+    }
+
+    /// Returns the operator fee of a non-deposit transaction that used `gas_used` gas.
+    ///
+    /// Isthmus: `gasUsed * operatorFeeScalar / 1e6 + operatorFeeConstant`.
+    ///
+    /// Jovian: `gasUsed * operatorFeeScalar * 100 + operatorFeeConstant`.
+    ///
+    /// The operator fee is only charged if the L1 attributes use the Isthmus or Jovian layout, and
+    /// is zero otherwise.
+    pub fn operator_fee(&self, gas_used: u64) -> U256 {
+        match self {
+            Self::Isthmus(info) => {
+                U256::from(gas_used).saturating_mul(U256::from(info.operator_fee_scalar))
+                    / U256::from(1_000_000)
+                    + U256::from(info.operator_fee_constant)
+            }
+            Self::Jovian(info) => U256::from(gas_used)
+                .saturating_mul(U256::from(info.operator_fee_scalar))
+                .saturating_mul(U256::from(100))
+                .saturating_add(U256::from(info.operator_fee_constant)),
+            Self::Bedrock(_) | Self::Ecotone(_) => U256::ZERO,
+        }
+    }
+
+    /// Returns the part of the operator fee that is refunded to the sender of a non-deposit
+    /// transaction.
+    ///
+    /// The operator fee is bought upfront for the whole `gas_limit` and the excess over the
+    /// operator fee of `gas_used` is refunded together with the unused gas, so that the sender
+    /// pays exactly [`Self::operator_fee`] of `gas_used`.
+    pub fn operator_fee_refund(&self, gas_limit: u64, gas_used: u64) -> U256 {
+        self.operator_fee(gas_limit).saturating_sub(self.operator_fee(gas_used))
+    }
+}
+
+impl OpTxEnvelope {
+    /// Returns the L1 data fee of the transaction, computed over its EIP-2718 encoding.
+    ///
+    /// See [`l1_data_fee`].
+    pub fn l1_data_fee(&self, l1_info: &L1BlockInfoTx, fork: OpHardfork) -> U256 {
+        if self.is_deposit() {
+            return U256::ZERO;
+        }
+        l1_data_fee(&self.encoded_2718(), l1_info, fork)
+    }
+
+    /// Returns the operator fee of the transaction, given the gas it used.
+    ///
+    /// Deposit transactions neither pay an operator fee nor receive an operator fee refund.
+    ///
+    /// See [`L1BlockInfoTx::operator_fee`].
+    pub fn operator_fee(&self, gas_used: u64, l1_info: &L1BlockInfoTx) -> U256 {
+        if self.is_deposit() {
+            return U256::ZERO;
+        }
+        l1_info.operator_fee(gas_used)
+    }
+
+    /// Returns the DA footprint of the transaction since Jovian, computed over its EIP-2718
+    /// encoding.
+    ///
+    /// See [`da_footprint`].
+    pub fn da_footprint(&self, da_footprint_gas_scalar: u16) -> u64 {
+        if self.is_deposit() {
+            return 0;
+        }
+        da_footprint(&self.encoded_2718(), da_footprint_gas_scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{L1BlockInfoIsthmus, L1BlockInfoJovian, SAMPLE_CONTRACT_CALL, TxDeposit};
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{Bytes, Sealed, hex};
+
+    const FACADE: [u8; 3] = hex!("FACADE");
+
+    fn bedrock_info() -> L1BlockInfoTx {
+        L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+            base_fee: 1_000,
+            l1_fee_overhead: U256::from(1_000),
+            l1_fee_scalar: U256::from(1_000),
+            ..Default::default()
+        })
+    }
+
+    fn ecotone_info() -> L1BlockInfoEcotone {
+        L1BlockInfoEcotone {
+            base_fee: 1_000,
+            base_fee_scalar: 1_000,
+            blob_base_fee: 1_000,
+            blob_base_fee_scalar: 1_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_l1_data_gas() {
+        // 3 non-zero bytes, plus 68 non-zero bytes before Regolith.
+        assert_eq!(l1_data_gas(&FACADE, OpHardfork::Bedrock), U256::from(1136));
+        assert_eq!(l1_data_gas(&FACADE, OpHardfork::Regolith), U256::from(48));
+        // 3 non-zero and 2 zero bytes.
+        assert_eq!(l1_data_gas(&hex!("FA00CA00DE"), OpHardfork::Regolith), U256::from(56));
+    }
+
+    #[test]
+    fn test_fjord_estimated_size() {
+        // Small transactions are charged the minimum size.
+        assert_eq!(fjord_estimated_size(&FACADE), U256::from(100_000_000));
+        // 836500 * 202 - 42585600
+        assert_eq!(fjord_estimated_size(SAMPLE_CONTRACT_CALL), U256::from(126_387_400));
+    }
+
+    #[test]
+    fn test_l1_data_fee_bedrock() {
+        // (48 + 1000) * 1000 * 1000 / 1e6
+        assert_eq!(l1_data_fee(&FACADE, &bedrock_info(), OpHardfork::Regolith), U256::from(1048));
+        // The Bedrock formula still applies in the Ecotone activation block.
+        assert_eq!(l1_data_fee(&FACADE, &bedrock_info(), OpHardfork::Ecotone), U256::from(1048));
+    }
+
+    #[test]
+    fn test_l1_data_fee_ecotone() {
+        // 48 * (1000 * 16 * 1000 + 1000 * 1000) / 16e6
+        let info = L1BlockInfoTx::Ecotone(ecotone_info());
+        assert_eq!(l1_data_fee(&FACADE, &info, OpHardfork::Ecotone), U256::from(51));
+    }
+
+    #[test]
+    fn test_l1_data_fee_fjord() {
+        // 100e6 * 17e6 / 1e12
+        let info = L1BlockInfoTx::Ecotone(ecotone_info());
+        assert_eq!(l1_data_fee(&FACADE, &info, OpHardfork::Fjord), U256::from(1700));
+        // 126387400 * 17e6 / 1e12
+        assert_eq!(l1_data_fee(SAMPLE_CONTRACT_CALL, &info, OpHardfork::Fjord), U256::from(2148));
+
+        let info = L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::from_ecotone(ecotone_info(), 1, 1));
+        assert_eq!(l1_data_fee(SAMPLE_CONTRACT_CALL, &info, OpHardfork::Isthmus), U256::from(2148));
+    }
+
+    #[test]
+    fn test_l1_data_fee_free_transactions() {
+        let info = L1BlockInfoTx::Ecotone(ecotone_info());
+        for fork in [OpHardfork::Regolith, OpHardfork::Ecotone, OpHardfork::Fjord] {
+            assert_eq!(l1_data_fee(&[], &info, fork), U256::ZERO);
+            assert_eq!(l1_data_fee(&hex!("7EFACADE"), &info, fork), U256::ZERO);
+        }
+
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            input: Bytes::from_static(&[1; 100]),
+            ..Default::default()
+        }));
+        assert_eq!(deposit.l1_data_fee(&info, OpHardfork::Fjord), U256::ZERO);
+    }
+
+    #[test]
+    fn test_l1_gas_used() {
+        assert_eq!(l1_gas_used(&FACADE, &bedrock_info(), OpHardfork::Regolith), U256::from(1048));
+        let info = L1BlockInfoTx::Ecotone(ecotone_info());
+        assert_eq!(l1_gas_used(&FACADE, &info, OpHardfork::Ecotone), U256::from(48));
+        assert_eq!(l1_gas_used(&FACADE, &info, OpHardfork::Fjord), U256::from(1600));
+        assert_eq!(l1_gas_used(SAMPLE_CONTRACT_CALL, &info, OpHardfork::Fjord), U256::from(2022));
+        assert_eq!(l1_gas_used(&hex!("7EFACADE"), &info, OpHardfork::Fjord), U256::ZERO);
+    }
+
+    #[test]
+    fn test_operator_fee() {
+        let info = L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::from_ecotone(
+            ecotone_info(),
+            2_000_000,
+            500,
+        ));
+        // 21000 * 2e6 / 1e6 + 500
+        assert_eq!(info.operator_fee(21_000), U256::from(42_500));
+        assert_eq!(info.operator_fee(0), U256::from(500));
+        // (30000 - 21000) * 2
+        assert_eq!(info.operator_fee_refund(30_000, 21_000), U256::from(18_000));
+
+        assert_eq!(L1BlockInfoTx::Ecotone(ecotone_info()).operator_fee(21_000), U256::ZERO);
+
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit::default()));
+        assert_eq!(deposit.operator_fee(21_000, &info), U256::ZERO);
+        let tx = OpTxEnvelope::decode_2718(&mut &SAMPLE_CONTRACT_CALL[..]).unwrap();
+        assert_eq!(tx.operator_fee(21_000, &info), U256::from(42_500));
+    }
+
+    #[test]
+    fn test_jovian_fees() {
+        let isthmus = L1BlockInfoIsthmus::from_ecotone(ecotone_info(), 2, 500);
+        let info = L1BlockInfoTx::Jovian(L1BlockInfoJovian::from_isthmus(isthmus, 400));
+        // 21000 * 2 * 100 + 500
+        assert_eq!(info.operator_fee(21_000), U256::from(4_200_500));
+        assert_eq!(info.operator_fee_refund(30_000, 21_000), U256::from(1_800_000));
+        // The Fjord formula still applies.
+        assert_eq!(l1_data_fee(SAMPLE_CONTRACT_CALL, &info, OpHardfork::Jovian), U256::from(2148));
+        assert_eq!(l1_gas_used(SAMPLE_CONTRACT_CALL, &info, OpHardfork::Jovian), U256::from(2022));
+    }
+
+    #[test]
+    fn test_da_footprint() {
+        // Small transactions are charged the minimum size: 100 * 400.
+        assert_eq!(da_footprint(&FACADE, 400), 40_000);
+        // 126387400 / 1e6 * 400
+        assert_eq!(da_footprint(SAMPLE_CONTRACT_CALL, 400), 50_400);
+        assert_eq!(da_footprint(&hex!("7EFACADE"), 400), 0);
+        assert_eq!(
+            block_da_footprint([&hex!("7EFACADE")[..], &FACADE, SAMPLE_CONTRACT_CALL], 400),
+            90_400
+        );
+        // A zero scalar falls back to the default scalar of 400.
+        assert_eq!(da_footprint(SAMPLE_CONTRACT_CALL, 0), 50_400);
+        assert_eq!(da_footprint(SAMPLE_CONTRACT_CALL, 1), 126);
+
+        let tx = OpTxEnvelope::decode_2718(&mut &SAMPLE_CONTRACT_CALL[..]).unwrap();
+        assert_eq!(tx.da_footprint(400), 50_400);
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit::default()));
+        assert_eq!(deposit.da_footprint(400), 0);
+    }
+
+    #[test]
+    fn test_envelope_l1_data_fee() {
+        let tx = OpTxEnvelope::decode_2718(&mut &SAMPLE_CONTRACT_CALL[..]).unwrap();
+        let info = L1BlockInfoTx::Ecotone(ecotone_info());
+        assert_eq!(tx.l1_data_fee(&info, OpHardfork::Fjord), U256::from(2148));
+        assert_eq!(
+            info.l1_data_fee(SAMPLE_CONTRACT_CALL, OpHardfork::Ecotone),
+            tx.l1_data_fee(&info, OpHardfork::Ecotone)
+        );
+    }
+}
@@ -9,6 +9,8 @@
 
 extern crate alloc;
 
+mod abi;
+
 #[cfg(feature = "alloy-compat")]
 mod alloy_compat;
 
@@ -17,27 +19,76 @@ pub use receipt::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope
 
 pub mod transaction;
 pub use transaction::{
-    DEPOSIT_TX_TYPE_ID, DepositTransaction, OpPooledTransaction, OpTransaction, OpTxEnvelope,
+    DEPOSIT_EVENT_ABI, DEPOSIT_EVENT_ABI_HASH, DEPOSIT_EVENT_VERSION_0, DEPOSIT_TX_TYPE_ID,
+    DepositLogError, DepositTransaction, OpPooledTransaction, OpTransaction, OpTxEnvelope,
     OpTxType, OpTypedTransaction, TxDeposit,
 };
 
 pub mod eip1559;
 pub use eip1559::{
-    EIP1559ParamError, decode_eip_1559_params, decode_holocene_extra_data,
-    encode_holocene_extra_data,
+    EIP1559ParamError, calc_next_block_base_fee, decode_eip_1559_params,
+    decode_holocene_extra_data, decode_jovian_extra_data, encode_holocene_extra_data,
+    encode_jovian_extra_data,
 };
 
 mod source;
 pub use source::*;
 
+pub mod l1_block_info;
+pub use l1_block_info::{
+    L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoError, L1BlockInfoIsthmus,
+    L1BlockInfoJovian, L1BlockInfoTx,
+};
+
+pub mod hardforks;
+pub use hardforks::{
+    ForkCondition, OpHardfork, OpHardforkParseError, OpHardforkSchedule, OpHardforkScheduleError,
+    OpHardforks,
+};
+
+pub mod predeploys;
+
+pub mod fast_lz;
+
+pub mod l1_fee;
+
+pub mod upgrades;
+
 mod block;
 pub use block::OpBlock;
 
+pub mod proofs;
+pub use proofs::{BlockIntegrityError, verify_block_integrity};
+
+pub mod validation;
+pub use validation::{
+    BlockBodyValidationError, HeaderValidationError, validate_block_body, validate_header,
+    validate_header_against_parent,
+};
+
 pub mod interop;
 
+pub mod withdrawal;
+pub use withdrawal::{MessagePassed, MessagePassedError, WithdrawalTransaction};
+
+pub mod messenger;
+pub use messenger::{CrossDomainMessage, CrossDomainMessageError, RelayMessageAbi};
+
+pub mod bridge;
+pub use bridge::{BridgeAction, BridgeDirection, BridgeToken};
+
+pub mod output_root;
+pub use output_root::{OutputRootError, OutputRootV0, OutputRootWithChain, SuperRoot};
+
 #[cfg(feature = "serde")]
 pub use transaction::serde_deposit_tx_rpc;
 
+/// An EIP-1559 contract call on OP Mainnet, EIP-2718 encoded, shared by the fee and compression
+/// tests.
+#[cfg(test)]
+pub(crate) const SAMPLE_CONTRACT_CALL: &[u8] =
+    include_bytes!("../testdata/sample_contract_call.bin");
+
 /// Bincode-compatible serde implementations for consensus types.
 ///
 /// `bincode` crate doesn't work well with optionally serializable serde fields, but some of the
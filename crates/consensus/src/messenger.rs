@@ -0,0 +1,292 @@
+//! Messages relayed by the `L1CrossDomainMessenger` and `L2CrossDomainMessenger`.
+
+use crate::{TxDeposit, abi, predeploys::L2_CROSS_DOMAIN_MESSENGER_ADDRESS};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, Bytes, Selector, TxKind, U256, fixed_bytes, keccak256};
+
+/// The selector of the version 0 `relayMessage(address,address,bytes,uint256)` function of the
+/// legacy messengers.
+pub const RELAY_MESSAGE_V0_SELECTOR: Selector = fixed_bytes!("0xcbd4ece9");
+
+/// The selector of the version 1
+/// `relayMessage(uint256,address,address,uint256,uint256,bytes)` function.
+pub const RELAY_MESSAGE_V1_SELECTOR: Selector = fixed_bytes!("0xd764ad0b");
+
+/// The mask of the nonce in a versioned nonce, whose top two bytes hold the message version.
+pub const NONCE_MASK: U256 = U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, 0xffff_ffff_ffff]);
+
+/// An error that can occur when decoding a `relayMessage` call.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum CrossDomainMessageError {
+    /// The calldata does not start with a `relayMessage` selector.
+    #[error("unknown relayMessage selector")]
+    UnknownSelector,
+    /// The calldata is not a valid ABI encoding of the `relayMessage` arguments.
+    #[error("invalid relayMessage calldata")]
+    InvalidCalldata,
+    /// The version of the message nonce is not relayed by the `relayMessage` function.
+    #[error("unsupported message version: {0}")]
+    UnsupportedVersion(u16),
+}
+
+/// Packs a message version in the top two bytes of a nonce.
+pub fn encode_versioned_nonce(nonce: U256, version: u16) -> U256 {
+    (U256::from(version) << 240) | (nonce & NONCE_MASK)
+}
+
+/// Splits a versioned nonce into its nonce and message version.
+pub fn decode_versioned_nonce(versioned_nonce: U256) -> (U256, u16) {
+    (versioned_nonce & NONCE_MASK, (versioned_nonce >> 240usize).to::<u16>())
+}
+
+/// The `relayMessage` function a [`CrossDomainMessage`] is relayed with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RelayMessageAbi {
+    /// The version 0 `relayMessage(address,address,bytes,uint256)` function of the legacy
+    /// messengers, which only relays version 0 messages.
+    V0,
+    /// The version 1 `relayMessage(uint256,address,address,uint256,uint256,bytes)` function,
+    /// which relays version 1 messages as well as migrated version 0 messages.
+    #[default]
+    V1,
+}
+
+/// A message sent through the cross domain messengers, relayed by a `relayMessage` call on the
+/// other domain.
+///
+/// The version of the message is held by the top two bytes of its nonce. Version 0 messages of
+/// the legacy messengers carry no value nor gas limit. Legacy withdrawals that were migrated to
+/// the current messengers keep their version 0 nonce, but are relayed with the version 1 function,
+/// which is recorded by [`abi`](Self::abi).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CrossDomainMessage {
+    /// The versioned nonce of the message.
+    pub nonce: U256,
+    /// The address that sent the message.
+    pub sender: Address,
+    /// The address that the message is relayed to.
+    pub target: Address,
+    /// The ETH value sent with the message.
+    pub value: U256,
+    /// The minimum gas limit of the call to the target.
+    pub min_gas_limit: U256,
+    /// The calldata of the call to the target.
+    pub message: Bytes,
+    /// The `relayMessage` function the message is relayed with.
+    pub abi: RelayMessageAbi,
+}
+
+impl CrossDomainMessage {
+    /// Returns the version of the message.
+    pub fn version(&self) -> u16 {
+        decode_versioned_nonce(self.nonce).1
+    }
+
+    /// Returns the `relayMessage` calldata of the message, for its [`abi`](Self::abi).
+    ///
+    /// The version 0 function only relays version 0 messages, and the version 1 function
+    /// messages of version 0 and 1.
+    pub fn encode_relay_message(&self) -> Result<Bytes, CrossDomainMessageError> {
+        let mut out = Vec::with_capacity(4 + 32 * 8 + self.message.len());
+        match (self.abi, self.version()) {
+            (RelayMessageAbi::V0, 0) => {
+                out.extend_from_slice(RELAY_MESSAGE_V0_SELECTOR.as_slice());
+                out.extend_from_slice(self.target.into_word().as_slice());
+                out.extend_from_slice(self.sender.into_word().as_slice());
+                out.extend_from_slice(&U256::from(32 * 4).to_be_bytes::<32>());
+                out.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+            }
+            (RelayMessageAbi::V1, 0 | 1) => {
+                out.extend_from_slice(RELAY_MESSAGE_V1_SELECTOR.as_slice());
+                out.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+                out.extend_from_slice(self.sender.into_word().as_slice());
+                out.extend_from_slice(self.target.into_word().as_slice());
+                out.extend_from_slice(&self.value.to_be_bytes::<32>());
+                out.extend_from_slice(&self.min_gas_limit.to_be_bytes::<32>());
+                out.extend_from_slice(&U256::from(32 * 6).to_be_bytes::<32>());
+            }
+            (_, version) => return Err(CrossDomainMessageError::UnsupportedVersion(version)),
+        }
+        abi::encode_bytes(&mut out, &self.message);
+        Ok(out.into())
+    }
+
+    /// Decodes a message from `relayMessage` calldata of either function, recording the function
+    /// in [`abi`](Self::abi).
+    pub fn decode_relay_message(input: &[u8]) -> Result<Self, CrossDomainMessageError> {
+        let (selector, data) =
+            input.split_at_checked(4).ok_or(CrossDomainMessageError::UnknownSelector)?;
+        let invalid = || CrossDomainMessageError::InvalidCalldata;
+        let message = match Selector::from_slice(selector) {
+            RELAY_MESSAGE_V0_SELECTOR => Self {
+                target: abi::address(data, 0).ok_or_else(invalid)?,
+                sender: abi::address(data, 1).ok_or_else(invalid)?,
+                message: Bytes::copy_from_slice(abi::bytes(data, 2).ok_or_else(invalid)?),
+                nonce: abi::word(data, 3).ok_or_else(invalid)?,
+                abi: RelayMessageAbi::V0,
+                ..Default::default()
+            },
+            RELAY_MESSAGE_V1_SELECTOR => Self {
+                nonce: abi::word(data, 0).ok_or_else(invalid)?,
+                sender: abi::address(data, 1).ok_or_else(invalid)?,
+                target: abi::address(data, 2).ok_or_else(invalid)?,
+                value: abi::word(data, 3).ok_or_else(invalid)?,
+                min_gas_limit: abi::word(data, 4).ok_or_else(invalid)?,
+                message: Bytes::copy_from_slice(abi::bytes(data, 5).ok_or_else(invalid)?),
+                abi: RelayMessageAbi::V1,
+            },
+            _ => return Err(CrossDomainMessageError::UnknownSelector),
+        };
+
+        match (message.abi, message.version()) {
+            (RelayMessageAbi::V0, 0) | (RelayMessageAbi::V1, 0 | 1) => Ok(message),
+            (_, version) => Err(CrossDomainMessageError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Returns the message hash, which identifies the message in the `successfulMessages` and
+    /// `failedMessages` mappings of the messengers: the hash of its `relayMessage` calldata.
+    pub fn hash(&self) -> Result<B256, CrossDomainMessageError> {
+        self.encode_relay_message().map(keccak256)
+    }
+}
+
+impl TxDeposit {
+    /// Returns the message relayed by the deposit, if it is a `relayMessage` call to the
+    /// `L2CrossDomainMessenger`, as sent by the `L1CrossDomainMessenger`.
+    pub fn cross_domain_message(&self) -> Option<CrossDomainMessage> {
+        if self.to != TxKind::Call(L2_CROSS_DOMAIN_MESSENGER_ADDRESS) {
+            return None;
+        }
+        CrossDomainMessage::decode_relay_message(&self.input).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex};
+
+    fn message_v1() -> CrossDomainMessage {
+        CrossDomainMessage {
+            nonce: encode_versioned_nonce(U256::from(5), 1),
+            sender: address!("0x1111111111111111111111111111111111111111"),
+            target: address!("0x2222222222222222222222222222222222222222"),
+            value: U256::from(10).pow(U256::from(18)),
+            min_gas_limit: U256::from(200_000),
+            message: Bytes::from_static(&hex!("deadbeef")),
+            abi: RelayMessageAbi::V1,
+        }
+    }
+
+    fn message_v0() -> CrossDomainMessage {
+        CrossDomainMessage {
+            nonce: U256::from(3),
+            value: U256::ZERO,
+            min_gas_limit: U256::ZERO,
+            abi: RelayMessageAbi::V0,
+            ..message_v1()
+        }
+    }
+
+    #[test]
+    fn test_versioned_nonce() {
+        let nonce = encode_versioned_nonce(U256::from(5), 1);
+        assert_eq!(nonce, U256::from(1) << 240 | U256::from(5));
+        assert_eq!(decode_versioned_nonce(nonce), (U256::from(5), 1));
+        assert_eq!(decode_versioned_nonce(U256::from(5)), (U256::from(5), 0));
+        assert_eq!(decode_versioned_nonce(U256::MAX), (NONCE_MASK, u16::MAX));
+        // Nonce bits that overlap the version are dropped.
+        assert_eq!(encode_versioned_nonce(U256::MAX, 0), NONCE_MASK);
+    }
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(message_v1().encode_relay_message().unwrap().len(), 260);
+        assert_eq!(
+            message_v1().hash(),
+            Ok(b256!("0xe912128c89cd1e99eadd6e55e6a7091fb12d3af12b74c9e6f3d75830628d8933"))
+        );
+        assert_eq!(message_v0().encode_relay_message().unwrap().len(), 196);
+        assert_eq!(
+            message_v0().hash(),
+            Ok(b256!("0x3aeac569c7740a0d110d78f2d83e422403d06c9014d52b5915a2f7da7a9a98bb"))
+        );
+
+        let message =
+            CrossDomainMessage { nonce: encode_versioned_nonce(U256::ZERO, 2), ..message_v1() };
+        assert_eq!(message.hash(), Err(CrossDomainMessageError::UnsupportedVersion(2)));
+        let message =
+            CrossDomainMessage { nonce: encode_versioned_nonce(U256::ZERO, 1), ..message_v0() };
+        assert_eq!(message.hash(), Err(CrossDomainMessageError::UnsupportedVersion(1)));
+    }
+
+    #[test]
+    fn test_relay_message_roundtrip() {
+        for message in [message_v0(), message_v1()] {
+            let input = message.encode_relay_message().unwrap();
+            assert_eq!(CrossDomainMessage::decode_relay_message(&input), Ok(message));
+        }
+
+        let input = message_v1().encode_relay_message().unwrap();
+        assert_eq!(
+            CrossDomainMessage::decode_relay_message(&input[..input.len() - 40]),
+            Err(CrossDomainMessageError::InvalidCalldata)
+        );
+        assert_eq!(
+            CrossDomainMessage::decode_relay_message(&input[..3]),
+            Err(CrossDomainMessageError::UnknownSelector)
+        );
+
+        // A migrated legacy message: a version 0 nonce relayed with the version 1 function, which
+        // is encoded back to the same calldata.
+        let mut migrated = input.to_vec();
+        migrated[5] = 0;
+        let message = CrossDomainMessage::decode_relay_message(&migrated).unwrap();
+        assert_eq!(message.version(), 0);
+        assert_eq!(message.abi, RelayMessageAbi::V1);
+        assert_eq!(message.encode_relay_message().unwrap(), migrated);
+        assert_ne!(
+            message.hash(),
+            CrossDomainMessage { abi: RelayMessageAbi::V0, ..message }.hash()
+        );
+
+        let mut invalid = migrated;
+        invalid[5] = 2;
+        assert_eq!(
+            CrossDomainMessage::decode_relay_message(&invalid),
+            Err(CrossDomainMessageError::UnsupportedVersion(2))
+        );
+        let mut invalid = message_v0().encode_relay_message().unwrap().to_vec();
+        invalid[4 + 32 * 3 + 1] = 1;
+        assert_eq!(
+            CrossDomainMessage::decode_relay_message(&invalid),
+            Err(CrossDomainMessageError::UnsupportedVersion(1))
+        );
+    }
+
+    #[test]
+    fn test_deposit_cross_domain_message() {
+        let deposit = TxDeposit {
+            to: TxKind::Call(L2_CROSS_DOMAIN_MESSENGER_ADDRESS),
+            input: message_v1().encode_relay_message().unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(deposit.cross_domain_message(), Some(message_v1()));
+
+        let deposit = TxDeposit { to: TxKind::Call(Address::ZERO), ..deposit };
+        assert_eq!(deposit.cross_domain_message(), None);
+        let deposit = TxDeposit {
+            to: TxKind::Call(L2_CROSS_DOMAIN_MESSENGER_ADDRESS),
+            input: Bytes::from_static(&hex!("deadbeef")),
+            ..deposit
+        };
+        assert_eq!(deposit.cross_domain_message(), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! Output roots and super roots, which commit to the state of L2 chains.
+
+use crate::{InteropBlockReplacementDepositSource, OpBlock};
+use alloc::vec::Vec;
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U256, keccak256};
+
+/// The version of [`OutputRootV0`].
+pub const OUTPUT_ROOT_VERSION_V0: B256 = B256::ZERO;
+
+/// The length of the encoding of an [`OutputRootV0`].
+pub const OUTPUT_ROOT_V0_LEN: usize = 32 * 4;
+
+/// The version of [`SuperRoot`].
+pub const SUPER_ROOT_VERSION_V1: u8 = 1;
+
+/// The length of the encoding of an [`OutputRootWithChain`] in a [`SuperRoot`].
+pub const OUTPUT_ROOT_WITH_CHAIN_LEN: usize = 32 * 2;
+
+/// An error that can occur when decoding an output root or a super root.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum OutputRootError {
+    /// The encoding does not have a valid length.
+    #[error("invalid encoding length: {0}")]
+    InvalidLength(usize),
+    /// The output root version is not supported.
+    #[error("unsupported output root version: {0}")]
+    UnsupportedOutputRootVersion(B256),
+    /// The super root version is not supported.
+    #[error("unsupported super root version: {0}")]
+    UnsupportedSuperRootVersion(u8),
+    /// A chain ID of a super root does not fit in 64 bits.
+    #[error("chain ID overflows")]
+    ChainIdOverflow,
+    /// The chain IDs of a super root are not strictly increasing.
+    #[error("super root chain IDs are not strictly increasing")]
+    UnsortedChainIds,
+}
+
+/// The version 0 output root of an L2 block, which commits to its state root, the storage root of
+/// its `L2ToL1MessagePasser` and its hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutputRootV0 {
+    /// The state root of the block.
+    pub state_root: B256,
+    /// The storage root of the `L2ToL1MessagePasser` after the block.
+    pub message_passer_storage_root: B256,
+    /// The hash of the block.
+    pub block_hash: B256,
+}
+
+impl OutputRootV0 {
+    /// Creates a new [`OutputRootV0`].
+    pub const fn new(
+        state_root: B256,
+        message_passer_storage_root: B256,
+        block_hash: B256,
+    ) -> Self {
+        Self { state_root, message_passer_storage_root, block_hash }
+    }
+
+    /// Builds the output root of the block with the given header, hashing the header.
+    pub fn from_header(header: &Header, message_passer_storage_root: B256) -> Self {
+        Self::new(header.state_root, message_passer_storage_root, header.hash_slow())
+    }
+
+    /// Builds the output root of a block, hashing its header.
+    pub fn from_block(block: &OpBlock, message_passer_storage_root: B256) -> Self {
+        Self::from_header(&block.header, message_passer_storage_root)
+    }
+
+    /// Returns the encoding of the output root: the version followed by the state root, the
+    /// message passer storage root and the block hash.
+    pub fn encode(&self) -> [u8; OUTPUT_ROOT_V0_LEN] {
+        let mut out = [0; OUTPUT_ROOT_V0_LEN];
+        out[..32].copy_from_slice(OUTPUT_ROOT_VERSION_V0.as_slice());
+        out[32..64].copy_from_slice(self.state_root.as_slice());
+        out[64..96].copy_from_slice(self.message_passer_storage_root.as_slice());
+        out[96..].copy_from_slice(self.block_hash.as_slice());
+        out
+    }
+
+    /// Decodes an output root from its encoding.
+    pub fn decode(data: &[u8]) -> Result<Self, OutputRootError> {
+        if data.len() != OUTPUT_ROOT_V0_LEN {
+            return Err(OutputRootError::InvalidLength(data.len()));
+        }
+        let version = B256::from_slice(&data[..32]);
+        if version != OUTPUT_ROOT_VERSION_V0 {
+            return Err(OutputRootError::UnsupportedOutputRootVersion(version));
+        }
+        Ok(Self::new(
+            B256::from_slice(&data[32..64]),
+            B256::from_slice(&data[64..96]),
+            B256::from_slice(&data[96..]),
+        ))
+    }
+
+    /// Returns the output root: the hash of the encoding.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encode())
+    }
+}
+
+impl From<&OutputRootV0> for InteropBlockReplacementDepositSource {
+    fn from(output_root: &OutputRootV0) -> Self {
+        Self::new(output_root.hash())
+    }
+}
+
+/// The output root of a chain in a [`SuperRoot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OutputRootWithChain {
+    /// The chain ID of the chain.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub chain_id: u64,
+    /// The output root of the chain.
+    pub output_root: B256,
+}
+
+impl OutputRootWithChain {
+    /// Creates a new [`OutputRootWithChain`].
+    pub const fn new(chain_id: u64, output_root: B256) -> Self {
+        Self { chain_id, output_root }
+    }
+}
+
+/// The version 1 super root, which commits to the output roots of the chains of an interop
+/// dependency set at a timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SuperRoot {
+    /// The timestamp of the super root.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub timestamp: u64,
+    /// The output roots of the chains, sorted by chain ID.
+    pub output_roots: Vec<OutputRootWithChain>,
+}
+
+impl SuperRoot {
+    /// Creates a new [`SuperRoot`], sorting the output roots by chain ID.
+    pub fn new(timestamp: u64, mut output_roots: Vec<OutputRootWithChain>) -> Self {
+        output_roots.sort_by_key(|output_root| output_root.chain_id);
+        Self { timestamp, output_roots }
+    }
+
+    /// Returns the encoding of the super root: the version byte and the big-endian timestamp,
+    /// followed by the 32-byte chain ID and the output root of each chain.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out =
+            Vec::with_capacity(1 + 8 + OUTPUT_ROOT_WITH_CHAIN_LEN * self.output_roots.len());
+        out.push(SUPER_ROOT_VERSION_V1);
+        out.extend_from_slice(&self.timestamp.to_be_bytes());
+        for output_root in &self.output_roots {
+            out.extend_from_slice(&U256::from(output_root.chain_id).to_be_bytes::<32>());
+            out.extend_from_slice(output_root.output_root.as_slice());
+        }
+        out
+    }
+
+    /// Decodes a super root from its encoding, which holds at least one output root, with strictly
+    /// increasing chain IDs.
+    pub fn decode(data: &[u8]) -> Result<Self, OutputRootError> {
+        let Some((&version, data)) = data.split_first() else {
+            return Err(OutputRootError::InvalidLength(0));
+        };
+        if version != SUPER_ROOT_VERSION_V1 {
+            return Err(OutputRootError::UnsupportedSuperRootVersion(version));
+        }
+        if data.len() < 8 + OUTPUT_ROOT_WITH_CHAIN_LEN
+            || (data.len() - 8) % OUTPUT_ROOT_WITH_CHAIN_LEN != 0
+        {
+            return Err(OutputRootError::InvalidLength(data.len() + 1));
+        }
+        let timestamp = u64::from_be_bytes(data[..8].try_into().expect("sufficient length"));
+        let output_roots: Vec<OutputRootWithChain> = data[8..]
+            .chunks_exact(OUTPUT_ROOT_WITH_CHAIN_LEN)
+            .map(|chunk| {
+                let chain_id = U256::from_be_slice(&chunk[..32])
+                    .try_into()
+                    .map_err(|_| OutputRootError::ChainIdOverflow)?;
+                Ok(OutputRootWithChain::new(chain_id, B256::from_slice(&chunk[32..])))
+            })
+            .collect::<Result<_, _>>()?;
+        if output_roots.windows(2).any(|pair| pair[0].chain_id >= pair[1].chain_id) {
+            return Err(OutputRootError::UnsortedChainIds);
+        }
+        Ok(Self { timestamp, output_roots })
+    }
+
+    /// Returns the super root: the hash of the encoding.
+    pub fn hash(&self) -> B256 {
+        keccak256(self.encode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_primitives::b256;
+
+    fn output_root() -> OutputRootV0 {
+        OutputRootV0::new(B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3))
+    }
+
+    fn super_root() -> SuperRoot {
+        SuperRoot::new(
+            1_735_000_000,
+            vec![
+                OutputRootWithChain::new(8453, B256::repeat_byte(0xbb)),
+                OutputRootWithChain::new(10, B256::repeat_byte(0xaa)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_output_root_v0_hash() {
+        assert_eq!(
+            output_root().hash(),
+            b256!("0xfa846ba062c4f02c422636c114d4c22c219e0d7f9db2db9621eb6f655ac8a51f")
+        );
+        let source = InteropBlockReplacementDepositSource::from(&output_root());
+        assert_eq!(source.output_root, output_root().hash());
+    }
+
+    #[test]
+    fn test_output_root_v0_from_block() {
+        let block = OpBlock {
+            header: Header { state_root: B256::repeat_byte(1), ..Default::default() },
+            body: Default::default(),
+        };
+        let output_root = OutputRootV0::from_block(&block, B256::repeat_byte(2));
+        assert_eq!(output_root.state_root, B256::repeat_byte(1));
+        assert_eq!(output_root.message_passer_storage_root, B256::repeat_byte(2));
+        assert_eq!(output_root.block_hash, block.header.hash_slow());
+    }
+
+    #[test]
+    fn test_output_root_v0_roundtrip() {
+        let encoded = output_root().encode();
+        assert_eq!(OutputRootV0::decode(&encoded), Ok(output_root()));
+        assert_eq!(OutputRootV0::decode(&encoded[1..]), Err(OutputRootError::InvalidLength(127)));
+
+        let mut invalid = encoded;
+        invalid[31] = 1;
+        assert_eq!(
+            OutputRootV0::decode(&invalid),
+            Err(OutputRootError::UnsupportedOutputRootVersion(B256::with_last_byte(1)))
+        );
+    }
+
+    #[test]
+    fn test_super_root_hash() {
+        let super_root = super_root();
+        assert_eq!(super_root.output_roots[0].chain_id, 10);
+        assert_eq!(super_root.encode().len(), 1 + 8 + 2 * OUTPUT_ROOT_WITH_CHAIN_LEN);
+        assert_eq!(
+            super_root.hash(),
+            b256!("0xcd9ba6d36d395304f08893dd503876a4ade3c0f20be81faffda9aea161f78ad6")
+        );
+    }
+
+    #[test]
+    fn test_super_root_roundtrip() {
+        let encoded = super_root().encode();
+        assert_eq!(SuperRoot::decode(&encoded), Ok(super_root()));
+
+        assert_eq!(SuperRoot::decode(&[]), Err(OutputRootError::InvalidLength(0)));
+        assert_eq!(SuperRoot::decode(&encoded[..9]), Err(OutputRootError::InvalidLength(9)));
+        assert_eq!(
+            SuperRoot::decode(&encoded[..encoded.len() - 1]),
+            Err(OutputRootError::InvalidLength(encoded.len() - 1))
+        );
+        let mut invalid = encoded.clone();
+        invalid[0] = 0;
+        assert_eq!(
+            SuperRoot::decode(&invalid),
+            Err(OutputRootError::UnsupportedSuperRootVersion(0))
+        );
+        let mut invalid = encoded.clone();
+        invalid[9] = 1;
+        assert_eq!(SuperRoot::decode(&invalid), Err(OutputRootError::ChainIdOverflow));
+
+        // The output roots must be sorted by chain ID, without duplicates.
+        let (first, second) = encoded[9..].split_at(OUTPUT_ROOT_WITH_CHAIN_LEN);
+        let unsorted = [&encoded[..9], second, first].concat();
+        assert_eq!(SuperRoot::decode(&unsorted), Err(OutputRootError::UnsortedChainIds));
+        let duplicated = [&encoded[..9], first, first].concat();
+        assert_eq!(SuperRoot::decode(&duplicated), Err(OutputRootError::UnsortedChainIds));
+    }
+}
@@ -0,0 +1,33 @@
+//! Addresses of the OP Stack L2 predeploys.
+//!
+//! See: <https://specs.optimism.io/protocol/predeploys.html>
+
+use alloy_primitives::{Address, address};
+
+/// The address of the `L2CrossDomainMessenger` predeploy, which relays messages sent from L1.
+pub const L2_CROSS_DOMAIN_MESSENGER_ADDRESS: Address =
+    address!("0x4200000000000000000000000000000000000007");
+
+/// The address of the `L2StandardBridge` predeploy, which bridges ETH and ERC-20 tokens.
+pub const L2_STANDARD_BRIDGE_ADDRESS: Address =
+    address!("0x4200000000000000000000000000000000000010");
+
+/// The address of the `L1Block` predeploy, which holds the attributes of the latest L1 origin.
+pub const L1_BLOCK_ADDRESS: Address = address!("0x4200000000000000000000000000000000000015");
+
+/// The address of the `GasPriceOracle` predeploy.
+pub const GAS_PRICE_ORACLE_ADDRESS: Address =
+    address!("0x420000000000000000000000000000000000000F");
+
+/// The address of the `L2ToL1MessagePasser` predeploy, which stores the withdrawals initiated on
+/// L2.
+pub const L2_TO_L1_MESSAGE_PASSER_ADDRESS: Address =
+    address!("0x4200000000000000000000000000000000000016");
+
+/// The address of the `OperatorFeeVault` predeploy, which collects the Isthmus operator fee.
+pub const OPERATOR_FEE_VAULT_ADDRESS: Address =
+    address!("0x420000000000000000000000000000000000001b");
+
+/// The address of the `L2ToL2CrossDomainMessenger` predeploy.
+pub const L2_TO_L2_CROSS_DOMAIN_MESSENGER_ADDRESS: Address =
+    address!("0x4200000000000000000000000000000000000023");
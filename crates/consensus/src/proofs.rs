@@ -0,0 +1,375 @@
+//! Merkle root computations for OP Stack blocks.
+
+use crate::{OpBlock, OpHardforks, OpReceiptEnvelope, OpTxEnvelope};
+use alloy_consensus::proofs::{calculate_ommers_root, ordered_trie_root_with_encoder};
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal};
+use alloy_primitives::B256;
+
+/// A commitment of a block header that does not match the block.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlockIntegrityError {
+    /// The block hash does not match the hash of the header.
+    #[error("block hash mismatch: expected {expected}, got {got}")]
+    BlockHash {
+        /// The hash of the header.
+        expected: B256,
+        /// The claimed block hash.
+        got: B256,
+    },
+    /// The transactions root does not match the transactions of the block.
+    #[error("transactions root mismatch: expected {expected}, got {got}")]
+    TransactionsRoot {
+        /// The root of the transactions of the block.
+        expected: B256,
+        /// The transactions root of the header.
+        got: B256,
+    },
+    /// The withdrawals root does not match the withdrawals of the block, or the storage root of
+    /// the `L2ToL1MessagePasser` since Isthmus.
+    #[error("withdrawals root mismatch: expected {expected:?}, got {got:?}")]
+    WithdrawalsRoot {
+        /// The expected withdrawals root.
+        expected: Option<B256>,
+        /// The withdrawals root of the header.
+        got: Option<B256>,
+    },
+    /// The ommers hash does not match the ommers of the block.
+    #[error("ommers hash mismatch: expected {expected}, got {got}")]
+    OmmersHash {
+        /// The root of the ommers of the block.
+        expected: B256,
+        /// The ommers hash of the header.
+        got: B256,
+    },
+}
+
+/// Calculates the transactions root of a block.
+pub fn calculate_transaction_root(transactions: &[OpTxEnvelope]) -> B256 {
+    alloy_consensus::proofs::calculate_transaction_root(transactions)
+}
+
+/// Calculates the withdrawals root of a block at the given timestamp.
+///
+/// There is no withdrawals root before Canyon. Between Canyon and Isthmus, it is the root of the
+/// withdrawals list of the block, which is empty on OP Stack chains. Since Isthmus, the withdrawals
+/// root holds the storage root of the `L2ToL1MessagePasser` after the block, which cannot be
+/// derived from the block and is taken from `message_passer_storage_root`. It is `None` if not
+/// provided.
+pub fn calculate_withdrawals_root(
+    withdrawals: Option<&[Withdrawal]>,
+    timestamp: u64,
+    hardforks: &impl OpHardforks,
+    message_passer_storage_root: Option<B256>,
+) -> Option<B256> {
+    if hardforks.is_isthmus_active_at_timestamp(timestamp) {
+        message_passer_storage_root
+    } else if hardforks.is_canyon_active_at_timestamp(timestamp) {
+        Some(alloy_consensus::proofs::calculate_withdrawals_root(withdrawals.unwrap_or_default()))
+    } else {
+        None
+    }
+}
+
+/// Verifies that the header of a block commits to its body, and that `block_hash` is the hash of
+/// its header.
+///
+/// The transactions root, ommers hash and withdrawals root of the header are recomputed from the
+/// body. Since Isthmus, the withdrawals root is checked against `message_passer_storage_root` if
+/// provided, and only required to be present otherwise. The first mismatching commitment is
+/// returned.
+pub fn verify_block_integrity(
+    block: &OpBlock,
+    block_hash: B256,
+    hardforks: &impl OpHardforks,
+    message_passer_storage_root: Option<B256>,
+) -> Result<(), BlockIntegrityError> {
+    let header = &block.header;
+
+    let expected = header.hash_slow();
+    if expected != block_hash {
+        return Err(BlockIntegrityError::BlockHash { expected, got: block_hash });
+    }
+
+    let expected = calculate_transaction_root(&block.body.transactions);
+    if expected != header.transactions_root {
+        return Err(BlockIntegrityError::TransactionsRoot {
+            expected,
+            got: header.transactions_root,
+        });
+    }
+
+    let expected = calculate_ommers_root(&block.body.ommers);
+    if expected != header.ommers_hash {
+        return Err(BlockIntegrityError::OmmersHash { expected, got: header.ommers_hash });
+    }
+
+    let expected = calculate_withdrawals_root(
+        block.body.withdrawals.as_ref().map(|withdrawals| &withdrawals[..]),
+        header.timestamp,
+        hardforks,
+        message_passer_storage_root,
+    );
+    let matches = if hardforks.is_isthmus_active_at_timestamp(header.timestamp)
+        && message_passer_storage_root.is_none()
+    {
+        header.withdrawals_root.is_some()
+    } else {
+        expected == header.withdrawals_root
+    };
+    if !matches {
+        return Err(BlockIntegrityError::WithdrawalsRoot {
+            expected,
+            got: header.withdrawals_root,
+        });
+    }
+
+    Ok(())
+}
+
+/// Calculates the receipts root of a block at the given timestamp.
+///
+/// Before Canyon, the deposit nonce of deposit receipts, introduced with Regolith, is not
+/// committed to the receipts root, and neither is the deposit receipt version. Both are committed
+/// since Canyon, and the remaining receipt types are committed with their [EIP-2718] encoding.
+///
+/// Receipts returned by RPC providers carry the deposit nonce regardless of the hardfork, so it is
+/// stripped here before Canyon.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub fn calculate_receipt_root(
+    receipts: &[OpReceiptEnvelope],
+    timestamp: u64,
+    hardforks: &impl OpHardforks,
+) -> B256 {
+    if hardforks.is_canyon_active_at_timestamp(timestamp) {
+        return alloy_consensus::proofs::calculate_receipt_root(receipts);
+    }
+    ordered_trie_root_with_encoder(receipts, |receipt, buf| match receipt {
+        OpReceiptEnvelope::Deposit(deposit)
+            if deposit.receipt.deposit_nonce.is_some()
+                || deposit.receipt.deposit_receipt_version.is_some() =>
+        {
+            let mut deposit = deposit.clone();
+            deposit.receipt.deposit_nonce = None;
+            deposit.receipt.deposit_receipt_version = None;
+            OpReceiptEnvelope::Deposit(deposit).encode_2718(buf)
+        }
+        _ => receipt.encode_2718(buf),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ForkCondition, L1BlockInfoEcotone, L1BlockInfoTx, OpDepositReceipt,
+        OpDepositReceiptWithBloom, OpHardfork, OpHardforkSchedule,
+    };
+    use alloc::vec;
+    use alloy_consensus::{
+        BlockBody, EMPTY_ROOT_HASH, Header, Receipt, ReceiptWithBloom, Sealable, Signed, TxEip1559,
+    };
+    use alloy_eips::eip4895::Withdrawals;
+    use alloy_primitives::{Log, LogData, Signature, address, b256};
+
+    fn schedule(canyon_time: u64) -> OpHardforkSchedule {
+        OpHardforkSchedule::new([
+            (OpHardfork::Bedrock, ForkCondition::Block(0)),
+            (OpHardfork::Regolith, ForkCondition::Timestamp(0)),
+            (OpHardfork::Canyon, ForkCondition::Timestamp(canyon_time)),
+        ])
+        .unwrap()
+    }
+
+    fn receipt(cumulative_gas_used: u64) -> ReceiptWithBloom<Receipt> {
+        let log = Log {
+            address: address!("0x4200000000000000000000000000000000000016"),
+            data: LogData::new_unchecked(
+                vec![b256!("0x02a52367d10742d8032712c1bb8e0144ff1ec5ffda1ed7d70bb05a2744955054")],
+                Default::default(),
+            ),
+        };
+        Receipt { status: true.into(), cumulative_gas_used, logs: vec![log] }.into()
+    }
+
+    fn deposit_receipt(nonce: Option<u64>, version: Option<u64>) -> OpReceiptEnvelope {
+        OpReceiptEnvelope::Deposit(OpDepositReceiptWithBloom {
+            receipt: OpDepositReceipt {
+                inner: Receipt { status: true.into(), cumulative_gas_used: 46913, logs: vec![] },
+                deposit_nonce: nonce,
+                deposit_receipt_version: version,
+            },
+            logs_bloom: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_receipt_root_empty() {
+        assert_eq!(calculate_receipt_root(&[], 0, &schedule(0)), EMPTY_ROOT_HASH);
+        assert_eq!(calculate_receipt_root(&[], 0, &schedule(u64::MAX)), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn test_receipt_root_deposit_nonce_before_canyon() {
+        let hardforks = schedule(100);
+        let stripped = [deposit_receipt(None, None), OpReceiptEnvelope::Eip1559(receipt(70_000))];
+        let with_nonce =
+            [deposit_receipt(Some(4012991), None), OpReceiptEnvelope::Eip1559(receipt(70_000))];
+        let root = alloy_consensus::proofs::calculate_receipt_root(&stripped);
+
+        // The deposit nonce is not committed before Canyon.
+        assert_eq!(calculate_receipt_root(&with_nonce, 99, &hardforks), root);
+        assert_eq!(calculate_receipt_root(&stripped, 99, &hardforks), root);
+        assert_ne!(alloy_consensus::proofs::calculate_receipt_root(&with_nonce), root);
+    }
+
+    #[test]
+    fn test_receipt_root_deposit_version_since_canyon() {
+        let hardforks = schedule(100);
+        let receipts = [
+            deposit_receipt(Some(4012991), Some(1)),
+            OpReceiptEnvelope::Legacy(receipt(50_000)),
+            OpReceiptEnvelope::Eip2930(receipt(60_000)),
+            OpReceiptEnvelope::Eip1559(receipt(70_000)),
+            OpReceiptEnvelope::Eip7702(receipt(80_000)),
+        ];
+        let root = alloy_consensus::proofs::calculate_receipt_root(&receipts);
+
+        // The deposit nonce and receipt version are committed since Canyon.
+        assert_eq!(calculate_receipt_root(&receipts, 100, &hardforks), root);
+        let mut stripped = receipts.clone();
+        stripped[0] = deposit_receipt(Some(4012991), None);
+        assert_ne!(calculate_receipt_root(&stripped, 100, &hardforks), root);
+
+        // The same receipts across the Canyon boundary commit to different roots.
+        assert_ne!(calculate_receipt_root(&receipts, 99, &hardforks), root);
+    }
+
+    #[test]
+    fn test_receipt_root_envelope_types() {
+        // The typed receipts commit to their type byte, while legacy receipts have none.
+        let hardforks = schedule(0);
+        let roots = [
+            OpReceiptEnvelope::Legacy(receipt(21_000)),
+            OpReceiptEnvelope::Eip2930(receipt(21_000)),
+            OpReceiptEnvelope::Eip1559(receipt(21_000)),
+            OpReceiptEnvelope::Eip7702(receipt(21_000)),
+        ]
+        .map(|receipt| calculate_receipt_root(&[receipt], 0, &hardforks));
+        for (i, root) in roots.iter().enumerate() {
+            assert!(roots[i + 1..].iter().all(|other| other != root));
+        }
+    }
+
+    /// A schedule with all hardforks up to Isthmus active at `isthmus_time`.
+    fn isthmus_schedule(isthmus_time: u64) -> OpHardforkSchedule {
+        OpHardforkSchedule::new(
+            OpHardfork::ALL.into_iter().take_while(|fork| *fork != OpHardfork::Jovian).map(
+                |fork| match fork {
+                    OpHardfork::Bedrock => (fork, ForkCondition::Block(0)),
+                    _ => (fork, ForkCondition::Timestamp(isthmus_time)),
+                },
+            ),
+        )
+        .unwrap()
+    }
+
+    fn block(hardforks: &impl OpHardforks, timestamp: u64) -> OpBlock {
+        let l1_info = L1BlockInfoTx::Ecotone(L1BlockInfoEcotone::default()).to_deposit(true);
+        let user_tx =
+            Signed::new_unchecked(TxEip1559::default(), Signature::test_signature(), B256::ZERO);
+        let transactions = vec![OpTxEnvelope::Deposit(l1_info.seal_slow()), user_tx.into()];
+        let withdrawals: Option<Withdrawals> =
+            hardforks.is_canyon_active_at_timestamp(timestamp).then(Default::default);
+        let header = Header {
+            timestamp,
+            transactions_root: calculate_transaction_root(&transactions),
+            withdrawals_root: calculate_withdrawals_root(
+                withdrawals.as_ref().map(|withdrawals| &withdrawals[..]),
+                timestamp,
+                hardforks,
+                Some(B256::repeat_byte(1)),
+            ),
+            ..Default::default()
+        };
+        OpBlock { header, body: BlockBody { transactions, ommers: vec![], withdrawals } }
+    }
+
+    #[test]
+    fn test_withdrawals_root() {
+        let root = Some(B256::repeat_byte(1));
+        assert_eq!(calculate_withdrawals_root(None, 99, &schedule(100), root), None);
+        assert_eq!(
+            calculate_withdrawals_root(Some(&[]), 100, &schedule(100), root),
+            Some(EMPTY_ROOT_HASH)
+        );
+        assert_eq!(calculate_withdrawals_root(Some(&[]), 100, &isthmus_schedule(100), root), root);
+        assert_eq!(calculate_withdrawals_root(Some(&[]), 100, &isthmus_schedule(100), None), None);
+    }
+
+    #[test]
+    fn test_verify_block_integrity() {
+        let hardforks = schedule(100);
+        for timestamp in [99, 100] {
+            let block = block(&hardforks, timestamp);
+            let hash = block.header.hash_slow();
+            assert_eq!(verify_block_integrity(&block, hash, &hardforks, None), Ok(()));
+            assert_eq!(
+                verify_block_integrity(&block, B256::ZERO, &hardforks, None),
+                Err(BlockIntegrityError::BlockHash { expected: hash, got: B256::ZERO })
+            );
+        }
+
+        let mut block = block(&hardforks, 100);
+        let got = block.header.transactions_root;
+        block.body.transactions.pop();
+        let expected = calculate_transaction_root(&block.body.transactions);
+        assert_eq!(
+            verify_block_integrity(&block, block.header.hash_slow(), &hardforks, None),
+            Err(BlockIntegrityError::TransactionsRoot { expected, got })
+        );
+
+        block.header.transactions_root = expected;
+        block.body.ommers.push(Header::default());
+        assert!(matches!(
+            verify_block_integrity(&block, block.header.hash_slow(), &hardforks, None),
+            Err(BlockIntegrityError::OmmersHash { .. })
+        ));
+
+        block.body.ommers.clear();
+        block.header.withdrawals_root = None;
+        assert_eq!(
+            verify_block_integrity(&block, block.header.hash_slow(), &hardforks, None),
+            Err(BlockIntegrityError::WithdrawalsRoot {
+                expected: Some(EMPTY_ROOT_HASH),
+                got: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_block_integrity_isthmus() {
+        let hardforks = isthmus_schedule(0);
+        let block = block(&hardforks, 100);
+        let hash = block.header.hash_slow();
+        let storage_root = B256::repeat_byte(1);
+
+        // The message passer storage root is only checked if provided.
+        assert_eq!(verify_block_integrity(&block, hash, &hardforks, None), Ok(()));
+        assert_eq!(verify_block_integrity(&block, hash, &hardforks, Some(storage_root)), Ok(()));
+        assert_eq!(
+            verify_block_integrity(&block, hash, &hardforks, Some(EMPTY_ROOT_HASH)),
+            Err(BlockIntegrityError::WithdrawalsRoot {
+                expected: Some(EMPTY_ROOT_HASH),
+                got: Some(storage_root)
+            })
+        );
+
+        let mut block = block;
+        block.header.withdrawals_root = None;
+        assert_eq!(
+            verify_block_integrity(&block, block.header.hash_slow(), &hardforks, None),
+            Err(BlockIntegrityError::WithdrawalsRoot { expected: None, got: None })
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     OpPooledTransaction, OpTypedTransaction, TxDeposit,
+    fast_lz::flz_compress_len,
     transaction::{OpDepositInfo, OpTransactionInfo},
 };
 use alloy_consensus::{
@@ -422,6 +423,12 @@ impl OpTxEnvelope {
             Self::Deposit(t) => t.eip2718_encoded_length(),
         }
     }
+
+    /// Returns the FastLZ compressed size of the EIP-2718 encoded transaction, which prices its
+    /// data availability since Fjord.
+    pub fn flz_compressed_size(&self) -> u32 {
+        flz_compress_len(&self.encoded_2718())
+    }
 }
 
 #[cfg(feature = "k256")]
@@ -653,6 +660,7 @@ pub mod serde_bincode_compat {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SAMPLE_CONTRACT_CALL;
     use alloc::vec;
     use alloy_consensus::{SignableTransaction, Transaction};
     use alloy_primitives::{Address, B256, Bytes, Signature, TxKind, U256, hex};
@@ -687,6 +695,12 @@ mod tests {
         assert!(!tx_envelope.is_system_transaction());
     }
 
+    #[test]
+    fn test_flz_compressed_size() {
+        let tx = OpTxEnvelope::decode_2718(&mut &SAMPLE_CONTRACT_CALL[..]).unwrap();
+        assert_eq!(tx.flz_compressed_size(), 202);
+    }
+
     #[test]
     fn test_encode_decode_deposit() {
         let tx = TxDeposit {
@@ -3,6 +3,11 @@
 mod deposit;
 pub use deposit::{DepositTransaction, TxDeposit};
 
+mod portal;
+pub use portal::{
+    DEPOSIT_EVENT_ABI, DEPOSIT_EVENT_ABI_HASH, DEPOSIT_EVENT_VERSION_0, DepositLogError,
+};
+
 mod tx_type;
 pub use tx_type::DEPOSIT_TX_TYPE_ID;
 
@@ -0,0 +1,423 @@
+//! Decoding and encoding of OptimismPortal `TransactionDeposited` events.
+//!
+//! See: <https://specs.optimism.io/protocol/deposits.html#deposit-contract>
+
+use super::TxDeposit;
+use crate::UserDepositSource;
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{Address, B256, Bytes, Log, LogData, TxKind, U256, b256};
+
+/// The ABI signature of the OptimismPortal `TransactionDeposited` event.
+pub const DEPOSIT_EVENT_ABI: &str = "TransactionDeposited(address,address,uint256,bytes)";
+
+/// The first topic of the OptimismPortal `TransactionDeposited` event,
+/// `keccak256("TransactionDeposited(address,address,uint256,bytes)")`.
+pub const DEPOSIT_EVENT_ABI_HASH: B256 =
+    b256!("0xb3813568d9991fc951961fcb4c784893574240a28925604d09fc577c55bb7c32");
+
+/// The only supported version of the `TransactionDeposited` event's opaque data.
+pub const DEPOSIT_EVENT_VERSION_0: B256 = B256::ZERO;
+
+/// The minimum length of version 0 opaque data: `mint`, `value`, `gasLimit` and `isCreation`.
+const OPAQUE_DATA_V0_MIN_LEN: usize = 32 + 32 + 8 + 1;
+
+/// An error that can occur when decoding a `TransactionDeposited` log into a [`TxDeposit`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum DepositLogError {
+    /// The log does not have exactly 4 topics.
+    #[error("unexpected number of deposit event topics: {0}")]
+    UnexpectedTopicsLen(usize),
+    /// The first topic is not the `TransactionDeposited` event selector.
+    #[error("invalid deposit event selector: {0}")]
+    InvalidSelector(B256),
+    /// The log data is too short to contain the opaque data slice header.
+    #[error("incomplete opaque data slice header: {0} bytes")]
+    IncompleteOpaqueData(usize),
+    /// The log data is not a multiple of 32 bytes.
+    #[error("deposit log data is not 32-byte aligned: {0} bytes")]
+    UnalignedData(usize),
+    /// The ABI offset of the opaque data is not 32.
+    #[error("invalid opaque data slice header offset: {0}")]
+    InvalidOpaqueDataOffset(U256),
+    /// The ABI length of the opaque data does not match the log data, or is not minimally padded.
+    #[error("invalid opaque data slice header length: {0}")]
+    InvalidOpaqueDataLength(U256),
+    /// The deposit event version is not supported.
+    #[error("unsupported deposit event version: {0}")]
+    UnsupportedVersion(B256),
+    /// The version 0 opaque data is too short.
+    #[error("unexpected version 0 opaque data length: {0}")]
+    UnexpectedOpaqueDataLength(usize),
+    /// The mint value does not fit in a `u128`.
+    #[error("mint value overflows u128: {0}")]
+    MintOverflow(U256),
+}
+
+impl TxDeposit {
+    /// Decodes a user deposit transaction from an OptimismPortal `TransactionDeposited` log.
+    ///
+    /// The `l1_block_hash` and `log_index` identify the log on L1 and are used to derive the
+    /// deposit's source hash through [`UserDepositSource`]. The log's emitting address is not
+    /// checked; callers are expected to filter logs by the portal address of their chain.
+    ///
+    /// Only version 0 of the opaque data is supported, which is tightly packed as:
+    ///
+    /// `mint (uint256) | value (uint256) | gasLimit (uint64) | isCreation (uint8) | data`
+    pub fn from_deposit_log(
+        log: &Log,
+        l1_block_hash: B256,
+        log_index: u64,
+    ) -> Result<Self, DepositLogError> {
+        let topics = log.topics();
+        if topics.len() != 4 {
+            return Err(DepositLogError::UnexpectedTopicsLen(topics.len()));
+        }
+        if topics[0] != DEPOSIT_EVENT_ABI_HASH {
+            return Err(DepositLogError::InvalidSelector(topics[0]));
+        }
+
+        let data = log.data.data.as_ref();
+        if data.len() < 64 {
+            return Err(DepositLogError::IncompleteOpaqueData(data.len()));
+        }
+        if data.len() % 32 != 0 {
+            return Err(DepositLogError::UnalignedData(data.len()));
+        }
+
+        // Solidity ABI-encodes the `opaqueData` bytes as a single dynamic value: the first word is
+        // the offset of the content, which is always 32.
+        let offset = U256::from_be_slice(&data[..32]);
+        if offset != U256::from(32) {
+            return Err(DepositLogError::InvalidOpaqueDataOffset(offset));
+        }
+
+        // The second word is the content length. It must fit in the remaining data, and the data
+        // must be minimally padded, i.e. adding another word would exceed it.
+        let length = U256::from_be_slice(&data[32..64]);
+        let remaining = data.len() - 64;
+        let opaque_len = usize::try_from(length)
+            .ok()
+            .filter(|len| *len <= remaining && len + 32 > remaining)
+            .ok_or(DepositLogError::InvalidOpaqueDataLength(length))?;
+        let opaque_data = &data[64..64 + opaque_len];
+
+        let version = topics[3];
+        if version != DEPOSIT_EVENT_VERSION_0 {
+            return Err(DepositLogError::UnsupportedVersion(version));
+        }
+
+        let from = Address::from_word(topics[1]);
+        let to = Address::from_word(topics[2]);
+        let source_hash = UserDepositSource::new(l1_block_hash, log_index).source_hash();
+
+        Self::from_opaque_data_v0(source_hash, from, to, opaque_data)
+    }
+
+    /// Returns the version 0 `opaqueData` that the OptimismPortal packs for this deposit.
+    ///
+    /// This is the inverse of the field layout decoded by [`TxDeposit::from_deposit_log`]. The
+    /// source hash and system transaction flag are not part of the opaque data.
+    pub fn deposit_log_opaque_data(&self) -> Bytes {
+        let mut data = Vec::with_capacity(OPAQUE_DATA_V0_MIN_LEN + self.input.len());
+        data.extend_from_slice(&U256::from(self.mint).to_be_bytes::<32>());
+        data.extend_from_slice(&self.value.to_be_bytes::<32>());
+        data.extend_from_slice(&self.gas_limit.to_be_bytes());
+        data.push(self.to.is_create() as u8);
+        data.extend_from_slice(&self.input);
+        data.into()
+    }
+
+    /// Returns the `TransactionDeposited` log that the OptimismPortal at `portal_address` emits
+    /// for this deposit.
+    ///
+    /// The source hash is not encoded in the log, it is derived from the L1 block hash and the
+    /// index of the log within that block. Decoding the returned log with
+    /// [`TxDeposit::from_deposit_log`] yields this deposit back if its source hash was derived
+    /// from the same L1 block hash and log index.
+    pub fn to_deposit_log(&self, portal_address: Address) -> Log {
+        let to = self.to.to().copied().unwrap_or_default();
+        let topics = vec![
+            DEPOSIT_EVENT_ABI_HASH,
+            self.from.into_word(),
+            to.into_word(),
+            DEPOSIT_EVENT_VERSION_0,
+        ];
+
+        // ABI-encode the opaque data as a single dynamic `bytes` value, padded to a full word.
+        let opaque_data = self.deposit_log_opaque_data();
+        let padded_len = opaque_data.len().div_ceil(32) * 32;
+        let mut data = Vec::with_capacity(64 + padded_len);
+        data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(opaque_data.len()).to_be_bytes::<32>());
+        data.extend_from_slice(&opaque_data);
+        data.resize(64 + padded_len, 0);
+
+        Log { address: portal_address, data: LogData::new_unchecked(topics, data.into()) }
+    }
+
+    /// Decodes the version 0 opaque data of a `TransactionDeposited` event.
+    fn from_opaque_data_v0(
+        source_hash: B256,
+        from: Address,
+        to: Address,
+        data: &[u8],
+    ) -> Result<Self, DepositLogError> {
+        if data.len() < OPAQUE_DATA_V0_MIN_LEN {
+            return Err(DepositLogError::UnexpectedOpaqueDataLength(data.len()));
+        }
+
+        let mint = U256::from_be_slice(&data[..32]);
+        let mint = u128::try_from(mint).map_err(|_| DepositLogError::MintOverflow(mint))?;
+        let value = U256::from_be_slice(&data[32..64]);
+        let gas_limit = u64::from_be_bytes(data[64..72].try_into().expect("sufficient length"));
+        // If the creation flag is set, the deposit creates a contract and `to` is ignored.
+        let to = if data[72] == 0 { TxKind::Call(to) } else { TxKind::Create };
+        // The remainder of the opaque data is the transaction input, without a length prefix.
+        let input = Bytes::copy_from_slice(&data[OPAQUE_DATA_V0_MIN_LEN..]);
+
+        Ok(Self {
+            source_hash,
+            from,
+            to,
+            mint,
+            value,
+            gas_limit,
+            is_system_transaction: false,
+            input,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, keccak256};
+
+    const PORTAL: Address = address!("0xbEb5Fc579115071764c7423A4f12eDde41f106Ed");
+
+    /// ABI-encodes `opaque_data` as the data of a `TransactionDeposited` log.
+    fn abi_encode_opaque_data(opaque_data: &[u8]) -> Bytes {
+        let padded_len = opaque_data.len().div_ceil(32) * 32;
+        let mut data = Vec::with_capacity(64 + padded_len);
+        data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(opaque_data.len()).to_be_bytes::<32>());
+        data.extend_from_slice(opaque_data);
+        data.resize(64 + padded_len, 0);
+        data.into()
+    }
+
+    fn opaque_data_v0(
+        mint: u128,
+        value: U256,
+        gas: u64,
+        is_creation: bool,
+        input: &[u8],
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&U256::from(mint).to_be_bytes::<32>());
+        data.extend_from_slice(&value.to_be_bytes::<32>());
+        data.extend_from_slice(&gas.to_be_bytes());
+        data.push(is_creation as u8);
+        data.extend_from_slice(input);
+        data
+    }
+
+    fn deposit_log(from: Address, to: Address, version: B256, data: Bytes) -> Log {
+        Log {
+            address: PORTAL,
+            data: LogData::new_unchecked(
+                vec![DEPOSIT_EVENT_ABI_HASH, from.into_word(), to.into_word(), version],
+                data,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_deposit_event_abi_hash() {
+        assert_eq!(keccak256(DEPOSIT_EVENT_ABI), DEPOSIT_EVENT_ABI_HASH);
+    }
+
+    #[test]
+    fn test_decode_deposit_log() {
+        let from = address!("0x1111111111111111111111111111111111111111");
+        let to = address!("0x2222222222222222222222222222222222222222");
+        let opaque = opaque_data_v0(10, U256::from(20), 100_000, false, &[0xde, 0xad]);
+        let log = deposit_log(from, to, DEPOSIT_EVENT_VERSION_0, abi_encode_opaque_data(&opaque));
+
+        let l1_block_hash = B256::with_last_byte(1);
+        let tx = TxDeposit::from_deposit_log(&log, l1_block_hash, 7).unwrap();
+
+        assert_eq!(
+            tx,
+            TxDeposit {
+                source_hash: UserDepositSource::new(l1_block_hash, 7).source_hash(),
+                from,
+                to: TxKind::Call(to),
+                mint: 10,
+                value: U256::from(20),
+                gas_limit: 100_000,
+                is_system_transaction: false,
+                input: Bytes::from_static(&[0xde, 0xad]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_log_contract_creation() {
+        let from = address!("0x1111111111111111111111111111111111111111");
+        let opaque = opaque_data_v0(0, U256::ZERO, 1_000_000, true, &[0x60, 0x80, 0x60, 0x40]);
+        let log = deposit_log(
+            from,
+            Address::ZERO,
+            DEPOSIT_EVENT_VERSION_0,
+            abi_encode_opaque_data(&opaque),
+        );
+
+        let tx = TxDeposit::from_deposit_log(&log, B256::ZERO, 0).unwrap();
+        assert_eq!(tx.to, TxKind::Create);
+        assert_eq!(tx.mint, 0);
+        assert_eq!(tx.gas_limit, 1_000_000);
+        assert_eq!(tx.input, Bytes::from_static(&[0x60, 0x80, 0x60, 0x40]));
+    }
+
+    #[test]
+    fn test_decode_deposit_log_invalid_topics() {
+        let opaque = opaque_data_v0(0, U256::ZERO, 0, false, &[]);
+        let mut log = deposit_log(
+            Address::ZERO,
+            Address::ZERO,
+            DEPOSIT_EVENT_VERSION_0,
+            abi_encode_opaque_data(&opaque),
+        );
+
+        let mut bad_selector = log.clone();
+        bad_selector.data.topics_mut()[0] = B256::ZERO;
+        assert_eq!(
+            TxDeposit::from_deposit_log(&bad_selector, B256::ZERO, 0),
+            Err(DepositLogError::InvalidSelector(B256::ZERO))
+        );
+
+        log.data.topics_mut_unchecked().pop();
+        assert_eq!(
+            TxDeposit::from_deposit_log(&log, B256::ZERO, 0),
+            Err(DepositLogError::UnexpectedTopicsLen(3))
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_log_unsupported_version() {
+        let opaque = opaque_data_v0(0, U256::ZERO, 0, false, &[]);
+        let version = B256::with_last_byte(1);
+        let log =
+            deposit_log(Address::ZERO, Address::ZERO, version, abi_encode_opaque_data(&opaque));
+        assert_eq!(
+            TxDeposit::from_deposit_log(&log, B256::ZERO, 0),
+            Err(DepositLogError::UnsupportedVersion(version))
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_log_invalid_data() {
+        let decode = |data: Vec<u8>| {
+            let log = deposit_log(Address::ZERO, Address::ZERO, B256::ZERO, data.into());
+            TxDeposit::from_deposit_log(&log, B256::ZERO, 0)
+        };
+
+        assert_eq!(decode(vec![0; 32]), Err(DepositLogError::IncompleteOpaqueData(32)));
+        assert_eq!(decode(vec![0; 65]), Err(DepositLogError::UnalignedData(65)));
+
+        let valid = abi_encode_opaque_data(&opaque_data_v0(0, U256::ZERO, 0, false, &[])).to_vec();
+
+        let mut bad_offset = valid.clone();
+        bad_offset[31] = 64;
+        assert_eq!(
+            decode(bad_offset),
+            Err(DepositLogError::InvalidOpaqueDataOffset(U256::from(64)))
+        );
+
+        // Content length exceeds the available data.
+        let mut too_long = valid.clone();
+        too_long[63] = 97;
+        assert_eq!(decode(too_long), Err(DepositLogError::InvalidOpaqueDataLength(U256::from(97))));
+
+        // Data is padded with an extra, unnecessary word.
+        let mut over_padded = valid;
+        over_padded.extend_from_slice(&[0; 32]);
+        assert_eq!(
+            decode(over_padded),
+            Err(DepositLogError::InvalidOpaqueDataLength(U256::from(OPAQUE_DATA_V0_MIN_LEN)))
+        );
+
+        // Opaque data is too short for version 0.
+        assert_eq!(
+            decode(abi_encode_opaque_data(&[0; 72]).to_vec()),
+            Err(DepositLogError::UnexpectedOpaqueDataLength(72))
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_log_mint_overflow() {
+        let mut opaque = opaque_data_v0(0, U256::ZERO, 0, false, &[]);
+        opaque[0] = 1;
+        let log = deposit_log(
+            Address::ZERO,
+            Address::ZERO,
+            DEPOSIT_EVENT_VERSION_0,
+            abi_encode_opaque_data(&opaque),
+        );
+        assert!(matches!(
+            TxDeposit::from_deposit_log(&log, B256::ZERO, 0),
+            Err(DepositLogError::MintOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_deposit_log() {
+        let tx = TxDeposit {
+            source_hash: B256::ZERO,
+            from: address!("0x1111111111111111111111111111111111111111"),
+            to: TxKind::Call(address!("0x2222222222222222222222222222222222222222")),
+            mint: 10,
+            value: U256::from(20),
+            gas_limit: 100_000,
+            is_system_transaction: false,
+            input: Bytes::from_static(&[0xde, 0xad]),
+        };
+
+        let opaque = opaque_data_v0(10, U256::from(20), 100_000, false, &[0xde, 0xad]);
+        assert_eq!(tx.deposit_log_opaque_data(), Bytes::from(opaque.clone()));
+        assert_eq!(
+            tx.to_deposit_log(PORTAL),
+            deposit_log(
+                address!("0x1111111111111111111111111111111111111111"),
+                address!("0x2222222222222222222222222222222222222222"),
+                DEPOSIT_EVENT_VERSION_0,
+                abi_encode_opaque_data(&opaque),
+            )
+        );
+    }
+
+    #[test]
+    fn test_deposit_log_roundtrip() {
+        let l1_block_hash = B256::with_last_byte(0xaa);
+        let log_index = 3;
+        let call = TxDeposit {
+            source_hash: UserDepositSource::new(l1_block_hash, log_index).source_hash(),
+            from: address!("0x1111111111111111111111111111111111111111"),
+            to: TxKind::Call(address!("0x2222222222222222222222222222222222222222")),
+            mint: u128::MAX,
+            value: U256::MAX,
+            gas_limit: u64::MAX,
+            is_system_transaction: false,
+            input: Bytes::from(vec![0xab; 100]),
+        };
+        let create = TxDeposit { to: TxKind::Create, mint: 0, input: Bytes::new(), ..call };
+
+        for tx in [call, create] {
+            let log = tx.to_deposit_log(PORTAL);
+            assert_eq!(log.address, PORTAL);
+            assert_eq!(log.data.data.len() % 32, 0);
+            assert_eq!(TxDeposit::from_deposit_log(&log, l1_block_hash, log_index).unwrap(), tx);
+        }
+    }
+}
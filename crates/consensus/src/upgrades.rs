@@ -0,0 +1,483 @@
+//! Network upgrade transactions.
+//!
+//! Hardforks that replace predeploy implementations or install system contracts do so with a
+//! fixed, ordered list of deposit transactions that is placed in the activation block right after
+//! the L1 attributes deposit. Each transaction is identified by an intent string, which is hashed
+//! into its source hash through [`UpgradeDepositSource`].
+//!
+//! The creation code of the predeploy implementations is compiled from the contracts release
+//! referenced by each upgrade spec and is not bundled with this crate. Those deployments are
+//! described by an [`Implementation`], and their creation code is supplied by the caller when the
+//! transactions are converted into [`TxDeposit`]s. All other inputs are fixed by the protocol.
+//!
+//! See: <https://specs.optimism.io/protocol/ecotone/derivation.html#network-upgrade-automation-transactions>
+
+use crate::{
+    TxDeposit, UpgradeDepositSource,
+    interop::CROSS_L2_INBOX_ADDRESS,
+    l1_block_info::L1_INFO_DEPOSITOR_ADDRESS,
+    predeploys::{
+        GAS_PRICE_ORACLE_ADDRESS, L1_BLOCK_ADDRESS, L2_TO_L2_CROSS_DOMAIN_MESSENGER_ADDRESS,
+        OPERATOR_FEE_VAULT_ADDRESS,
+    },
+};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, Bytes, TxKind, U256, address, hex};
+
+/// The sender of the [EIP-4788] beacon roots contract deployment.
+///
+/// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+pub const BEACON_ROOTS_DEPLOYER: Address = address!("0x0B799C86a49DEeb90402691F1041aa3AF2d3C875");
+
+/// The creation code of the [EIP-4788] beacon roots contract.
+///
+/// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+pub const BEACON_ROOTS_CREATION_CODE: &[u8] = &hex!(
+    "60618060095f395ff33373fffffffffffffffffffffffffffffffffffffffe14604d57602036146024575f5ffd5b5f35801560495762001fff810690815414603c575f5ffd5b62001fff01545f5260205ff35b5f5ffd5b62001fff42064281555f359062001fff015500"
+);
+
+/// The sender of the [EIP-2935] block hash history contract deployment.
+///
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+pub const HISTORY_STORAGE_DEPLOYER: Address =
+    address!("0x3462413Af4609098e1E27A490f554f260213D685");
+
+/// The creation code of the [EIP-2935] block hash history contract.
+///
+/// [EIP-2935]: https://eips.ethereum.org/EIPS/eip-2935
+pub const HISTORY_STORAGE_CREATION_CODE: &[u8] = &hex!(
+    "60538060095f395ff33373fffffffffffffffffffffffffffffffffffffffe14604657602036036042575f35600143038111604257611fff81430311604257611fff9006545f5260205ff35b5f5ffd5b5f35611fff60014303065500"
+);
+
+/// A predeploy implementation contract deployed by a network upgrade.
+///
+/// Each implementation is deployed by a dedicated account with nonce `0`, so its address is known
+/// ahead of time and referenced by the proxy upgrade that follows the deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Implementation {
+    /// The Ecotone `L1Block` implementation.
+    EcotoneL1Block,
+    /// The Ecotone `GasPriceOracle` implementation.
+    EcotoneGasPriceOracle,
+    /// The Fjord `GasPriceOracle` implementation.
+    FjordGasPriceOracle,
+    /// The Isthmus `L1Block` implementation.
+    IsthmusL1Block,
+    /// The Isthmus `GasPriceOracle` implementation.
+    IsthmusGasPriceOracle,
+    /// The Isthmus `OperatorFeeVault` implementation.
+    IsthmusOperatorFeeVault,
+    /// The Interop `CrossL2Inbox` implementation.
+    InteropCrossL2Inbox,
+    /// The Interop `L2ToL2CrossDomainMessenger` implementation.
+    InteropL2ToL2CrossDomainMessenger,
+}
+
+impl Implementation {
+    /// Returns the account that deploys the implementation.
+    pub const fn deployer(&self) -> Address {
+        match self {
+            Self::EcotoneL1Block => address!("0x4210000000000000000000000000000000000000"),
+            Self::EcotoneGasPriceOracle => address!("0x4210000000000000000000000000000000000001"),
+            Self::FjordGasPriceOracle => address!("0x4210000000000000000000000000000000000002"),
+            Self::IsthmusL1Block => address!("0x4210000000000000000000000000000000000003"),
+            Self::IsthmusGasPriceOracle => address!("0x4210000000000000000000000000000000000004"),
+            Self::IsthmusOperatorFeeVault => address!("0x4210000000000000000000000000000000000005"),
+            Self::InteropCrossL2Inbox => address!("0x4220000000000000000000000000000000000000"),
+            Self::InteropL2ToL2CrossDomainMessenger => {
+                address!("0x4220000000000000000000000000000000000001")
+            }
+        }
+    }
+
+    /// Returns the address the implementation is deployed at, i.e. the `CREATE` address of the
+    /// [deployer](Self::deployer) at nonce `0`.
+    pub const fn address(&self) -> Address {
+        match self {
+            Self::EcotoneL1Block => address!("0x07dbe8500fc591d1852B76feE44d5a05e13097Ff"),
+            Self::EcotoneGasPriceOracle => address!("0xb528D11cC114E026F138fE568744c6D45ce6Da7A"),
+            Self::FjordGasPriceOracle => address!("0xa919894851548179A0750865e7974DA599C0Fac7"),
+            Self::IsthmusL1Block => address!("0xFf256497D61dcd71a9e9Ff43967C13fdE1F72D12"),
+            Self::IsthmusGasPriceOracle => address!("0x93e57A196454CB919193fa9946f14943cf733845"),
+            Self::IsthmusOperatorFeeVault => {
+                address!("0x4fa2Be8cd41504037F1838BcE3bCC93bC68Ff537")
+            }
+            Self::InteropCrossL2Inbox => address!("0x691300f512e48B463C2617b34Eef1A9f82EE7dBf"),
+            Self::InteropL2ToL2CrossDomainMessenger => {
+                address!("0x0D0eDd0ebd0e94d218670a8De867Eb5C4d37cadD")
+            }
+        }
+    }
+}
+
+/// The input of an [`UpgradeTransaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpgradeInput {
+    /// Calldata or creation code that is fixed by the protocol.
+    Data(&'static [u8]),
+    /// The creation code of a predeploy implementation, supplied by the caller.
+    Implementation(Implementation),
+}
+
+/// A network upgrade deposit transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpgradeTransaction {
+    /// The intent of the transaction, from which its source hash is derived.
+    pub intent: &'static str,
+    /// The sender of the transaction.
+    pub from: Address,
+    /// The recipient of the transaction, or [`TxKind::Create`] for contract deployments.
+    pub to: TxKind,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The input of the transaction.
+    pub input: UpgradeInput,
+}
+
+impl UpgradeTransaction {
+    /// Returns the source hash of the transaction.
+    pub fn source_hash(&self) -> B256 {
+        UpgradeDepositSource::new(self.intent.into()).source_hash()
+    }
+
+    /// Returns the address of the contract created by the transaction, if it is a deployment.
+    pub fn deployed_address(&self) -> Option<Address> {
+        self.to.is_create().then(|| self.from.create(0))
+    }
+
+    /// Converts the upgrade transaction into a [`TxDeposit`].
+    ///
+    /// `implementation_code` is only called if the transaction deploys an [`Implementation`], and
+    /// must return its creation code.
+    pub fn to_deposit(
+        &self,
+        implementation_code: impl FnOnce(Implementation) -> Bytes,
+    ) -> TxDeposit {
+        let input = match self.input {
+            UpgradeInput::Data(data) => Bytes::from_static(data),
+            UpgradeInput::Implementation(implementation) => implementation_code(implementation),
+        };
+        TxDeposit {
+            source_hash: self.source_hash(),
+            from: self.from,
+            to: self.to,
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit: self.gas_limit,
+            is_system_transaction: false,
+            input,
+        }
+    }
+}
+
+/// Converts an ordered list of upgrade transactions into the deposits of the activation block.
+///
+/// See [`UpgradeTransaction::to_deposit`].
+pub fn upgrade_deposits(
+    txs: &[UpgradeTransaction],
+    mut implementation_code: impl FnMut(Implementation) -> Bytes,
+) -> Vec<TxDeposit> {
+    txs.iter().map(|tx| tx.to_deposit(&mut implementation_code)).collect()
+}
+
+/// Deploys an implementation from its dedicated deployer account.
+const fn deploy(
+    intent: &'static str,
+    implementation: Implementation,
+    gas_limit: u64,
+) -> UpgradeTransaction {
+    UpgradeTransaction {
+        intent,
+        from: implementation.deployer(),
+        to: TxKind::Create,
+        gas_limit,
+        input: UpgradeInput::Implementation(implementation),
+    }
+}
+
+/// Points a predeploy proxy at a new implementation with `upgradeTo(address)`.
+const fn upgrade_proxy(
+    intent: &'static str,
+    proxy: Address,
+    calldata: &'static [u8],
+) -> UpgradeTransaction {
+    UpgradeTransaction {
+        intent,
+        from: Address::ZERO,
+        to: TxKind::Call(proxy),
+        gas_limit: 50_000,
+        input: UpgradeInput::Data(calldata),
+    }
+}
+
+/// Switches the `GasPriceOracle` to the fee formula of a hardfork, from the L1 attributes
+/// depositor.
+const fn enable_gas_price_oracle(
+    intent: &'static str,
+    gas_limit: u64,
+    calldata: &'static [u8],
+) -> UpgradeTransaction {
+    UpgradeTransaction {
+        intent,
+        from: L1_INFO_DEPOSITOR_ADDRESS,
+        to: TxKind::Call(GAS_PRICE_ORACLE_ADDRESS),
+        gas_limit,
+        input: UpgradeInput::Data(calldata),
+    }
+}
+
+/// The upgrade transactions of the Ecotone activation block.
+///
+/// See: <https://specs.optimism.io/protocol/ecotone/derivation.html#network-upgrade-automation-transactions>
+pub const ECOTONE_UPGRADE_TXS: &[UpgradeTransaction] = &[
+    deploy("Ecotone: L1 Block Deployment", Implementation::EcotoneL1Block, 375_000),
+    deploy(
+        "Ecotone: Gas Price Oracle Deployment",
+        Implementation::EcotoneGasPriceOracle,
+        1_000_000,
+    ),
+    upgrade_proxy(
+        "Ecotone: L1 Block Proxy Update",
+        L1_BLOCK_ADDRESS,
+        &hex!("3659cfe600000000000000000000000007dbe8500fc591d1852b76fee44d5a05e13097ff"),
+    ),
+    upgrade_proxy(
+        "Ecotone: Gas Price Oracle Proxy Update",
+        GAS_PRICE_ORACLE_ADDRESS,
+        &hex!("3659cfe6000000000000000000000000b528d11cc114e026f138fe568744c6d45ce6da7a"),
+    ),
+    enable_gas_price_oracle("Ecotone: Gas Price Oracle Set Ecotone", 80_000, &hex!("22b90ab3")),
+    UpgradeTransaction {
+        intent: "Ecotone: beacon block roots contract deployment",
+        from: BEACON_ROOTS_DEPLOYER,
+        to: TxKind::Create,
+        gas_limit: 250_000,
+        input: UpgradeInput::Data(BEACON_ROOTS_CREATION_CODE),
+    },
+];
+
+/// The upgrade transactions of the Fjord activation block.
+///
+/// See: <https://specs.optimism.io/protocol/fjord/derivation.html#network-upgrade-automation-transactions>
+pub const FJORD_UPGRADE_TXS: &[UpgradeTransaction] = &[
+    deploy("Fjord: Gas Price Oracle Deployment", Implementation::FjordGasPriceOracle, 1_450_000),
+    upgrade_proxy(
+        "Fjord: Gas Price Oracle Proxy Update",
+        GAS_PRICE_ORACLE_ADDRESS,
+        &hex!("3659cfe6000000000000000000000000a919894851548179a0750865e7974da599c0fac7"),
+    ),
+    enable_gas_price_oracle("Fjord: Gas Price Oracle Set Fjord", 90_000, &hex!("8e98b106")),
+];
+
+/// The upgrade transactions of the Isthmus activation block.
+///
+/// See: <https://specs.optimism.io/protocol/isthmus/derivation.html#network-upgrade-automation-transactions>
+pub const ISTHMUS_UPGRADE_TXS: &[UpgradeTransaction] = &[
+    deploy("Isthmus: L1 Block Deployment", Implementation::IsthmusL1Block, 425_000),
+    deploy(
+        "Isthmus: Gas Price Oracle Deployment",
+        Implementation::IsthmusGasPriceOracle,
+        1_625_000,
+    ),
+    deploy(
+        "Isthmus: Operator Fee Vault Deployment",
+        Implementation::IsthmusOperatorFeeVault,
+        500_000,
+    ),
+    upgrade_proxy(
+        "Isthmus: L1 Block Proxy Update",
+        L1_BLOCK_ADDRESS,
+        &hex!("3659cfe6000000000000000000000000ff256497d61dcd71a9e9ff43967c13fde1f72d12"),
+    ),
+    upgrade_proxy(
+        "Isthmus: Gas Price Oracle Proxy Update",
+        GAS_PRICE_ORACLE_ADDRESS,
+        &hex!("3659cfe600000000000000000000000093e57a196454cb919193fa9946f14943cf733845"),
+    ),
+    upgrade_proxy(
+        "Isthmus: Operator Fee Vault Proxy Update",
+        OPERATOR_FEE_VAULT_ADDRESS,
+        &hex!("3659cfe60000000000000000000000004fa2be8cd41504037f1838bce3bcc93bc68ff537"),
+    ),
+    enable_gas_price_oracle("Isthmus: Gas Price Oracle Set Isthmus", 90_000, &hex!("291b0383")),
+    UpgradeTransaction {
+        intent: "Isthmus: EIP-2935 Contract Deployment",
+        from: HISTORY_STORAGE_DEPLOYER,
+        to: TxKind::Create,
+        gas_limit: 250_000,
+        input: UpgradeInput::Data(HISTORY_STORAGE_CREATION_CODE),
+    },
+];
+
+/// The upgrade transactions of the Interop activation block.
+///
+/// See: <https://specs.optimism.io/interop/derivation.html#network-upgrade-transactions>
+pub const INTEROP_UPGRADE_TXS: &[UpgradeTransaction] = &[
+    deploy("Interop: CrossL2Inbox Deployment", Implementation::InteropCrossL2Inbox, 420_000),
+    upgrade_proxy(
+        "Interop: CrossL2Inbox Proxy Update",
+        CROSS_L2_INBOX_ADDRESS,
+        &hex!("3659cfe6000000000000000000000000691300f512e48b463c2617b34eef1a9f82ee7dbf"),
+    ),
+    deploy(
+        "Interop: L2ToL2CrossDomainMessenger Deployment",
+        Implementation::InteropL2ToL2CrossDomainMessenger,
+        1_100_000,
+    ),
+    upgrade_proxy(
+        "Interop: L2ToL2CrossDomainMessenger Proxy Update",
+        L2_TO_L2_CROSS_DOMAIN_MESSENGER_ADDRESS,
+        &hex!("3659cfe60000000000000000000000000d0edd0ebd0e94d218670a8de867eb5c4d37cadd"),
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::{
+        eip2935::{HISTORY_STORAGE_ADDRESS, HISTORY_STORAGE_CODE},
+        eip4788::BEACON_ROOTS_ADDRESS,
+    };
+    use alloy_primitives::{b256, keccak256};
+
+    const ALL_UPGRADE_TXS: [&[UpgradeTransaction]; 4] =
+        [ECOTONE_UPGRADE_TXS, FJORD_UPGRADE_TXS, ISTHMUS_UPGRADE_TXS, INTEROP_UPGRADE_TXS];
+
+    fn selector(signature: &str) -> [u8; 4] {
+        keccak256(signature)[..4].try_into().unwrap()
+    }
+
+    fn input_data(tx: &UpgradeTransaction) -> &'static [u8] {
+        match tx.input {
+            UpgradeInput::Data(data) => data,
+            UpgradeInput::Implementation(_) => panic!("expected protocol-defined input"),
+        }
+    }
+
+    #[test]
+    fn test_implementation_addresses() {
+        for txs in ALL_UPGRADE_TXS {
+            for tx in txs {
+                if let UpgradeInput::Implementation(implementation) = tx.input {
+                    assert_eq!(implementation.deployer(), tx.from);
+                    assert_eq!(implementation.deployer().create(0), implementation.address());
+                    assert_eq!(tx.deployed_address(), Some(implementation.address()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_proxy_upgrades_target_preceding_deployments() {
+        for txs in ALL_UPGRADE_TXS {
+            let mut deployed = Vec::new();
+            for tx in txs {
+                match tx.input {
+                    UpgradeInput::Implementation(implementation) => {
+                        deployed.push(implementation.address())
+                    }
+                    UpgradeInput::Data(data) if tx.from.is_zero() => {
+                        assert_eq!(data.len(), 36);
+                        assert_eq!(data[..4], selector("upgradeTo(address)"));
+                        assert_eq!(data[4..16], [0; 12]);
+                        assert!(deployed.contains(&Address::from_slice(&data[16..])));
+                    }
+                    UpgradeInput::Data(_) => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gas_price_oracle_selectors() {
+        assert_eq!(input_data(&ECOTONE_UPGRADE_TXS[4]), selector("setEcotone()"));
+        assert_eq!(input_data(&FJORD_UPGRADE_TXS[2]), selector("setFjord()"));
+        assert_eq!(input_data(&ISTHMUS_UPGRADE_TXS[6]), selector("setIsthmus()"));
+    }
+
+    #[test]
+    fn test_system_contract_deployments() {
+        let beacon_roots = ECOTONE_UPGRADE_TXS[5];
+        assert_eq!(beacon_roots.deployed_address(), Some(BEACON_ROOTS_ADDRESS));
+        // The init code copies the `0x61` byte long runtime code that follows its 9 bytes.
+        assert_eq!(BEACON_ROOTS_CREATION_CODE.len(), 9 + 0x61);
+
+        let history_storage = ISTHMUS_UPGRADE_TXS[7];
+        assert_eq!(history_storage.deployed_address(), Some(HISTORY_STORAGE_ADDRESS));
+        assert_eq!(HISTORY_STORAGE_CREATION_CODE[9..], HISTORY_STORAGE_CODE[..]);
+    }
+
+    #[test]
+    fn test_ecotone_source_hashes() {
+        let expected = [
+            b256!("0x877a6077205782ea15a6dc8699fa5ebcec5e0f4389f09cb8eda09488231346f8"),
+            b256!("0xa312b4510adf943510f05fcc8f15f86995a5066bd83ce11384688ae20e6ecf42"),
+            b256!("0x18acb38c5ff1c238a7460ebc1b421fa49ec4874bdf1e0a530d234104e5e67dbc"),
+            b256!("0xee4f9385eceef498af0be7ec5862229f426dec41c8d42397c7257a5117d9230a"),
+            b256!("0x0c1cb38e99dbc9cbfab3bb80863380b0905290b37eb3d6ab18dc01c1f3e75f93"),
+            b256!("0x69b763c48478b9dc2f65ada09b3d92133ec592ea715ec65ad6e7f3dc519dc00c"),
+        ];
+        let source_hashes: Vec<_> = ECOTONE_UPGRADE_TXS.iter().map(|tx| tx.source_hash()).collect();
+        assert_eq!(source_hashes, expected);
+    }
+
+    #[test]
+    fn test_fjord_source_hashes() {
+        let expected = [
+            b256!("0x86122c533fdcb89b16d8713174625e44578a89751d96c098ec19ab40a51a8ea3"),
+            b256!("0x1e6bb0c28bfab3dc9b36ffb0f721f00d6937f33577606325692db0965a7d58c6"),
+            b256!("0xbac7bb0d5961cad209a345408b0280a0d4686b1b20665e1b0f9cdafd73b19b6b"),
+        ];
+        let source_hashes: Vec<_> = FJORD_UPGRADE_TXS.iter().map(|tx| tx.source_hash()).collect();
+        assert_eq!(source_hashes, expected);
+    }
+
+    #[test]
+    fn test_isthmus_source_hashes() {
+        let expected = [
+            b256!("0x3b2d0821ca2411ad5cd3595804d1213d15737188ae4cbd58aa19c821a6c211bf"),
+            b256!("0xfc70b48424763fa3fab9844253b4f8d508f91eb1f7cb11a247c9baec0afb8035"),
+            b256!("0x107a570d3db75e6110817eb024f09f3172657e920634111ce9875d08a16daa96"),
+            b256!("0xebe8b5cb10ca47e0d8bda8f5355f2d66711a54ddeb0ef1d30e29418c9bf17a0e"),
+            b256!("0xecf2d9161d26c54eda6b7bfdd9142719b1e1199a6e5641468d1bf705bc531ab0"),
+            b256!("0xad74e1adb877ccbe176b8fa1cc559388a16e090ddbe8b512f5b37d07d887a927"),
+            b256!("0x3ddf4b1302548dd92939826e970f260ba36167f4c25f18390a5e8b194b295319"),
+            b256!("0xbfb734dae514c5974ddf803e54c1bc43d5cdb4a48ae27e1d9b875a5a150b553a"),
+        ];
+        let source_hashes: Vec<_> = ISTHMUS_UPGRADE_TXS.iter().map(|tx| tx.source_hash()).collect();
+        assert_eq!(source_hashes, expected);
+    }
+
+    #[test]
+    fn test_interop_source_hashes() {
+        let expected = [
+            b256!("0x6e5e214f73143df8fe6f6054a3ed7eb472d373376458a9c8aecdf23475beb616"),
+            b256!("0x88c6b48354c367125a59792a93a7b60ad7cd66e516157dbba16558c68a46d3cb"),
+            b256!("0xf5484697c7a9a791db32a3bf0763bf2ba686c77ae7d4c0a5ee8c222a92a8dcc2"),
+            b256!("0xe54b4d06bbcc857f41ae00e89d820339ac5ce0034aac722c817b2873e03a7e68"),
+        ];
+        let source_hashes: Vec<_> = INTEROP_UPGRADE_TXS.iter().map(|tx| tx.source_hash()).collect();
+        assert_eq!(source_hashes, expected);
+    }
+
+    #[test]
+    fn test_upgrade_deposits() {
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let deposits = upgrade_deposits(FJORD_UPGRADE_TXS, |implementation| {
+            assert_eq!(implementation, Implementation::FjordGasPriceOracle);
+            code.clone()
+        });
+
+        assert_eq!(deposits.len(), FJORD_UPGRADE_TXS.len());
+        assert_eq!(deposits[0].input, code);
+        assert_eq!(deposits[0].to, TxKind::Create);
+        for (deposit, tx) in deposits.iter().zip(FJORD_UPGRADE_TXS) {
+            assert_eq!(deposit.source_hash, tx.source_hash());
+            assert_eq!(deposit.from, tx.from);
+            assert_eq!(deposit.gas_limit, tx.gas_limit);
+            assert_eq!(deposit.mint, 0);
+            assert!(!deposit.is_system_transaction);
+        }
+        assert_eq!(deposits[2].input.as_ref(), input_data(&FJORD_UPGRADE_TXS[2]));
+    }
+}
@@ -0,0 +1,390 @@
+//! Block body validation.
+
+use crate::{
+    L1BlockInfoError, L1BlockInfoTx, OpHardfork, OpHardforks, OpTransaction,
+    l1_block_info::L1_INFO_DEPOSITOR_ADDRESS, predeploys::L1_BLOCK_ADDRESS,
+};
+use alloy_consensus::{Block, Transaction};
+use alloy_eips::Typed2718;
+use alloy_primitives::TxKind;
+
+/// An error that can occur when validating the body of an [`OpBlock`](crate::OpBlock).
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BlockBodyValidationError {
+    /// The block has no transactions.
+    #[error("block has no transactions")]
+    EmptyBlock,
+    /// The first transaction is not a deposit.
+    #[error("first transaction is not the L1 info deposit")]
+    MissingL1InfoDeposit,
+    /// The first transaction is a deposit that is not sent by the L1 info depositor to the
+    /// `L1Block` predeploy.
+    #[error("L1 info deposit has an invalid sender or recipient")]
+    InvalidL1InfoDepositAccounts,
+    /// The calldata of the L1 info deposit cannot be decoded.
+    #[error("invalid L1 info deposit calldata: {0}")]
+    InvalidL1InfoCalldata(L1BlockInfoError),
+    /// The calldata of the L1 info deposit does not use the layout of the active hardfork.
+    #[error("L1 info deposit uses the {got} layout, expected the {expected} layout")]
+    InvalidL1InfoLayout {
+        /// The hardfork that introduced the expected layout.
+        expected: OpHardfork,
+        /// The hardfork that introduced the layout of the deposit.
+        got: OpHardfork,
+    },
+    /// A deposit transaction follows a user transaction.
+    #[error("deposit transaction at index {index} follows a user transaction")]
+    DepositAfterUserTransaction {
+        /// The index of the deposit transaction.
+        index: usize,
+    },
+    /// A system deposit transaction after Regolith, or an L1 info deposit that is not a system
+    /// transaction before Regolith.
+    #[error("invalid system transaction flag at index {index}")]
+    InvalidSystemTransaction {
+        /// The index of the deposit transaction.
+        index: usize,
+    },
+    /// An EIP-4844 blob transaction.
+    #[error("EIP-4844 transaction at index {index}")]
+    Eip4844Transaction {
+        /// The index of the blob transaction.
+        index: usize,
+    },
+    /// The block has L1 withdrawals, or a withdrawals list before Canyon.
+    #[error("L1 withdrawals are not supported")]
+    L1Withdrawals,
+    /// The withdrawals list is missing since Canyon.
+    #[error("missing withdrawals list")]
+    MissingWithdrawals,
+    /// The block has ommers.
+    #[error("block has ommers")]
+    Ommers,
+}
+
+/// Validates the body of a block against the rules of the hardforks active at its timestamp.
+///
+/// The first transaction must be the L1 info deposit, sent by the L1 info depositor to the
+/// `L1Block` predeploy with calldata in the layout of the active hardfork. The `L1Block` predeploy
+/// is upgraded by the deposits of the Ecotone, Isthmus and Jovian activation blocks, so their L1
+/// info deposit still uses the previous layout: the layout is that of the hardforks active at
+/// `parent_timestamp`. Deposits must come before all user transactions, and
+/// may only be system transactions before Regolith, where the L1 info deposit is one. Blob
+/// transactions, L1 withdrawals and ommers are not supported. Errors related to a specific
+/// transaction report its index in the block.
+///
+/// The body is generic over its transaction type, so that blocks with extended transaction types
+/// can be validated as well. [`OpTxEnvelope`](crate::OpTxEnvelope) cannot represent blob
+/// transactions.
+pub fn validate_block_body<T: OpTransaction + Typed2718>(
+    block: &Block<T>,
+    parent_timestamp: u64,
+    hardforks: &impl OpHardforks,
+) -> Result<(), BlockBodyValidationError> {
+    let timestamp = block.header.timestamp;
+    let is_regolith = hardforks.is_regolith_active_at_timestamp(timestamp);
+    let body = &block.body;
+
+    if !body.ommers.is_empty() {
+        return Err(BlockBodyValidationError::Ommers);
+    }
+    match &body.withdrawals {
+        Some(withdrawals) if !withdrawals.is_empty() => {
+            return Err(BlockBodyValidationError::L1Withdrawals);
+        }
+        Some(_) if !hardforks.is_canyon_active_at_timestamp(timestamp) => {
+            return Err(BlockBodyValidationError::L1Withdrawals);
+        }
+        None if hardforks.is_canyon_active_at_timestamp(timestamp) => {
+            return Err(BlockBodyValidationError::MissingWithdrawals);
+        }
+        _ => {}
+    }
+
+    let first = body.transactions.first().ok_or(BlockBodyValidationError::EmptyBlock)?;
+    let l1_info = first.as_deposit().ok_or(BlockBodyValidationError::MissingL1InfoDeposit)?;
+    if l1_info.from != L1_INFO_DEPOSITOR_ADDRESS || l1_info.to != TxKind::Call(L1_BLOCK_ADDRESS) {
+        return Err(BlockBodyValidationError::InvalidL1InfoDepositAccounts);
+    }
+    let info = L1BlockInfoTx::decode_calldata(l1_info.input())
+        .map_err(BlockBodyValidationError::InvalidL1InfoCalldata)?;
+    let expected = l1_info_layout_at(hardforks, parent_timestamp);
+    let got = l1_info_layout(&info);
+    if got != expected {
+        return Err(BlockBodyValidationError::InvalidL1InfoLayout { expected, got });
+    }
+    if l1_info.is_system_transaction == is_regolith {
+        return Err(BlockBodyValidationError::InvalidSystemTransaction { index: 0 });
+    }
+
+    let mut seen_user_transaction = false;
+    for (index, tx) in body.transactions.iter().enumerate().skip(1) {
+        if tx.is_eip4844() {
+            return Err(BlockBodyValidationError::Eip4844Transaction { index });
+        }
+        match tx.as_deposit() {
+            Some(_) if seen_user_transaction => {
+                return Err(BlockBodyValidationError::DepositAfterUserTransaction { index });
+            }
+            Some(deposit) if is_regolith && deposit.is_system_transaction => {
+                return Err(BlockBodyValidationError::InvalidSystemTransaction { index });
+            }
+            Some(_) => {}
+            None => seen_user_transaction = true,
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the hardfork that introduced the L1 info layout used while the hardforks active at
+/// `timestamp` are active.
+fn l1_info_layout_at(hardforks: &impl OpHardforks, timestamp: u64) -> OpHardfork {
+    if hardforks.is_jovian_active_at_timestamp(timestamp) {
+        OpHardfork::Jovian
+    } else if hardforks.is_isthmus_active_at_timestamp(timestamp) {
+        OpHardfork::Isthmus
+    } else if hardforks.is_ecotone_active_at_timestamp(timestamp) {
+        OpHardfork::Ecotone
+    } else {
+        OpHardfork::Bedrock
+    }
+}
+
+/// Returns the hardfork that introduced the layout of the L1 info calldata.
+const fn l1_info_layout(info: &L1BlockInfoTx) -> OpHardfork {
+    match info {
+        L1BlockInfoTx::Bedrock(_) => OpHardfork::Bedrock,
+        L1BlockInfoTx::Ecotone(_) => OpHardfork::Ecotone,
+        L1BlockInfoTx::Isthmus(_) => OpHardfork::Isthmus,
+        L1BlockInfoTx::Jovian(_) => OpHardfork::Jovian,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ForkCondition, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus,
+        L1BlockInfoJovian, OpHardforkSchedule, OpTxEnvelope, TxDeposit,
+    };
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{BlockBody, Extended, Header, Sealable, Sealed, Signed, TxEip1559};
+    use alloy_eips::eip4895::Withdrawal;
+    use alloy_primitives::{Address, B256, Signature};
+
+    fn schedule(regolith_time: u64, canyon_time: u64) -> OpHardforkSchedule {
+        OpHardforkSchedule::new([
+            (OpHardfork::Bedrock, ForkCondition::Block(0)),
+            (OpHardfork::Regolith, ForkCondition::Timestamp(regolith_time)),
+            (OpHardfork::Canyon, ForkCondition::Timestamp(canyon_time)),
+        ])
+        .unwrap()
+    }
+
+    fn deposit(tx: TxDeposit) -> OpTxEnvelope {
+        OpTxEnvelope::Deposit(tx.seal_slow())
+    }
+
+    fn l1_info(is_regolith: bool) -> OpTxEnvelope {
+        l1_info_with(L1BlockInfoTx::Bedrock(L1BlockInfoBedrock::default()), is_regolith)
+    }
+
+    fn l1_info_with(info: L1BlockInfoTx, is_regolith: bool) -> OpTxEnvelope {
+        deposit(info.to_deposit(is_regolith))
+    }
+
+    fn user_tx() -> OpTxEnvelope {
+        OpTxEnvelope::Eip1559(Signed::new_unchecked(
+            TxEip1559::default(),
+            Signature::test_signature(),
+            B256::ZERO,
+        ))
+    }
+
+    fn block<T>(transactions: Vec<T>) -> Block<T> {
+        Block {
+            header: Header { timestamp: 100, ..Default::default() },
+            body: BlockBody { transactions, ommers: vec![], withdrawals: Some(Default::default()) },
+        }
+    }
+
+    #[test]
+    fn test_validate_block_body() {
+        let hardforks = schedule(0, 0);
+        let txs = vec![l1_info(true), deposit(TxDeposit::default()), user_tx(), user_tx()];
+        assert_eq!(validate_block_body(&block(txs), 98, &hardforks), Ok(()));
+
+        let txs = vec![l1_info(true), user_tx(), deposit(TxDeposit::default())];
+        assert_eq!(
+            validate_block_body(&block(txs), 98, &hardforks),
+            Err(BlockBodyValidationError::DepositAfterUserTransaction { index: 2 })
+        );
+
+        let system_tx = TxDeposit { is_system_transaction: true, ..Default::default() };
+        let txs = vec![l1_info(true), deposit(system_tx), user_tx()];
+        assert_eq!(
+            validate_block_body(&block(txs), 98, &hardforks),
+            Err(BlockBodyValidationError::InvalidSystemTransaction { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_validate_block_body_l1_info() {
+        let hardforks = schedule(0, 0);
+        assert_eq!(
+            validate_block_body(&block::<OpTxEnvelope>(vec![]), 98, &hardforks),
+            Err(BlockBodyValidationError::EmptyBlock)
+        );
+        assert_eq!(
+            validate_block_body(&block(vec![user_tx()]), 98, &hardforks),
+            Err(BlockBodyValidationError::MissingL1InfoDeposit)
+        );
+
+        let OpTxEnvelope::Deposit(info) = l1_info(true) else { unreachable!() };
+        let tx = TxDeposit { from: Address::ZERO, ..info.inner().clone() };
+        assert_eq!(
+            validate_block_body(&block(vec![deposit(tx)]), 98, &hardforks),
+            Err(BlockBodyValidationError::InvalidL1InfoDepositAccounts)
+        );
+        let tx = TxDeposit { input: info.input.slice(..10), ..info.inner().clone() };
+        assert_eq!(
+            validate_block_body(&block(vec![deposit(tx)]), 98, &hardforks),
+            Err(BlockBodyValidationError::InvalidL1InfoCalldata(L1BlockInfoError::InvalidLength {
+                expected: 260,
+                got: 10
+            }))
+        );
+
+        // The L1 info deposit is a system transaction before Regolith only.
+        let hardforks = schedule(u64::MAX, u64::MAX);
+        let mut bedrock_block = block(vec![l1_info(false), user_tx()]);
+        bedrock_block.body.withdrawals = None;
+        assert_eq!(validate_block_body(&bedrock_block, 98, &hardforks), Ok(()));
+        bedrock_block.body.transactions[0] = l1_info(true);
+        assert_eq!(
+            validate_block_body(&bedrock_block, 98, &hardforks),
+            Err(BlockBodyValidationError::InvalidSystemTransaction { index: 0 })
+        );
+    }
+
+    #[test]
+    fn test_validate_block_body_l1_info_layout() {
+        // Ecotone at 100, Isthmus at 200 and Jovian at 300, every other fork at genesis.
+        let hardforks = OpHardforkSchedule::new(OpHardfork::ALL[..10].iter().map(|&fork| {
+            let time = match fork {
+                OpHardfork::Bedrock => return (fork, ForkCondition::Block(0)),
+                OpHardfork::Ecotone
+                | OpHardfork::Fjord
+                | OpHardfork::Granite
+                | OpHardfork::Holocene => 100,
+                OpHardfork::Isthmus => 200,
+                OpHardfork::Jovian => 300,
+                _ => 0,
+            };
+            (fork, ForkCondition::Timestamp(time))
+        }))
+        .unwrap();
+        let bedrock = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock::default());
+        let ecotone = L1BlockInfoTx::Ecotone(L1BlockInfoEcotone::default());
+        let isthmus = L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::default());
+        let jovian = L1BlockInfoTx::Jovian(L1BlockInfoJovian::default());
+        let validate = |info, timestamp| {
+            let mut block = block(vec![l1_info_with(info, true)]);
+            block.header.timestamp = timestamp;
+            validate_block_body(&block, timestamp - 2, &hardforks)
+        };
+
+        // The activation blocks still use the previous layout.
+        assert_eq!(validate(bedrock, 98), Ok(()));
+        assert_eq!(validate(bedrock, 100), Ok(()));
+        assert_eq!(
+            validate(ecotone, 100),
+            Err(BlockBodyValidationError::InvalidL1InfoLayout {
+                expected: OpHardfork::Bedrock,
+                got: OpHardfork::Ecotone
+            })
+        );
+        assert_eq!(validate(ecotone, 102), Ok(()));
+        assert_eq!(
+            validate(bedrock, 102),
+            Err(BlockBodyValidationError::InvalidL1InfoLayout {
+                expected: OpHardfork::Ecotone,
+                got: OpHardfork::Bedrock
+            })
+        );
+        assert_eq!(validate(ecotone, 200), Ok(()));
+        assert_eq!(validate(isthmus, 202), Ok(()));
+        assert_eq!(
+            validate(jovian, 202),
+            Err(BlockBodyValidationError::InvalidL1InfoLayout {
+                expected: OpHardfork::Isthmus,
+                got: OpHardfork::Jovian
+            })
+        );
+        assert_eq!(validate(isthmus, 300), Ok(()));
+        assert_eq!(validate(jovian, 302), Ok(()));
+        assert_eq!(
+            validate(isthmus, 302),
+            Err(BlockBodyValidationError::InvalidL1InfoLayout {
+                expected: OpHardfork::Jovian,
+                got: OpHardfork::Isthmus
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_block_body_withdrawals() {
+        let mut block = block(vec![l1_info(true)]);
+        block.body.withdrawals = Some(vec![Withdrawal::default()].into());
+        assert_eq!(
+            validate_block_body(&block, 98, &schedule(0, 0)),
+            Err(BlockBodyValidationError::L1Withdrawals)
+        );
+        block.body.withdrawals = Some(Default::default());
+        assert_eq!(
+            validate_block_body(&block, 98, &schedule(0, u64::MAX)),
+            Err(BlockBodyValidationError::L1Withdrawals)
+        );
+        block.body.withdrawals = None;
+        assert_eq!(
+            validate_block_body(&block, 98, &schedule(0, 0)),
+            Err(BlockBodyValidationError::MissingWithdrawals)
+        );
+        block.body.withdrawals = Some(Default::default());
+        block.body.ommers.push(Header::default());
+        assert_eq!(
+            validate_block_body(&block, 98, &schedule(0, 0)),
+            Err(BlockBodyValidationError::Ommers)
+        );
+    }
+
+    #[derive(Debug)]
+    struct BlobTx;
+
+    impl Typed2718 for BlobTx {
+        fn ty(&self) -> u8 {
+            alloy_eips::eip2718::EIP4844_TX_TYPE_ID
+        }
+    }
+
+    impl OpTransaction for BlobTx {
+        fn is_deposit(&self) -> bool {
+            false
+        }
+
+        fn as_deposit(&self) -> Option<&Sealed<TxDeposit>> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_validate_block_body_blob_transaction() {
+        let txs: Vec<Extended<OpTxEnvelope, BlobTx>> =
+            vec![l1_info(true).into(), user_tx().into(), Extended::Other(BlobTx)];
+        assert_eq!(
+            validate_block_body(&block(txs), 98, &schedule(0, 0)),
+            Err(BlockBodyValidationError::Eip4844Transaction { index: 2 })
+        );
+    }
+}
@@ -0,0 +1,487 @@
+//! Header validation.
+
+use crate::{
+    EIP1559ParamError, OpHardforks, calc_next_block_base_fee, decode_holocene_extra_data,
+    eip1559::decode_jovian_extra_data,
+};
+use alloy_consensus::{EMPTY_ROOT_HASH, Header};
+use alloy_eips::{eip1559::BaseFeeParams, eip7685::EMPTY_REQUESTS_HASH};
+use alloy_primitives::B256;
+
+/// An error that can occur when validating a [`Header`] of an OP Stack chain.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderValidationError {
+    /// The extra data is not empty before Holocene.
+    #[error("extra data must be empty before Holocene, got {0} bytes")]
+    ExtraDataNotEmpty(usize),
+    /// The extra data does not have the length of the EIP-1559 parameters encoding.
+    #[error("invalid extra data length: expected {expected}, got {got}")]
+    InvalidExtraDataLength {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        got: usize,
+    },
+    /// The extra data is not a valid encoding of the EIP-1559 parameters.
+    #[error("invalid extra data: {0}")]
+    InvalidExtraData(EIP1559ParamError),
+    /// The blob gas used is not zero before Jovian.
+    #[error("blob gas used must be zero, got {0}")]
+    NonZeroBlobGasUsed(u64),
+    /// The excess blob gas is not zero.
+    #[error("excess blob gas must be zero, got {0}")]
+    NonZeroExcessBlobGas(u64),
+    /// The blob gas fields are missing after Ecotone.
+    #[error("missing blob gas fields")]
+    MissingBlobGasFields,
+    /// The blob gas fields are present before Ecotone.
+    #[error("unexpected blob gas fields before Ecotone")]
+    UnexpectedBlobGasFields,
+    /// The requests hash is not the empty requests hash after Isthmus.
+    #[error("invalid requests hash: {0:?}")]
+    InvalidRequestsHash(Option<B256>),
+    /// The requests hash is present before Isthmus.
+    #[error("unexpected requests hash before Isthmus")]
+    UnexpectedRequestsHash,
+    /// The withdrawals root is missing after Canyon.
+    #[error("missing withdrawals root")]
+    MissingWithdrawalsRoot,
+    /// The withdrawals root is present before Canyon.
+    #[error("unexpected withdrawals root before Canyon")]
+    UnexpectedWithdrawalsRoot,
+    /// The withdrawals root is not the empty root between Canyon and Isthmus.
+    #[error("withdrawals root must be empty before Isthmus, got {0}")]
+    NonEmptyWithdrawalsRoot(B256),
+    /// The parent beacon block root is missing after Ecotone.
+    #[error("missing parent beacon block root")]
+    MissingParentBeaconBlockRoot,
+    /// The parent beacon block root is present before Ecotone.
+    #[error("unexpected parent beacon block root before Ecotone")]
+    UnexpectedParentBeaconBlockRoot,
+    /// The gas used exceeds the gas limit.
+    #[error("gas used {gas_used} exceeds gas limit {gas_limit}")]
+    GasUsedExceedsGasLimit {
+        /// The gas used.
+        gas_used: u64,
+        /// The gas limit.
+        gas_limit: u64,
+    },
+    /// The block number does not follow the parent block number.
+    #[error("block number {number} does not follow parent block number {parent_number}")]
+    InvalidBlockNumber {
+        /// The parent block number.
+        parent_number: u64,
+        /// The block number.
+        number: u64,
+    },
+    /// The parent hash does not match the hash of the parent header.
+    #[error("parent hash mismatch: expected {expected}, got {got}")]
+    ParentHashMismatch {
+        /// The hash of the parent header.
+        expected: B256,
+        /// The parent hash of the header.
+        got: B256,
+    },
+    /// The timestamp is not after the parent timestamp.
+    #[error("timestamp {timestamp} is not after parent timestamp {parent_timestamp}")]
+    TimestampNotAfterParent {
+        /// The parent timestamp.
+        parent_timestamp: u64,
+        /// The timestamp.
+        timestamp: u64,
+    },
+    /// The base fee does not match the base fee computed from the parent.
+    #[error("base fee mismatch: expected {expected}, got {got:?}")]
+    BaseFeeMismatch {
+        /// The base fee computed from the parent.
+        expected: u64,
+        /// The base fee of the header.
+        got: Option<u64>,
+    },
+    /// The base fee cannot be computed from the parent.
+    #[error("invalid parent base fee parameters: {0}")]
+    InvalidParentBaseFee(EIP1559ParamError),
+}
+
+/// Validates a standalone header against the hardfork rules active at its timestamp.
+///
+/// - Before Holocene, the extra data is empty. Since Holocene, it encodes the EIP-1559 parameters,
+///   with a non-zero denominator, and since Jovian, also the minimum base fee.
+/// - Since Ecotone, the blob gas fields are present, with zero excess blob gas. The blob gas used
+///   is zero before Jovian, and holds the DA footprint of the block since Jovian.
+/// - Since Isthmus, the requests hash is the empty requests hash.
+/// - Since Canyon, the withdrawals root is present. It is the empty root before Isthmus, and the
+///   storage root of the `L2ToL1MessagePasser` since Isthmus.
+/// - Since Ecotone, the parent beacon block root is present.
+pub fn validate_header(
+    header: &Header,
+    hardforks: &impl OpHardforks,
+) -> Result<(), HeaderValidationError> {
+    let timestamp = header.timestamp;
+
+    if header.gas_used > header.gas_limit {
+        return Err(HeaderValidationError::GasUsedExceedsGasLimit {
+            gas_used: header.gas_used,
+            gas_limit: header.gas_limit,
+        });
+    }
+
+    validate_extra_data(header, hardforks)?;
+
+    if hardforks.is_ecotone_active_at_timestamp(timestamp) {
+        let (Some(blob_gas_used), Some(excess_blob_gas)) =
+            (header.blob_gas_used, header.excess_blob_gas)
+        else {
+            return Err(HeaderValidationError::MissingBlobGasFields);
+        };
+        if blob_gas_used != 0 && !hardforks.is_jovian_active_at_timestamp(timestamp) {
+            return Err(HeaderValidationError::NonZeroBlobGasUsed(blob_gas_used));
+        }
+        if excess_blob_gas != 0 {
+            return Err(HeaderValidationError::NonZeroExcessBlobGas(excess_blob_gas));
+        }
+        if header.parent_beacon_block_root.is_none() {
+            return Err(HeaderValidationError::MissingParentBeaconBlockRoot);
+        }
+    } else {
+        if header.blob_gas_used.is_some() || header.excess_blob_gas.is_some() {
+            return Err(HeaderValidationError::UnexpectedBlobGasFields);
+        }
+        if header.parent_beacon_block_root.is_some() {
+            return Err(HeaderValidationError::UnexpectedParentBeaconBlockRoot);
+        }
+    }
+
+    if hardforks.is_isthmus_active_at_timestamp(timestamp) {
+        if header.requests_hash != Some(EMPTY_REQUESTS_HASH) {
+            return Err(HeaderValidationError::InvalidRequestsHash(header.requests_hash));
+        }
+    } else if header.requests_hash.is_some() {
+        return Err(HeaderValidationError::UnexpectedRequestsHash);
+    }
+
+    match header.withdrawals_root {
+        None if hardforks.is_canyon_active_at_timestamp(timestamp) => {
+            Err(HeaderValidationError::MissingWithdrawalsRoot)
+        }
+        Some(_) if !hardforks.is_canyon_active_at_timestamp(timestamp) => {
+            Err(HeaderValidationError::UnexpectedWithdrawalsRoot)
+        }
+        Some(root)
+            if root != EMPTY_ROOT_HASH && !hardforks.is_isthmus_active_at_timestamp(timestamp) =>
+        {
+            Err(HeaderValidationError::NonEmptyWithdrawalsRoot(root))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates a header against its `parent` header, and against the hardfork rules with
+/// [`validate_header`].
+///
+/// The block number, parent hash and timestamp must follow the parent, and the base fee must be
+/// the one computed from the parent with [`calc_next_block_base_fee`], where
+/// `default_base_fee_params` are the base fee parameters of the chain at the timestamp of the
+/// header.
+pub fn validate_header_against_parent(
+    header: &Header,
+    parent: &Header,
+    hardforks: &impl OpHardforks,
+    default_base_fee_params: BaseFeeParams,
+) -> Result<(), HeaderValidationError> {
+    validate_header(header, hardforks)?;
+
+    if parent.number + 1 != header.number {
+        return Err(HeaderValidationError::InvalidBlockNumber {
+            parent_number: parent.number,
+            number: header.number,
+        });
+    }
+
+    let parent_hash = parent.hash_slow();
+    if parent_hash != header.parent_hash {
+        return Err(HeaderValidationError::ParentHashMismatch {
+            expected: parent_hash,
+            got: header.parent_hash,
+        });
+    }
+
+    if header.timestamp <= parent.timestamp {
+        return Err(HeaderValidationError::TimestampNotAfterParent {
+            parent_timestamp: parent.timestamp,
+            timestamp: header.timestamp,
+        });
+    }
+
+    let expected = calc_next_block_base_fee(hardforks, parent, default_base_fee_params)
+        .map_err(HeaderValidationError::InvalidParentBaseFee)?;
+    if header.base_fee_per_gas != Some(expected) {
+        return Err(HeaderValidationError::BaseFeeMismatch {
+            expected,
+            got: header.base_fee_per_gas,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates the extra data of the header: empty before Holocene, and a valid encoding of the
+/// EIP-1559 parameters afterwards.
+fn validate_extra_data(
+    header: &Header,
+    hardforks: &impl OpHardforks,
+) -> Result<(), HeaderValidationError> {
+    let extra_data = &header.extra_data;
+    let (expected_len, denominator) = if hardforks.is_jovian_active_at_timestamp(header.timestamp) {
+        let (_, denominator, _) = decode_jovian_extra_data(extra_data)
+            .map_err(HeaderValidationError::InvalidExtraData)?;
+        (17, denominator)
+    } else if hardforks.is_holocene_active_at_timestamp(header.timestamp) {
+        let (_, denominator) = decode_holocene_extra_data(extra_data)
+            .map_err(HeaderValidationError::InvalidExtraData)?;
+        (9, denominator)
+    } else if extra_data.is_empty() {
+        return Ok(());
+    } else {
+        return Err(HeaderValidationError::ExtraDataNotEmpty(extra_data.len()));
+    };
+
+    if extra_data.len() != expected_len {
+        return Err(HeaderValidationError::InvalidExtraDataLength {
+            expected: expected_len,
+            got: extra_data.len(),
+        });
+    }
+    if denominator == 0 {
+        return Err(HeaderValidationError::InvalidExtraData(EIP1559ParamError::ZeroDenominator));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ForkCondition, OpHardfork, OpHardforkSchedule, encode_holocene_extra_data};
+    use alloy_primitives::{B64, Bytes, b256};
+
+    /// A schedule with all hardforks up to `latest` active from genesis.
+    fn schedule(latest: OpHardfork) -> OpHardforkSchedule {
+        let count = OpHardfork::ALL.iter().position(|fork| *fork == latest).unwrap() + 1;
+        OpHardforkSchedule::new(OpHardfork::ALL.into_iter().take(count).map(|fork| {
+            let condition = if fork == OpHardfork::Bedrock {
+                ForkCondition::Block(0)
+            } else {
+                ForkCondition::Timestamp(0)
+            };
+            (fork, condition)
+        }))
+        .unwrap()
+    }
+
+    fn ecotone_header() -> Header {
+        Header {
+            timestamp: 100,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        }
+    }
+
+    fn isthmus_header() -> Header {
+        Header {
+            extra_data: Bytes::from_static(&[0, 0, 0, 0, 250, 0, 0, 0, 6]),
+            requests_hash: Some(EMPTY_REQUESTS_HASH),
+            ..ecotone_header()
+        }
+    }
+
+    #[test]
+    fn test_validate_header_bedrock() {
+        let hardforks = schedule(OpHardfork::Bedrock);
+        assert_eq!(validate_header(&Header::default(), &hardforks), Ok(()));
+        assert_eq!(
+            validate_header(&ecotone_header(), &hardforks),
+            Err(HeaderValidationError::UnexpectedBlobGasFields)
+        );
+        let header = Header { withdrawals_root: Some(EMPTY_ROOT_HASH), ..Default::default() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::UnexpectedWithdrawalsRoot)
+        );
+        let header = Header { gas_used: 2, gas_limit: 1, ..Default::default() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::GasUsedExceedsGasLimit { gas_used: 2, gas_limit: 1 })
+        );
+    }
+
+    #[test]
+    fn test_validate_header_extra_data() {
+        let header = Header { extra_data: Bytes::from_static(b"foo"), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &schedule(OpHardfork::Granite)),
+            Err(HeaderValidationError::ExtraDataNotEmpty(3))
+        );
+
+        let hardforks = schedule(OpHardfork::Holocene);
+        assert_eq!(
+            validate_header(&ecotone_header(), &hardforks),
+            Err(HeaderValidationError::InvalidExtraData(EIP1559ParamError::NoEIP1559Params))
+        );
+        let extra_data = encode_holocene_extra_data(B64::ZERO, BaseFeeParams::optimism()).unwrap();
+        let header = Header { extra_data, ..ecotone_header() };
+        assert_eq!(validate_header(&header, &hardforks), Ok(()));
+
+        let header = Header { extra_data: Bytes::from_static(&[0; 9]), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::InvalidExtraData(EIP1559ParamError::ZeroDenominator))
+        );
+        let header = Header { extra_data: Bytes::from_static(&[1; 9]), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::InvalidExtraData(EIP1559ParamError::InvalidVersion(1)))
+        );
+        let header = Header {
+            extra_data: Bytes::from_static(&[0, 0, 0, 0, 250, 0, 0, 0, 6, 0]),
+            ..ecotone_header()
+        };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::InvalidExtraDataLength { expected: 9, got: 10 })
+        );
+
+        let hardforks = schedule(OpHardfork::Jovian);
+        assert_eq!(
+            validate_header(&isthmus_header(), &hardforks),
+            Err(HeaderValidationError::InvalidExtraData(EIP1559ParamError::NoEIP1559Params))
+        );
+        let header = Header {
+            extra_data: Bytes::from_static(&[1, 0, 0, 0, 250, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 1]),
+            ..isthmus_header()
+        };
+        assert_eq!(validate_header(&header, &hardforks), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_header_blob_gas() {
+        let hardforks = schedule(OpHardfork::Ecotone);
+        assert_eq!(validate_header(&ecotone_header(), &hardforks), Ok(()));
+        let header = Header { blob_gas_used: Some(1), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::NonZeroBlobGasUsed(1))
+        );
+        let header = Header { excess_blob_gas: Some(1), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::NonZeroExcessBlobGas(1))
+        );
+        let header = Header { blob_gas_used: None, ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::MissingBlobGasFields)
+        );
+
+        // The blob gas used holds the DA footprint since Jovian.
+        let header = Header {
+            extra_data: Bytes::from_static(&[1, 0, 0, 0, 250, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 1]),
+            blob_gas_used: Some(1),
+            ..isthmus_header()
+        };
+        assert_eq!(validate_header(&header, &schedule(OpHardfork::Jovian)), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_header_roots() {
+        let hardforks = schedule(OpHardfork::Ecotone);
+        let header = Header { parent_beacon_block_root: None, ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::MissingParentBeaconBlockRoot)
+        );
+        assert_eq!(
+            validate_header(&Header::default(), &schedule(OpHardfork::Canyon)),
+            Err(HeaderValidationError::MissingWithdrawalsRoot)
+        );
+        let header = Header { parent_beacon_block_root: Some(B256::ZERO), ..Default::default() };
+        assert_eq!(
+            validate_header(&header, &schedule(OpHardfork::Regolith)),
+            Err(HeaderValidationError::UnexpectedParentBeaconBlockRoot)
+        );
+        let header = Header { requests_hash: Some(EMPTY_REQUESTS_HASH), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::UnexpectedRequestsHash)
+        );
+
+        let root = b256!("0x8ed4baae3a927be3dea54996b4d5899f8c01e7594bf50b17dc1e741388ce3d12");
+        let header = Header { withdrawals_root: Some(root), ..ecotone_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::NonEmptyWithdrawalsRoot(root))
+        );
+
+        // The withdrawals root is the message passer storage root since Isthmus.
+        let hardforks = schedule(OpHardfork::Isthmus);
+        let header = Header { withdrawals_root: Some(root), ..isthmus_header() };
+        assert_eq!(validate_header(&header, &hardforks), Ok(()));
+        let header = Header { withdrawals_root: None, ..isthmus_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::MissingWithdrawalsRoot)
+        );
+        let header = Header { requests_hash: None, ..isthmus_header() };
+        assert_eq!(
+            validate_header(&header, &hardforks),
+            Err(HeaderValidationError::InvalidRequestsHash(None))
+        );
+    }
+
+    #[test]
+    fn test_validate_header_against_parent() {
+        let hardforks = schedule(OpHardfork::Isthmus);
+        let parent = Header { number: 1, ..isthmus_header() };
+        let params = BaseFeeParams::optimism_canyon();
+        // An empty parent halves the gas target away: 1e9 - 1e9 / 250.
+        let header = Header {
+            number: 2,
+            parent_hash: parent.hash_slow(),
+            timestamp: 102,
+            base_fee_per_gas: Some(996_000_000),
+            ..isthmus_header()
+        };
+        assert_eq!(validate_header_against_parent(&header, &parent, &hardforks, params), Ok(()));
+
+        let invalid = Header { number: 3, ..header.clone() };
+        assert_eq!(
+            validate_header_against_parent(&invalid, &parent, &hardforks, params),
+            Err(HeaderValidationError::InvalidBlockNumber { parent_number: 1, number: 3 })
+        );
+        let invalid = Header { parent_hash: B256::ZERO, ..header.clone() };
+        assert_eq!(
+            validate_header_against_parent(&invalid, &parent, &hardforks, params),
+            Err(HeaderValidationError::ParentHashMismatch {
+                expected: parent.hash_slow(),
+                got: B256::ZERO
+            })
+        );
+        let invalid = Header { timestamp: 100, ..header.clone() };
+        assert_eq!(
+            validate_header_against_parent(&invalid, &parent, &hardforks, params),
+            Err(HeaderValidationError::TimestampNotAfterParent {
+                parent_timestamp: 100,
+                timestamp: 100
+            })
+        );
+        let invalid = Header { base_fee_per_gas: Some(1), ..header };
+        assert_eq!(
+            validate_header_against_parent(&invalid, &parent, &hardforks, params),
+            Err(HeaderValidationError::BaseFeeMismatch { expected: 996_000_000, got: Some(1) })
+        );
+    }
+}
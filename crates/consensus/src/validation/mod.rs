@@ -0,0 +1,7 @@
+//! Validation of OP Stack blocks against the hardfork rules.
+
+mod header;
+pub use header::{HeaderValidationError, validate_header, validate_header_against_parent};
+
+mod body;
+pub use body::{BlockBodyValidationError, validate_block_body};
@@ -0,0 +1,272 @@
+//! Withdrawals initiated through the `L2ToL1MessagePasser`.
+
+use crate::{OpReceiptEnvelope, abi, predeploys::L2_TO_L1_MESSAGE_PASSER_ADDRESS};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256, Bytes, Log, U256, b256, keccak256};
+
+/// The topic of the `MessagePassed(uint256 indexed nonce, address indexed sender, address indexed
+/// target, uint256 value, uint256 gasLimit, bytes data, bytes32 withdrawalHash)` event emitted by
+/// the `L2ToL1MessagePasser`.
+pub const MESSAGE_PASSED_EVENT_TOPIC: B256 =
+    b256!("0x02a52367d10742d8032712c1bb8e0144ff1ec5ffda1ed7d70bb05a2744955054");
+
+/// The storage slot of the `sentMessages` mapping of the `L2ToL1MessagePasser`.
+pub const SENT_MESSAGES_SLOT: U256 = U256::ZERO;
+
+/// An error that can occur when decoding a `MessagePassed` event.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum MessagePassedError {
+    /// The log is not emitted by the `L2ToL1MessagePasser`.
+    #[error("log is not emitted by the L2ToL1MessagePasser: {0}")]
+    InvalidAddress(Address),
+    /// The log does not have the `MessagePassed` event topics.
+    #[error("invalid MessagePassed topics")]
+    InvalidTopics,
+    /// The log data is not a valid ABI encoding of the event fields.
+    #[error("invalid MessagePassed data")]
+    InvalidData,
+    /// The withdrawal hash of the event does not match the hash of the withdrawal.
+    #[error("withdrawal hash mismatch: expected {expected}, got {got}")]
+    WithdrawalHashMismatch {
+        /// The hash of the withdrawal.
+        expected: B256,
+        /// The withdrawal hash of the event.
+        got: B256,
+    },
+}
+
+/// A withdrawal transaction, initiated on L2 through the `L2ToL1MessagePasser` and finalized on
+/// L1 through the `OptimismPortal`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WithdrawalTransaction {
+    /// The nonce of the withdrawal, which embeds the message version in its top two bytes.
+    pub nonce: U256,
+    /// The L2 address that initiated the withdrawal.
+    pub sender: Address,
+    /// The L1 address that the withdrawal is sent to.
+    pub target: Address,
+    /// The ETH value sent to the target.
+    pub value: U256,
+    /// The gas limit of the call to the target.
+    pub gas_limit: U256,
+    /// The calldata of the call to the target.
+    pub data: Bytes,
+}
+
+impl WithdrawalTransaction {
+    /// Returns the withdrawal hash: the hash of the ABI encoding of the withdrawal fields.
+    pub fn hash(&self) -> B256 {
+        let mut encoded = Vec::with_capacity(32 * 8 + self.data.len());
+        encoded.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+        encoded.extend_from_slice(self.sender.into_word().as_slice());
+        encoded.extend_from_slice(self.target.into_word().as_slice());
+        encoded.extend_from_slice(&self.value.to_be_bytes::<32>());
+        encoded.extend_from_slice(&self.gas_limit.to_be_bytes::<32>());
+        encoded.extend_from_slice(&U256::from(32 * 6).to_be_bytes::<32>());
+        abi::encode_bytes(&mut encoded, &self.data);
+        keccak256(encoded)
+    }
+
+    /// Returns the storage slot of the `L2ToL1MessagePasser` that records the withdrawal as sent,
+    /// which is proven on L1. See [`sent_message_slot`].
+    pub fn storage_slot(&self) -> B256 {
+        sent_message_slot(self.hash())
+    }
+}
+
+/// Returns the storage slot of the `sentMessages` mapping of the `L2ToL1MessagePasser` for the
+/// given withdrawal hash.
+pub fn sent_message_slot(withdrawal_hash: B256) -> B256 {
+    let mut key = [0u8; 64];
+    key[..32].copy_from_slice(withdrawal_hash.as_slice());
+    key[32..].copy_from_slice(&SENT_MESSAGES_SLOT.to_be_bytes::<32>());
+    keccak256(key)
+}
+
+/// A `MessagePassed` event, emitted by the `L2ToL1MessagePasser` when a withdrawal is initiated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct MessagePassed {
+    /// The initiated withdrawal.
+    pub withdrawal: WithdrawalTransaction,
+    /// The hash of the withdrawal.
+    pub withdrawal_hash: B256,
+}
+
+impl MessagePassed {
+    /// Decodes a `MessagePassed` event log, checking that its withdrawal hash matches the hash of
+    /// the withdrawal.
+    pub fn decode_log(log: &Log) -> Result<Self, MessagePassedError> {
+        if log.address != L2_TO_L1_MESSAGE_PASSER_ADDRESS {
+            return Err(MessagePassedError::InvalidAddress(log.address));
+        }
+        let [topic, nonce, sender, target] = log.topics() else {
+            return Err(MessagePassedError::InvalidTopics);
+        };
+        if *topic != MESSAGE_PASSED_EVENT_TOPIC {
+            return Err(MessagePassedError::InvalidTopics);
+        }
+
+        let data = &log.data.data;
+        if data.len() < 32 * 5 || data.len() % 32 != 0 {
+            return Err(MessagePassedError::InvalidData);
+        }
+        let word = |i: usize| U256::from_be_slice(&data[32 * i..32 * (i + 1)]);
+        if word(2) != U256::from(32 * 4) {
+            return Err(MessagePassedError::InvalidData);
+        }
+        let len: usize = word(4).try_into().map_err(|_| MessagePassedError::InvalidData)?;
+        let encoded_len = len.div_ceil(32).checked_mul(32).and_then(|len| len.checked_add(32 * 5));
+        if encoded_len != Some(data.len()) {
+            return Err(MessagePassedError::InvalidData);
+        }
+
+        let withdrawal = WithdrawalTransaction {
+            nonce: U256::from_be_bytes(nonce.0),
+            sender: abi::address(sender.as_slice(), 0).ok_or(MessagePassedError::InvalidData)?,
+            target: abi::address(target.as_slice(), 0).ok_or(MessagePassedError::InvalidData)?,
+            value: word(0),
+            gas_limit: word(1),
+            data: data.slice(32 * 5..32 * 5 + len),
+        };
+        let withdrawal_hash = B256::from(word(3));
+        let expected = withdrawal.hash();
+        if expected != withdrawal_hash {
+            return Err(MessagePassedError::WithdrawalHashMismatch {
+                expected,
+                got: withdrawal_hash,
+            });
+        }
+        Ok(Self { withdrawal, withdrawal_hash })
+    }
+
+    /// Returns the `MessagePassed` event log of the withdrawal.
+    pub fn to_log(&self) -> Log {
+        let withdrawal = &self.withdrawal;
+        let mut data = Vec::with_capacity(32 * 5 + withdrawal.data.len());
+        data.extend_from_slice(&withdrawal.value.to_be_bytes::<32>());
+        data.extend_from_slice(&withdrawal.gas_limit.to_be_bytes::<32>());
+        data.extend_from_slice(&U256::from(32 * 4).to_be_bytes::<32>());
+        data.extend_from_slice(self.withdrawal_hash.as_slice());
+        abi::encode_bytes(&mut data, &withdrawal.data);
+        Log::new_unchecked(
+            L2_TO_L1_MESSAGE_PASSER_ADDRESS,
+            alloc::vec![
+                MESSAGE_PASSED_EVENT_TOPIC,
+                withdrawal.nonce.into(),
+                withdrawal.sender.into_word(),
+                withdrawal.target.into_word(),
+            ],
+            data.into(),
+        )
+    }
+}
+
+impl From<WithdrawalTransaction> for MessagePassed {
+    fn from(withdrawal: WithdrawalTransaction) -> Self {
+        Self { withdrawal_hash: withdrawal.hash(), withdrawal }
+    }
+}
+
+impl OpReceiptEnvelope {
+    /// Returns the `MessagePassed` events in the logs of the receipt, decoded in log order.
+    ///
+    /// Logs of the `L2ToL1MessagePasser` with other topics are skipped, while malformed
+    /// `MessagePassed` events are returned as errors.
+    pub fn message_passed_events(
+        &self,
+    ) -> impl Iterator<Item = Result<MessagePassed, MessagePassedError>> + '_ {
+        self.logs()
+            .iter()
+            .filter(|log| {
+                log.address == L2_TO_L1_MESSAGE_PASSER_ADDRESS
+                    && log.topics().first() == Some(&MESSAGE_PASSED_EVENT_TOPIC)
+            })
+            .map(MessagePassed::decode_log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{address, hex};
+
+    fn withdrawal() -> WithdrawalTransaction {
+        WithdrawalTransaction {
+            nonce: U256::from(1) << 240 | U256::from(5),
+            sender: address!("0x4200000000000000000000000000000000000007"),
+            target: address!("0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1"),
+            value: U256::from(10).pow(U256::from(18)),
+            gas_limit: U256::from(200_000),
+            data: Bytes::from_static(&hex!("d764ad0b0000000000")),
+        }
+    }
+
+    #[test]
+    fn test_withdrawal_hash() {
+        let withdrawal = withdrawal();
+        assert_eq!(
+            withdrawal.hash(),
+            b256!("0xa19df677e6b8abe1a18b3a13c4a604f7e13e855a3f1baff6cf2bf608402dce81")
+        );
+        assert_eq!(
+            withdrawal.storage_slot(),
+            b256!("0x6bb2d84f3523528dac899df64e8a549e960f84ae29ffa191e5c0066148d95d04")
+        );
+    }
+
+    #[test]
+    fn test_message_passed_roundtrip() {
+        let event = MessagePassed::from(withdrawal());
+        let log = event.to_log();
+        assert_eq!(log.data.data.len(), 32 * 6);
+        assert_eq!(MessagePassed::decode_log(&log), Ok(event.clone()));
+
+        let empty = MessagePassed::from(WithdrawalTransaction::default());
+        assert_eq!(MessagePassed::decode_log(&empty.to_log()), Ok(empty));
+
+        let mut invalid = event.clone();
+        invalid.withdrawal_hash = B256::ZERO;
+        assert_eq!(
+            MessagePassed::decode_log(&invalid.to_log()),
+            Err(MessagePassedError::WithdrawalHashMismatch {
+                expected: event.withdrawal_hash,
+                got: B256::ZERO
+            })
+        );
+
+        let mut invalid = log.clone();
+        invalid.data.data = invalid.data.data.slice(..32 * 5);
+        assert_eq!(MessagePassed::decode_log(&invalid), Err(MessagePassedError::InvalidData));
+        // A length word whose padded length overflows must not panic.
+        let mut invalid = log.clone();
+        let mut data = invalid.data.data.to_vec();
+        data[32 * 4..32 * 5].copy_from_slice(&U256::from(u64::MAX).to_be_bytes::<32>());
+        invalid.data.data = data.into();
+        assert_eq!(MessagePassed::decode_log(&invalid), Err(MessagePassedError::InvalidData));
+        let mut invalid = log;
+        invalid.address = Address::ZERO;
+        assert_eq!(
+            MessagePassed::decode_log(&invalid),
+            Err(MessagePassedError::InvalidAddress(Address::ZERO))
+        );
+    }
+
+    #[test]
+    fn test_receipt_message_passed_events() {
+        let event = MessagePassed::from(withdrawal());
+        let other =
+            Log::new_unchecked(L2_TO_L1_MESSAGE_PASSER_ADDRESS, vec![B256::ZERO], Bytes::new());
+        let receipt = OpReceiptEnvelope::Eip1559(ReceiptWithBloom::from(Receipt {
+            status: true.into(),
+            cumulative_gas_used: 100_000,
+            logs: vec![other, event.to_log()],
+        }));
+        assert_eq!(receipt.message_passed_events().collect::<Vec<_>>(), vec![Ok(event)]);
+    }
+}
@@ -27,6 +27,10 @@ pub use op_alloy_rpc_types as rpc_types;
 #[doc(inline)]
 pub use op_alloy_rpc_types_engine as rpc_types_engine;
 
+#[cfg(feature = "protocol")]
+#[doc(inline)]
+pub use op_alloy_protocol as protocol;
+
 #[cfg(feature = "rpc-jsonrpsee")]
 #[doc(inline)]
 pub use op_alloy_rpc_jsonrpsee as rpc_jsonrpsee;
@@ -0,0 +1,116 @@
+//! Batches of L2 transactions carried by channels.
+//!
+//! See the [batch format] in the derivation spec.
+//!
+//! [batch format]: https://specs.optimism.io/protocol/derivation.html#batch-format
+
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+use op_alloy_consensus::{DEPOSIT_TX_TYPE_ID, OpTxEnvelope, OpTxType};
+
+mod single;
+pub use single::SingleBatch;
+
+mod span;
+pub use span::{
+    MAX_SPAN_BATCH_ELEMENT_COUNT, RawSpanBatch, RawSpanBatchBlock, SpanBatch, SpanBatchElement,
+    SpanBatchError, SpanBatchTransaction, SpanTxData,
+};
+
+/// The version byte of a [`SingleBatch`].
+pub const SINGLE_BATCH_TYPE: u8 = 0;
+
+/// The version byte of a [`SpanBatch`], introduced with Delta.
+pub const SPAN_BATCH_TYPE: u8 = 1;
+
+/// An error decoding a batch.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatchDecodingError {
+    /// The batch data is empty.
+    #[error("empty batch data")]
+    Empty,
+    /// The version byte does not match the expected batch type.
+    #[error("invalid batch type: expected {expected}, got {got}")]
+    InvalidBatchType {
+        /// The expected version byte.
+        expected: u8,
+        /// The version byte of the batch.
+        got: u8,
+    },
+    /// The batch content is not valid RLP.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The span batch content is invalid.
+    #[error(transparent)]
+    Span(#[from] SpanBatchError),
+}
+
+/// An error converting the opaque transactions of a batch into [`OpTxEnvelope`]s.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum BatchTransactionError {
+    /// The transaction is empty.
+    #[error("empty transaction at index {0}")]
+    Empty(usize),
+    /// The transaction is a deposit, which the sequencer may not include in a batch.
+    #[error("deposit transaction at index {0}")]
+    Deposit(usize),
+    /// The transaction type is not supported.
+    #[error("unsupported transaction type {ty} at index {index}")]
+    UnsupportedType {
+        /// The index of the transaction in the batch.
+        index: usize,
+        /// The transaction type.
+        ty: u8,
+    },
+    /// The transaction failed to decode.
+    #[error("invalid transaction at index {index}: {error}")]
+    Invalid {
+        /// The index of the transaction in the batch.
+        index: usize,
+        /// The decoding error.
+        error: alloy_rlp::Error,
+    },
+}
+
+/// Decodes the [EIP-2718] encoded transactions of a batch.
+///
+/// Deposits are rejected: they are derived from L1 and may not be included by the sequencer.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+pub(crate) fn decode_transactions(
+    transactions: &[Bytes],
+) -> Result<Vec<OpTxEnvelope>, BatchTransactionError> {
+    use alloy_eips::eip2718::{Decodable2718, Eip2718Error};
+    use alloy_rlp::EMPTY_LIST_CODE;
+
+    transactions
+        .iter()
+        .enumerate()
+        .map(|(index, tx)| {
+            match tx.first() {
+                None => return Err(BatchTransactionError::Empty(index)),
+                Some(&DEPOSIT_TX_TYPE_ID) => return Err(BatchTransactionError::Deposit(index)),
+                // Legacy transactions start with an RLP list header.
+                Some(&ty) if ty < EMPTY_LIST_CODE && OpTxType::try_from(ty).is_err() => {
+                    return Err(BatchTransactionError::UnsupportedType { index, ty });
+                }
+                Some(_) => {}
+            }
+            let mut buf = &tx[..];
+            let envelope = OpTxEnvelope::decode_2718(&mut buf).map_err(|err| {
+                let error = match err {
+                    Eip2718Error::RlpError(error) => error,
+                    _ => alloy_rlp::Error::Custom("invalid transaction"),
+                };
+                BatchTransactionError::Invalid { index, error }
+            })?;
+            if !buf.is_empty() {
+                return Err(BatchTransactionError::Invalid {
+                    index,
+                    error: alloy_rlp::Error::UnexpectedLength,
+                });
+            }
+            Ok(envelope)
+        })
+        .collect()
+}
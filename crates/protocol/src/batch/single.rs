@@ -0,0 +1,249 @@
+//! Singular batches.
+
+use super::{BatchDecodingError, BatchTransactionError, SINGLE_BATCH_TYPE, decode_transactions};
+use alloc::vec::Vec;
+use alloy_primitives::{B256, Bytes};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use op_alloy_consensus::OpTxEnvelope;
+
+/// A batch of the transactions of a single L2 block.
+///
+/// Encoded as `rlp([parent_hash, epoch_number, epoch_hash, timestamp, transaction_list])`, where
+/// the transaction list holds the [EIP-2718] encoded transactions.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SingleBatch {
+    /// The hash of the parent L2 block.
+    pub parent_hash: B256,
+    /// The number of the L1 origin of the block.
+    pub epoch_num: u64,
+    /// The hash of the L1 origin of the block.
+    pub epoch_hash: B256,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The [EIP-2718] encoded transactions of the block, excluding deposits.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub transactions: Vec<Bytes>,
+}
+
+impl SingleBatch {
+    /// Decodes the transactions of the batch.
+    ///
+    /// Fails if any of the transactions is empty, undecodable or a deposit.
+    pub fn decode_transactions(&self) -> Result<Vec<OpTxEnvelope>, BatchTransactionError> {
+        decode_transactions(&self.transactions)
+    }
+
+    /// Encodes the batch prefixed with [`SINGLE_BATCH_TYPE`].
+    pub fn encode_batch(&self, out: &mut dyn BufMut) {
+        out.put_u8(SINGLE_BATCH_TYPE);
+        self.encode(out);
+    }
+
+    /// Decodes a batch prefixed with [`SINGLE_BATCH_TYPE`].
+    pub fn decode_batch(data: &[u8]) -> Result<Self, BatchDecodingError> {
+        let (&ty, mut buf) = data.split_first().ok_or(BatchDecodingError::Empty)?;
+        if ty != SINGLE_BATCH_TYPE {
+            return Err(BatchDecodingError::InvalidBatchType {
+                expected: SINGLE_BATCH_TYPE,
+                got: ty,
+            });
+        }
+        let batch = Self::decode(&mut buf)?;
+        if !buf.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength.into());
+        }
+        Ok(batch)
+    }
+
+    fn rlp_encoded_fields_length(&self) -> usize {
+        self.parent_hash.length()
+            + self.epoch_num.length()
+            + self.epoch_hash.length()
+            + self.timestamp.length()
+            + self.transactions.length()
+    }
+}
+
+impl Encodable for SingleBatch {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.rlp_encoded_fields_length() }.encode(out);
+        self.parent_hash.encode(out);
+        self.epoch_num.encode(out);
+        self.epoch_hash.encode(out);
+        self.timestamp.encode(out);
+        self.transactions.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.rlp_encoded_fields_length();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for SingleBatch {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let remaining = buf.len();
+        if header.payload_length > remaining {
+            return Err(alloy_rlp::Error::InputTooShort);
+        }
+
+        let batch = Self {
+            parent_hash: Decodable::decode(buf)?,
+            epoch_num: Decodable::decode(buf)?,
+            epoch_hash: Decodable::decode(buf)?,
+            timestamp: Decodable::decode(buf)?,
+            transactions: Decodable::decode(buf)?,
+        };
+        if buf.len() + header.payload_length != remaining {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Signed, TxEip1559, TxLegacy};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Signature, TxKind, U256, address, b256};
+    use op_alloy_consensus::TxDeposit;
+
+    fn envelopes() -> Vec<OpTxEnvelope> {
+        let to = TxKind::Call(address!("0x4200000000000000000000000000000000000011"));
+        vec![
+            OpTxEnvelope::Legacy(Signed::new_unchecked(
+                TxLegacy {
+                    chain_id: Some(10),
+                    nonce: 1,
+                    to,
+                    value: U256::from(2),
+                    ..Default::default()
+                },
+                Signature::test_signature(),
+                B256::ZERO,
+            )),
+            OpTxEnvelope::Eip1559(Signed::new_unchecked(
+                TxEip1559 { chain_id: 10, nonce: 2, to, gas_limit: 21_000, ..Default::default() },
+                Signature::test_signature(),
+                B256::ZERO,
+            )),
+        ]
+    }
+
+    fn batch(transactions: Vec<Bytes>) -> SingleBatch {
+        SingleBatch {
+            parent_hash: b256!(
+                "0x1111111111111111111111111111111111111111111111111111111111111111"
+            ),
+            epoch_num: 0x1234,
+            epoch_hash: b256!("0x2222222222222222222222222222222222222222222222222222222222222222"),
+            timestamp: 0x5678,
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test_single_batch_roundtrip() {
+        let envelopes = envelopes();
+        let batch = batch(envelopes.iter().map(|tx| tx.encoded_2718().into()).collect());
+
+        let mut encoded = Vec::new();
+        batch.encode_batch(&mut encoded);
+        assert_eq!(encoded[0], SINGLE_BATCH_TYPE);
+        assert_eq!(encoded.len(), 1 + batch.length());
+        // The list header is followed by the parent hash.
+        let mut buf = &encoded[1..];
+        assert!(Header::decode(&mut buf).unwrap().list);
+        assert_eq!(B256::decode(&mut buf).unwrap(), batch.parent_hash);
+
+        let decoded = SingleBatch::decode_batch(&encoded).unwrap();
+        assert_eq!(decoded, batch);
+
+        // Sealing re-hashes the transactions, compare the decoded transactions themselves.
+        let decoded = decoded.decode_transactions().unwrap();
+        assert_eq!(decoded.len(), envelopes.len());
+        for (decoded, envelope) in decoded.iter().zip(&envelopes) {
+            assert_eq!(decoded.encoded_2718(), envelope.encoded_2718());
+        }
+    }
+
+    #[test]
+    fn test_decode_batch_invalid() {
+        let mut encoded = Vec::new();
+        batch(vec![]).encode_batch(&mut encoded);
+
+        assert_eq!(SingleBatch::decode_batch(&[]), Err(BatchDecodingError::Empty));
+        let mut wrong_type = encoded.clone();
+        wrong_type[0] = 1;
+        assert_eq!(
+            SingleBatch::decode_batch(&wrong_type),
+            Err(BatchDecodingError::InvalidBatchType { expected: SINGLE_BATCH_TYPE, got: 1 })
+        );
+        assert_eq!(
+            SingleBatch::decode_batch(&encoded[..encoded.len() - 1]),
+            Err(BatchDecodingError::Rlp(alloy_rlp::Error::InputTooShort))
+        );
+        encoded.push(0);
+        assert_eq!(
+            SingleBatch::decode_batch(&encoded),
+            Err(BatchDecodingError::Rlp(alloy_rlp::Error::UnexpectedLength))
+        );
+    }
+
+    #[test]
+    fn test_decode_transactions_invalid() {
+        let valid: Bytes = envelopes()[1].encoded_2718().into();
+        let deposit = OpTxEnvelope::Deposit(alloy_consensus::Sealed::new(TxDeposit::default()));
+
+        let batch = batch(vec![valid.clone(), deposit.encoded_2718().into()]);
+        assert_eq!(batch.decode_transactions(), Err(BatchTransactionError::Deposit(1)));
+        let batch = SingleBatch { transactions: vec![Bytes::new()], ..batch };
+        assert_eq!(batch.decode_transactions(), Err(BatchTransactionError::Empty(0)));
+
+        let mut blob = valid.to_vec();
+        blob[0] = 0x03;
+        let batch = SingleBatch { transactions: vec![blob.into()], ..batch };
+        assert_eq!(
+            batch.decode_transactions(),
+            Err(BatchTransactionError::UnsupportedType { index: 0, ty: 3 })
+        );
+
+        let mut trailing = valid.to_vec();
+        trailing.push(0);
+        let batch = SingleBatch { transactions: vec![valid, trailing.into()], ..batch };
+        assert_eq!(
+            batch.decode_transactions(),
+            Err(BatchTransactionError::Invalid {
+                index: 1,
+                error: alloy_rlp::Error::UnexpectedLength
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_single_batch_roundtrip() {
+        use arbitrary::Arbitrary;
+        use rand::Rng;
+
+        let mut bytes = [0u8; 4096];
+        rand::rng().fill(bytes.as_mut_slice());
+        let mut u = arbitrary::Unstructured::new(&bytes);
+        for _ in 0..8 {
+            let batch = SingleBatch::arbitrary(&mut u).unwrap();
+            let mut encoded = Vec::new();
+            batch.encode_batch(&mut encoded);
+            assert_eq!(SingleBatch::decode_batch(&encoded).unwrap(), batch);
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Span batches, introduced with Delta.
+//!
+//! See the [span batch format] in the derivation spec.
+//!
+//! [span batch format]: https://specs.optimism.io/protocol/delta/span-batches.html
+
+use alloc::vec::Vec;
+use alloy_primitives::{FixedBytes, Signature};
+use op_alloy_consensus::{OpHardforks, OpTypedTransaction};
+
+mod raw;
+pub use raw::{RawSpanBatch, RawSpanBatchBlock};
+
+mod transaction;
+pub use transaction::{SpanBatchTransaction, SpanTxData};
+
+/// The maximum number of elements of a span batch: blocks, transactions, and bytes of a single
+/// `tx_data`.
+pub const MAX_SPAN_BATCH_ELEMENT_COUNT: u64 = 10_000_000;
+
+/// An error decoding a span batch, or converting it to or from its blocks.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum SpanBatchError {
+    /// The span batch data ended early.
+    #[error("span batch data truncated")]
+    Truncated,
+    /// A varint does not fit in a `u64`.
+    #[error("varint overflows u64")]
+    VarintOverflow,
+    /// A bitlist has bits set past its length.
+    #[error("bitlist has bits set past its length")]
+    InvalidBitlist,
+    /// The span batch has no blocks.
+    #[error("span batch has no blocks")]
+    Empty,
+    /// The span batch exceeds [`MAX_SPAN_BATCH_ELEMENT_COUNT`].
+    #[error("span batch exceeds {MAX_SPAN_BATCH_ELEMENT_COUNT} elements")]
+    TooBigSpanBatch,
+    /// A `tx_data` is not valid RLP.
+    #[error("invalid transaction data: {0}")]
+    InvalidTransactionData(#[from] alloy_rlp::Error),
+    /// A `tx_data` has an unsupported transaction type.
+    #[error("unsupported transaction type {0}")]
+    UnsupportedTransactionType(u8),
+    /// An [EIP-7702] transaction is a contract creation.
+    ///
+    /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+    #[error("EIP-7702 transaction is a contract creation")]
+    Eip7702ContractCreation,
+    /// An [EIP-7702] transaction in a block before Isthmus.
+    ///
+    /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+    #[error("EIP-7702 transaction in block {0} before Isthmus")]
+    Eip7702BeforeIsthmus(usize),
+    /// The first block of the span batch is before Delta, which introduced span batches.
+    #[error("span batch before Delta")]
+    BeforeDelta,
+    /// A deposit transaction, which the sequencer may not include in a batch.
+    #[error("deposit transaction in span batch")]
+    DepositTransaction,
+    /// A transaction commits to another chain ID.
+    #[error("chain ID mismatch: expected {expected}, got {got}")]
+    ChainIdMismatch {
+        /// The chain ID of the span batch.
+        expected: u64,
+        /// The chain ID of the transaction.
+        got: u64,
+    },
+    /// A block timestamp does not follow from the genesis timestamp and block time.
+    #[error("invalid timestamp of block {0}")]
+    InvalidTimestamp(usize),
+    /// A block L1 origin number cannot be encoded or derived from the origin bits.
+    #[error("invalid L1 origin of block {0}")]
+    InvalidL1Origin(usize),
+}
+
+/// A block of a [`SpanBatch`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanBatchElement {
+    /// The number of the L1 origin of the block.
+    pub epoch_num: u64,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+    /// The signed transactions of the block, excluding deposits.
+    pub transactions: Vec<(OpTypedTransaction, Signature)>,
+}
+
+/// A span batch: a range of consecutive L2 blocks, batched together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanBatch {
+    /// The first 20 bytes of the hash of the parent of the first block.
+    pub parent_check: FixedBytes<20>,
+    /// The first 20 bytes of the hash of the L1 origin of the last block.
+    pub l1_origin_check: FixedBytes<20>,
+    /// Whether the first block is the first L2 block of its L1 origin.
+    pub starts_epoch: bool,
+    /// The blocks of the span.
+    pub blocks: Vec<SpanBatchElement>,
+}
+
+impl SpanBatch {
+    /// Derives the blocks of a span batch of a chain with the given genesis timestamp, block time
+    /// and chain ID.
+    ///
+    /// The L1 origins are derived backwards from the L1 origin of the last block, decrementing at
+    /// each block with its origin bit set. Span batches whose first block is before Delta are
+    /// rejected, as are [EIP-7702] transactions in blocks before Isthmus.
+    ///
+    /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+    pub fn from_raw(
+        raw: &RawSpanBatch,
+        genesis_timestamp: u64,
+        block_time: u64,
+        chain_id: u64,
+        hardforks: &impl OpHardforks,
+    ) -> Result<Self, SpanBatchError> {
+        let first = raw.blocks.first().ok_or(SpanBatchError::Empty)?;
+
+        let mut epoch_num = raw.l1_origin_num;
+        let mut epochs = Vec::with_capacity(raw.blocks.len());
+        for (i, block) in raw.blocks.iter().enumerate().rev() {
+            epochs.push(epoch_num);
+            if block.origin_bit && i > 0 {
+                epoch_num =
+                    epoch_num.checked_sub(1).ok_or(SpanBatchError::InvalidL1Origin(i - 1))?;
+            }
+        }
+        epochs.reverse();
+
+        let blocks: Vec<SpanBatchElement> = raw
+            .blocks
+            .iter()
+            .zip(epochs)
+            .enumerate()
+            .map(|(i, (block, epoch_num))| {
+                let timestamp = (i as u64)
+                    .checked_mul(block_time)
+                    .and_then(|offset| offset.checked_add(raw.rel_timestamp))
+                    .and_then(|offset| offset.checked_add(genesis_timestamp))
+                    .ok_or(SpanBatchError::InvalidTimestamp(i))?;
+                let transactions = block
+                    .transactions
+                    .iter()
+                    .map(|tx| tx.to_signed(chain_id))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let element = SpanBatchElement { epoch_num, timestamp, transactions };
+                check_eip7702(&element, i, hardforks)?;
+                Ok(element)
+            })
+            .collect::<Result<_, SpanBatchError>>()?;
+        if !hardforks.is_delta_active_at_timestamp(blocks[0].timestamp) {
+            return Err(SpanBatchError::BeforeDelta);
+        }
+
+        Ok(Self {
+            parent_check: raw.parent_check,
+            l1_origin_check: raw.l1_origin_check,
+            starts_epoch: first.origin_bit,
+            blocks,
+        })
+    }
+
+    /// Encodes the blocks of the span batch for a chain with the given genesis timestamp, block
+    /// time and chain ID.
+    ///
+    /// The blocks must be spaced by the block time, and the L1 origin may only advance by one
+    /// between consecutive blocks. The first block must be at or after Delta.
+    pub fn to_raw(
+        &self,
+        genesis_timestamp: u64,
+        block_time: u64,
+        chain_id: u64,
+        hardforks: &impl OpHardforks,
+    ) -> Result<RawSpanBatch, SpanBatchError> {
+        let (first, last) =
+            self.blocks.first().zip(self.blocks.last()).ok_or(SpanBatchError::Empty)?;
+        if self.blocks.len() as u64 > MAX_SPAN_BATCH_ELEMENT_COUNT
+            || self.blocks.iter().map(|block| block.transactions.len() as u64).sum::<u64>()
+                > MAX_SPAN_BATCH_ELEMENT_COUNT
+        {
+            return Err(SpanBatchError::TooBigSpanBatch);
+        }
+        if !hardforks.is_delta_active_at_timestamp(first.timestamp) {
+            return Err(SpanBatchError::BeforeDelta);
+        }
+        let rel_timestamp = first
+            .timestamp
+            .checked_sub(genesis_timestamp)
+            .ok_or(SpanBatchError::InvalidTimestamp(0))?;
+
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for (i, block) in self.blocks.iter().enumerate() {
+            let timestamp = (i as u64)
+                .checked_mul(block_time)
+                .and_then(|offset| offset.checked_add(first.timestamp));
+            if timestamp != Some(block.timestamp) {
+                return Err(SpanBatchError::InvalidTimestamp(i));
+            }
+            let origin_bit = match i.checked_sub(1).map(|parent| self.blocks[parent].epoch_num) {
+                None => self.starts_epoch,
+                Some(parent) if parent == block.epoch_num => false,
+                Some(parent) if parent.checked_add(1) == Some(block.epoch_num) => true,
+                Some(_) => return Err(SpanBatchError::InvalidL1Origin(i)),
+            };
+            check_eip7702(block, i, hardforks)?;
+            let transactions = block
+                .transactions
+                .iter()
+                .map(|(tx, signature)| SpanBatchTransaction::from_signed(tx, signature, chain_id))
+                .collect::<Result<_, _>>()?;
+            blocks.push(RawSpanBatchBlock { origin_bit, transactions });
+        }
+
+        Ok(RawSpanBatch {
+            rel_timestamp,
+            l1_origin_num: last.epoch_num,
+            parent_check: self.parent_check,
+            l1_origin_check: self.l1_origin_check,
+            blocks,
+        })
+    }
+}
+
+/// Rejects [EIP-7702] transactions in a block before Isthmus.
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+fn check_eip7702(
+    block: &SpanBatchElement,
+    index: usize,
+    hardforks: &impl OpHardforks,
+) -> Result<(), SpanBatchError> {
+    if !hardforks.is_isthmus_active_at_timestamp(block.timestamp)
+        && block.transactions.iter().any(|(tx, _)| matches!(tx, OpTypedTransaction::Eip7702(_)))
+    {
+        return Err(SpanBatchError::Eip7702BeforeIsthmus(index));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{TxEip1559, TxEip2930, TxEip7702, TxLegacy};
+    use alloy_eips::eip7702::Authorization;
+    use alloy_primitives::{Address, TxKind, U256};
+    use op_alloy_consensus::{ForkCondition, OpHardfork, OpHardforkSchedule, TxDeposit};
+
+    const CHAIN_ID: u64 = 10;
+    const GENESIS: u64 = 1000;
+    const BLOCK_TIME: u64 = 2;
+
+    fn schedule(isthmus_time: u64) -> OpHardforkSchedule {
+        schedule_with(0, isthmus_time)
+    }
+
+    fn schedule_with(delta_time: u64, isthmus_time: u64) -> OpHardforkSchedule {
+        let forks = OpHardfork::ALL.into_iter().take_while(|fork| *fork != OpHardfork::Jovian);
+        OpHardforkSchedule::new(forks.map(|fork| {
+            let condition = match fork {
+                OpHardfork::Bedrock => ForkCondition::Block(0),
+                OpHardfork::Isthmus => ForkCondition::Timestamp(isthmus_time),
+                _ if fork >= OpHardfork::Delta => ForkCondition::Timestamp(delta_time),
+                _ => ForkCondition::Timestamp(0),
+            };
+            (fork, condition)
+        }))
+        .unwrap()
+    }
+
+    fn signature(y_parity: bool) -> Signature {
+        Signature::new(U256::from(1), U256::from(2), y_parity)
+    }
+
+    fn eip7702() -> OpTypedTransaction {
+        OpTypedTransaction::Eip7702(TxEip7702 {
+            chain_id: CHAIN_ID,
+            nonce: 4,
+            gas_limit: 100_000,
+            to: Address::repeat_byte(0x44),
+            authorization_list: vec![
+                Authorization { chain_id: U256::from(CHAIN_ID), address: Address::ZERO, nonce: 1 }
+                    .into_signed(signature(true)),
+            ],
+            ..Default::default()
+        })
+    }
+
+    fn transactions() -> Vec<(OpTypedTransaction, Signature)> {
+        let to = TxKind::Call(Address::repeat_byte(0x33));
+        vec![
+            (
+                OpTypedTransaction::Legacy(TxLegacy {
+                    chain_id: Some(CHAIN_ID),
+                    nonce: 1,
+                    gas_price: 7,
+                    gas_limit: 21_000,
+                    to,
+                    value: U256::from(5),
+                    input: Default::default(),
+                }),
+                signature(true),
+            ),
+            (
+                OpTypedTransaction::Legacy(TxLegacy {
+                    chain_id: None,
+                    nonce: 2,
+                    gas_limit: 1_000_000,
+                    to: TxKind::Create,
+                    input: vec![0x60, 0x00].into(),
+                    ..Default::default()
+                }),
+                signature(false),
+            ),
+            (
+                OpTypedTransaction::Eip2930(TxEip2930 {
+                    chain_id: CHAIN_ID,
+                    nonce: 3,
+                    gas_price: 7,
+                    gas_limit: 30_000,
+                    to,
+                    ..Default::default()
+                }),
+                signature(false),
+            ),
+            (
+                OpTypedTransaction::Eip1559(TxEip1559 {
+                    chain_id: CHAIN_ID,
+                    nonce: 4,
+                    gas_limit: 21_000,
+                    max_fee_per_gas: 9,
+                    max_priority_fee_per_gas: 1,
+                    to,
+                    ..Default::default()
+                }),
+                signature(true),
+            ),
+        ]
+    }
+
+    fn batch(transactions: Vec<(OpTypedTransaction, Signature)>) -> SpanBatch {
+        let (first, second) = transactions.split_at(transactions.len() / 2);
+        SpanBatch {
+            parent_check: FixedBytes::repeat_byte(0x11),
+            l1_origin_check: FixedBytes::repeat_byte(0x22),
+            starts_epoch: false,
+            blocks: vec![
+                SpanBatchElement { epoch_num: 7, timestamp: 1010, transactions: first.to_vec() },
+                SpanBatchElement { epoch_num: 7, timestamp: 1012, transactions: vec![] },
+                SpanBatchElement { epoch_num: 8, timestamp: 1014, transactions: second.to_vec() },
+            ],
+        }
+    }
+
+    fn roundtrip(batch: &SpanBatch, hardforks: &OpHardforkSchedule) -> SpanBatch {
+        let raw = batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, hardforks).unwrap();
+        let mut encoded = Vec::new();
+        raw.encode_batch(&mut encoded);
+        let decoded = RawSpanBatch::decode_batch(&encoded).unwrap();
+        assert_eq!(decoded, raw);
+        SpanBatch::from_raw(&decoded, GENESIS, BLOCK_TIME, CHAIN_ID, hardforks).unwrap()
+    }
+
+    #[test]
+    fn test_span_batch_roundtrip() {
+        let batch = batch(transactions());
+        let raw = batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)).unwrap();
+        assert_eq!(raw.rel_timestamp, 10);
+        assert_eq!(raw.l1_origin_num, 8);
+        assert_eq!(
+            raw.blocks.iter().map(|block| block.origin_bit).collect::<Vec<_>>(),
+            [false, false, true]
+        );
+        let protected = raw.transactions().map(|tx| tx.protected).collect::<Vec<_>>();
+        assert_eq!(protected, [true, false, false, false]);
+
+        assert_eq!(roundtrip(&batch, &schedule(0)), batch);
+    }
+
+    #[test]
+    fn test_span_batch_eip7702() {
+        let mut transactions = transactions();
+        transactions.push((eip7702(), signature(false)));
+        let batch = batch(transactions);
+        assert_eq!(roundtrip(&batch, &schedule(1014)), batch);
+
+        // The EIP-7702 transaction is in the last block.
+        let hardforks = schedule(1015);
+        assert_eq!(
+            batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks),
+            Err(SpanBatchError::Eip7702BeforeIsthmus(2))
+        );
+        let raw = batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)).unwrap();
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks),
+            Err(SpanBatchError::Eip7702BeforeIsthmus(2))
+        );
+
+        let mut raw = raw;
+        raw.blocks[2].transactions.last_mut().unwrap().to = None;
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)),
+            Err(SpanBatchError::Eip7702ContractCreation)
+        );
+    }
+
+    #[test]
+    fn test_span_batch_epochs() {
+        let block = |origin_bit| RawSpanBatchBlock { origin_bit, transactions: vec![] };
+        let raw = RawSpanBatch {
+            rel_timestamp: 0,
+            l1_origin_num: 5,
+            blocks: vec![block(true), block(false), block(true), block(true)],
+            ..Default::default()
+        };
+        let batch = SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)).unwrap();
+        assert!(batch.starts_epoch);
+        assert_eq!(
+            batch.blocks.iter().map(|block| block.epoch_num).collect::<Vec<_>>(),
+            [3, 3, 4, 5]
+        );
+        assert_eq!(
+            batch.blocks.iter().map(|block| block.timestamp).collect::<Vec<_>>(),
+            [1000, 1002, 1004, 1006]
+        );
+        assert_eq!(batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)), Ok(raw.clone()));
+
+        let raw = RawSpanBatch { l1_origin_num: 1, ..raw };
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)),
+            Err(SpanBatchError::InvalidL1Origin(1))
+        );
+        let raw = RawSpanBatch { blocks: vec![], ..raw };
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)),
+            Err(SpanBatchError::Empty)
+        );
+        let raw = RawSpanBatch { rel_timestamp: u64::MAX, blocks: vec![block(false)], ..raw };
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)),
+            Err(SpanBatchError::InvalidTimestamp(0))
+        );
+    }
+
+    #[test]
+    fn test_span_batch_before_delta() {
+        // The first block is at 1010, one block before Delta.
+        let hardforks = schedule_with(1012, 1012);
+        let raw = batch(vec![]).to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &schedule(0)).unwrap();
+        assert_eq!(
+            SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks),
+            Err(SpanBatchError::BeforeDelta)
+        );
+        assert_eq!(
+            batch(vec![]).to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks),
+            Err(SpanBatchError::BeforeDelta)
+        );
+
+        let raw = RawSpanBatch { rel_timestamp: 12, ..raw };
+        assert!(SpanBatch::from_raw(&raw, GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks).is_ok());
+    }
+
+    #[test]
+    fn test_span_batch_to_raw_invalid() {
+        let hardforks = schedule(0);
+        let to_raw = |batch: &SpanBatch| batch.to_raw(GENESIS, BLOCK_TIME, CHAIN_ID, &hardforks);
+        assert_eq!(to_raw(&SpanBatch::default()), Err(SpanBatchError::Empty));
+
+        let mut invalid = batch(vec![]);
+        invalid.blocks[1].timestamp += 1;
+        assert_eq!(to_raw(&invalid), Err(SpanBatchError::InvalidTimestamp(1)));
+        let mut invalid = batch(vec![]);
+        invalid.blocks.iter_mut().for_each(|block| block.timestamp -= 12);
+        assert_eq!(to_raw(&invalid), Err(SpanBatchError::InvalidTimestamp(0)));
+        let mut invalid = batch(vec![]);
+        invalid.blocks[2].epoch_num = 9;
+        assert_eq!(to_raw(&invalid), Err(SpanBatchError::InvalidL1Origin(2)));
+        let mut invalid = batch(vec![]);
+        invalid.blocks[1].epoch_num = 6;
+        assert_eq!(to_raw(&invalid), Err(SpanBatchError::InvalidL1Origin(1)));
+
+        let deposit = (OpTypedTransaction::Deposit(TxDeposit::default()), signature(false));
+        assert_eq!(to_raw(&batch(vec![deposit])), Err(SpanBatchError::DepositTransaction));
+        let mut transactions = transactions();
+        if let OpTypedTransaction::Eip1559(tx) = &mut transactions[3].0 {
+            tx.chain_id = 1;
+        }
+        assert_eq!(
+            to_raw(&batch(transactions)),
+            Err(SpanBatchError::ChainIdMismatch { expected: CHAIN_ID, got: 1 })
+        );
+    }
+
+    #[test]
+    fn test_span_tx_data_invalid() {
+        assert_eq!(SpanTxData::decode(&mut [].as_slice()), Err(SpanBatchError::Truncated));
+        assert_eq!(
+            SpanTxData::decode(&mut [0x03, 0xc0].as_slice()),
+            Err(SpanBatchError::UnsupportedTransactionType(3))
+        );
+        assert_eq!(
+            SpanTxData::decode(&mut [0x80].as_slice()),
+            Err(SpanBatchError::InvalidTransactionData(alloy_rlp::Error::UnexpectedString))
+        );
+        assert_eq!(
+            SpanTxData::decode(&mut [0xc4, 0x01, 0x02, 0x80, 0x03].as_slice()),
+            Err(SpanBatchError::InvalidTransactionData(alloy_rlp::Error::ListLengthMismatch {
+                expected: 4,
+                got: 3
+            }))
+        );
+
+        let mut oversized = Vec::new();
+        alloy_rlp::Header { list: true, payload_length: MAX_SPAN_BATCH_ELEMENT_COUNT as usize }
+            .encode(&mut oversized);
+        oversized.resize(oversized.len() + MAX_SPAN_BATCH_ELEMENT_COUNT as usize, 0);
+        assert_eq!(
+            SpanTxData::decode(&mut oversized.as_slice()),
+            Err(SpanBatchError::TooBigSpanBatch)
+        );
+    }
+}
@@ -0,0 +1,415 @@
+//! The wire encoding of span batches.
+
+use super::{MAX_SPAN_BATCH_ELEMENT_COUNT, SpanBatchError, SpanBatchTransaction, SpanTxData};
+use crate::batch::{BatchDecodingError, SPAN_BATCH_TYPE};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{Address, FixedBytes, Signature, U256};
+use alloy_rlp::BufMut;
+
+/// A block of a [`RawSpanBatch`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawSpanBatchBlock {
+    /// Whether the L1 origin changed at this block. For the first block of the span, whether it is
+    /// the first L2 block of its L1 origin.
+    pub origin_bit: bool,
+    /// The transactions of the block.
+    pub transactions: Vec<SpanBatchTransaction>,
+}
+
+/// A span batch as encoded on the wire.
+///
+/// Encoded as `prefix ++ payload`, where
+///
+/// ```text
+/// prefix  := rel_timestamp ++ l1_origin_num ++ parent_check ++ l1_origin_check
+/// payload := block_count ++ origin_bits ++ block_tx_counts ++ txs
+/// txs     := contract_creation_bits ++ y_parity_bits ++ tx_sigs ++ tx_tos ++ tx_datas
+///            ++ tx_nonces ++ tx_gases ++ protected_bits
+/// ```
+///
+/// Integers are unsigned varints and bitlists are big-endian integers of the given number of bits,
+/// with the bit of the first element in the least significant position. The transactions are
+/// stored by column here, and by row in [`RawSpanBatchBlock`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawSpanBatch {
+    /// The timestamp of the first block, relative to the L2 genesis.
+    pub rel_timestamp: u64,
+    /// The number of the L1 origin of the last block.
+    pub l1_origin_num: u64,
+    /// The first 20 bytes of the hash of the parent of the first block.
+    pub parent_check: FixedBytes<20>,
+    /// The first 20 bytes of the hash of the L1 origin of the last block.
+    pub l1_origin_check: FixedBytes<20>,
+    /// The blocks of the span.
+    pub blocks: Vec<RawSpanBatchBlock>,
+}
+
+impl RawSpanBatch {
+    /// Returns an iterator over the transactions of all blocks.
+    pub fn transactions(&self) -> impl Iterator<Item = &SpanBatchTransaction> {
+        self.blocks.iter().flat_map(|block| &block.transactions)
+    }
+
+    /// Appends the encoded span batch to `out`.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        write_uvarint(out, self.rel_timestamp);
+        write_uvarint(out, self.l1_origin_num);
+        out.put_slice(self.parent_check.as_slice());
+        out.put_slice(self.l1_origin_check.as_slice());
+
+        write_uvarint(out, self.blocks.len() as u64);
+        write_bits(out, self.blocks.iter().map(|block| block.origin_bit));
+        for block in &self.blocks {
+            write_uvarint(out, block.transactions.len() as u64);
+        }
+
+        write_bits(out, self.transactions().map(|tx| tx.to.is_none()));
+        write_bits(out, self.transactions().map(|tx| tx.signature.v()));
+        for tx in self.transactions() {
+            out.put_slice(&tx.signature.r().to_be_bytes::<32>());
+            out.put_slice(&tx.signature.s().to_be_bytes::<32>());
+        }
+        for to in self.transactions().filter_map(|tx| tx.to) {
+            out.put_slice(to.as_slice());
+        }
+        for tx in self.transactions() {
+            tx.data.encode(out);
+        }
+        for tx in self.transactions() {
+            write_uvarint(out, tx.nonce);
+        }
+        for tx in self.transactions() {
+            write_uvarint(out, tx.gas_limit);
+        }
+        write_bits(
+            out,
+            self.transactions().filter(|tx| tx.data.is_legacy()).map(|tx| tx.protected),
+        );
+    }
+
+    /// Decodes a span batch from the start of `buf`.
+    ///
+    /// The block count, the transaction count of each block, their total and the size of each
+    /// `tx_data` are limited to [`MAX_SPAN_BATCH_ELEMENT_COUNT`], and every column is checked to
+    /// be present before it is allocated.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, SpanBatchError> {
+        let rel_timestamp = read_uvarint(buf)?;
+        let l1_origin_num = read_uvarint(buf)?;
+        let parent_check = FixedBytes::from_slice(take(buf, 20)?);
+        let l1_origin_check = FixedBytes::from_slice(take(buf, 20)?);
+
+        let block_count = read_uvarint(buf)?;
+        if block_count == 0 {
+            return Err(SpanBatchError::Empty);
+        }
+        if block_count > MAX_SPAN_BATCH_ELEMENT_COUNT {
+            return Err(SpanBatchError::TooBigSpanBatch);
+        }
+        let origin_bits = read_bits(buf, block_count as usize)?;
+        let mut block_tx_counts = Vec::new();
+        let mut tx_count = 0u64;
+        for _ in 0..block_count {
+            let count = read_uvarint(buf)?;
+            tx_count = tx_count.saturating_add(count);
+            if tx_count > MAX_SPAN_BATCH_ELEMENT_COUNT {
+                return Err(SpanBatchError::TooBigSpanBatch);
+            }
+            block_tx_counts.push(count as usize);
+        }
+        let tx_count = tx_count as usize;
+
+        let contract_creation_bits = read_bits(buf, tx_count)?;
+        let y_parity_bits = read_bits(buf, tx_count)?;
+        let signatures = take(buf, tx_count * 64)?;
+        let to_count = contract_creation_bits.iter().filter(|&&creation| !creation).count();
+        let mut tos = take(buf, to_count * 20)?.chunks_exact(20).map(Address::from_slice);
+        let mut datas = Vec::new();
+        for _ in 0..tx_count {
+            datas.push(SpanTxData::decode(buf)?);
+        }
+        let mut nonces = Vec::new();
+        for _ in 0..tx_count {
+            nonces.push(read_uvarint(buf)?);
+        }
+        let mut gases = Vec::new();
+        for _ in 0..tx_count {
+            gases.push(read_uvarint(buf)?);
+        }
+        let legacy_count = datas.iter().filter(|data| data.is_legacy()).count();
+        let mut protected_bits = read_bits(buf, legacy_count)?.into_iter();
+
+        let mut transactions = datas
+            .into_iter()
+            .zip(nonces)
+            .zip(gases)
+            .zip(signatures.chunks_exact(64))
+            .enumerate()
+            .map(|(i, (((data, nonce), gas_limit), signature))| SpanBatchTransaction {
+                to: if contract_creation_bits[i] { None } else { tos.next() },
+                nonce,
+                gas_limit,
+                signature: Signature::new(
+                    U256::from_be_slice(&signature[..32]),
+                    U256::from_be_slice(&signature[32..]),
+                    y_parity_bits[i],
+                ),
+                protected: data.is_legacy() && protected_bits.next().unwrap_or_default(),
+                data,
+            });
+        let blocks = origin_bits
+            .into_iter()
+            .zip(block_tx_counts)
+            .map(|(origin_bit, count)| RawSpanBatchBlock {
+                origin_bit,
+                transactions: transactions.by_ref().take(count).collect(),
+            })
+            .collect();
+
+        Ok(Self { rel_timestamp, l1_origin_num, parent_check, l1_origin_check, blocks })
+    }
+
+    /// Encodes the span batch prefixed with [`SPAN_BATCH_TYPE`].
+    pub fn encode_batch(&self, out: &mut dyn BufMut) {
+        out.put_u8(SPAN_BATCH_TYPE);
+        self.encode(out);
+    }
+
+    /// Decodes a span batch prefixed with [`SPAN_BATCH_TYPE`].
+    ///
+    /// Like the reference implementation, this ignores any bytes following the span batch.
+    pub fn decode_batch(data: &[u8]) -> Result<Self, BatchDecodingError> {
+        let (&ty, mut buf) = data.split_first().ok_or(BatchDecodingError::Empty)?;
+        if ty != SPAN_BATCH_TYPE {
+            return Err(BatchDecodingError::InvalidBatchType {
+                expected: SPAN_BATCH_TYPE,
+                got: ty,
+            });
+        }
+        Ok(Self::decode(&mut buf)?)
+    }
+}
+
+/// Splits off the first `len` bytes of `buf`.
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], SpanBatchError> {
+    if buf.len() < len {
+        return Err(SpanBatchError::Truncated);
+    }
+    let (head, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(head)
+}
+
+/// Writes `value` as an unsigned LEB128 varint.
+fn write_uvarint(out: &mut dyn BufMut, mut value: u64) {
+    while value >= 0x80 {
+        out.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.put_u8(value as u8);
+}
+
+/// Reads an unsigned LEB128 varint of at most 10 bytes.
+///
+/// Like Go's `binary.ReadUvarint`, this accepts non-minimal encodings.
+fn read_uvarint(buf: &mut &[u8]) -> Result<u64, SpanBatchError> {
+    let mut value = 0u64;
+    for i in 0..10 {
+        let &byte = take(buf, 1)?.first().unwrap();
+        if byte < 0x80 {
+            if i == 9 && byte > 1 {
+                return Err(SpanBatchError::VarintOverflow);
+            }
+            return Ok(value | (byte as u64) << (7 * i));
+        }
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+    }
+    Err(SpanBatchError::VarintOverflow)
+}
+
+/// Writes a bitlist as a big-endian integer, with the first bit in the least significant
+/// position.
+fn write_bits(out: &mut dyn BufMut, bits: impl IntoIterator<Item = bool>) {
+    let bits = bits.into_iter().collect::<Vec<_>>();
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    let len = bytes.len();
+    for (i, bit) in bits.into_iter().enumerate() {
+        bytes[len - 1 - i / 8] |= (bit as u8) << (i % 8);
+    }
+    out.put_slice(&bytes);
+}
+
+/// Reads a bitlist of `bit_len` bits, rejecting bits set past its length.
+fn read_bits(buf: &mut &[u8], bit_len: usize) -> Result<Vec<bool>, SpanBatchError> {
+    let bytes = take(buf, bit_len.div_ceil(8))?;
+    if bit_len % 8 != 0 && bytes[0] >> (bit_len % 8) != 0 {
+        return Err(SpanBatchError::InvalidBitlist);
+    }
+    Ok((0..bit_len).map(|i| bytes[bytes.len() - 1 - i / 8] >> (i % 8) & 1 == 1).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uvarint(value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_uvarint(&mut out, value);
+        out
+    }
+
+    fn bits(bits: &[bool]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_bits(&mut out, bits.iter().copied());
+        out
+    }
+
+    fn batch() -> RawSpanBatch {
+        let tx = |to, data, protected| SpanBatchTransaction {
+            data,
+            to,
+            nonce: 300,
+            gas_limit: 21_000,
+            signature: Signature::test_signature(),
+            protected,
+        };
+        let legacy =
+            SpanTxData::Legacy { value: U256::from(1), gas_price: 2, input: vec![3].into() };
+        let eip1559 = SpanTxData::Eip1559 {
+            value: U256::ZERO,
+            max_priority_fee_per_gas: 1,
+            max_fee_per_gas: 2,
+            input: Default::default(),
+            access_list: Default::default(),
+        };
+        RawSpanBatch {
+            rel_timestamp: 12,
+            l1_origin_num: 1000,
+            parent_check: FixedBytes::repeat_byte(0x11),
+            l1_origin_check: FixedBytes::repeat_byte(0x22),
+            blocks: vec![
+                RawSpanBatchBlock {
+                    origin_bit: true,
+                    transactions: vec![
+                        tx(Some(Address::repeat_byte(0x33)), legacy.clone(), true),
+                        tx(None, eip1559, false),
+                    ],
+                },
+                RawSpanBatchBlock { origin_bit: false, transactions: vec![] },
+                RawSpanBatchBlock { origin_bit: true, transactions: vec![tx(None, legacy, false)] },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_uvarint() {
+        assert_eq!(uvarint(0), [0x00]);
+        assert_eq!(uvarint(0x7f), [0x7f]);
+        assert_eq!(uvarint(0x80), [0x80, 0x01]);
+        assert_eq!(uvarint(300), [0xac, 0x02]);
+        let max = uvarint(u64::MAX);
+        assert_eq!(max, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+
+        for value in [0, 0x7f, 0x80, 300, u64::MAX] {
+            let encoded = uvarint(value);
+            let mut buf = encoded.as_slice();
+            assert_eq!(read_uvarint(&mut buf), Ok(value));
+            assert!(buf.is_empty());
+        }
+        // Non-minimal encodings are accepted.
+        assert_eq!(read_uvarint(&mut [0x80, 0x00].as_slice()), Ok(0));
+
+        let mut overflow = max;
+        overflow[9] = 0x02;
+        assert_eq!(read_uvarint(&mut overflow.as_slice()), Err(SpanBatchError::VarintOverflow));
+        assert_eq!(read_uvarint(&mut [0x80; 10].as_slice()), Err(SpanBatchError::VarintOverflow));
+        assert_eq!(read_uvarint(&mut [0x80].as_slice()), Err(SpanBatchError::Truncated));
+    }
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(bits(&[]), [0u8; 0]);
+        assert_eq!(bits(&[true, false, true]), [0b101]);
+        let mut nine = [false; 9];
+        nine[0] = true;
+        nine[8] = true;
+        assert_eq!(bits(&nine), [0x01, 0x01]);
+
+        assert_eq!(read_bits(&mut [0x01, 0x01].as_slice(), 9), Ok(nine.to_vec()));
+        assert_eq!(read_bits(&mut [0b101].as_slice(), 3), Ok(vec![true, false, true]));
+        assert_eq!(read_bits(&mut [0b1101].as_slice(), 3), Err(SpanBatchError::InvalidBitlist));
+        assert_eq!(read_bits(&mut [0x01].as_slice(), 9), Err(SpanBatchError::Truncated));
+    }
+
+    #[test]
+    fn test_raw_span_batch_roundtrip() {
+        let batch = batch();
+        let mut encoded = Vec::new();
+        batch.encode_batch(&mut encoded);
+        assert_eq!(encoded[0], SPAN_BATCH_TYPE);
+        assert_eq!(RawSpanBatch::decode_batch(&encoded), Ok(batch.clone()));
+
+        // Trailing bytes are ignored.
+        encoded.push(0);
+        assert_eq!(RawSpanBatch::decode_batch(&encoded), Ok(batch));
+    }
+
+    #[test]
+    fn test_raw_span_batch_truncated() {
+        let mut encoded = Vec::new();
+        batch().encode(&mut encoded);
+        for len in 0..encoded.len() {
+            assert_eq!(
+                RawSpanBatch::decode(&mut &encoded[..len]),
+                Err(SpanBatchError::Truncated),
+                "prefix of length {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_span_batch_invalid() {
+        assert_eq!(RawSpanBatch::decode_batch(&[]), Err(BatchDecodingError::Empty));
+        assert_eq!(
+            RawSpanBatch::decode_batch(&[0]),
+            Err(BatchDecodingError::InvalidBatchType { expected: SPAN_BATCH_TYPE, got: 0 })
+        );
+
+        let prefix = [uvarint(0), uvarint(0), vec![0; 40]].concat();
+        let decode = |payload: &[u8]| {
+            RawSpanBatch::decode_batch(&[&[SPAN_BATCH_TYPE], &prefix[..], payload].concat())
+        };
+        assert_eq!(decode(&uvarint(0)), Err(SpanBatchError::Empty.into()));
+        assert_eq!(
+            decode(&uvarint(MAX_SPAN_BATCH_ELEMENT_COUNT + 1)),
+            Err(SpanBatchError::TooBigSpanBatch.into())
+        );
+        let payload =
+            [uvarint(2), bits(&[true, false]), uvarint(MAX_SPAN_BATCH_ELEMENT_COUNT), uvarint(1)]
+                .concat();
+        assert_eq!(decode(&payload), Err(SpanBatchError::TooBigSpanBatch.into()));
+        let payload = [uvarint(2), vec![0b111]].concat();
+        assert_eq!(decode(&payload), Err(SpanBatchError::InvalidBitlist.into()));
+    }
+
+    #[test]
+    fn test_raw_span_batch_corrupted() {
+        use rand::Rng;
+
+        let mut encoded = Vec::new();
+        batch().encode(&mut encoded);
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let mut corrupted = encoded.clone();
+            for _ in 0..rng.random_range(1..4) {
+                let i = rng.random_range(0..corrupted.len());
+                corrupted[i] = rng.random();
+            }
+            // Decoding arbitrary data fails or yields a batch that re-encodes, without panicking.
+            if let Ok(batch) = RawSpanBatch::decode(&mut corrupted.as_slice()) {
+                let mut reencoded = Vec::new();
+                batch.encode(&mut reencoded);
+                assert_eq!(RawSpanBatch::decode(&mut reencoded.as_slice()), Ok(batch));
+            }
+        }
+    }
+}
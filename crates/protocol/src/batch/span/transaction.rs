@@ -0,0 +1,421 @@
+//! Transactions of span batches.
+
+use super::SpanBatchError;
+use alloc::vec::Vec;
+use alloy_consensus::{TxEip1559, TxEip2930, TxEip7702, TxLegacy};
+use alloy_eips::{
+    eip2718::{EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP7702_TX_TYPE_ID},
+    eip2930::AccessList,
+    eip7702::SignedAuthorization,
+};
+use alloy_primitives::{Address, Bytes, Signature, TxKind, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header};
+use op_alloy_consensus::OpTypedTransaction;
+
+/// The fields of a span batch transaction that are encoded in its `tx_data`.
+///
+/// Legacy transactions are encoded as `rlp([value, gas_price, data])`, typed transactions as their
+/// type byte followed by the RLP list of their fields. The remaining fields of the transactions are
+/// encoded in the other columns of the span batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanTxData {
+    /// A legacy transaction.
+    Legacy {
+        /// The value transferred.
+        value: U256,
+        /// The gas price.
+        gas_price: u128,
+        /// The calldata.
+        input: Bytes,
+    },
+    /// An [EIP-2930] transaction.
+    ///
+    /// [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+    Eip2930 {
+        /// The value transferred.
+        value: U256,
+        /// The gas price.
+        gas_price: u128,
+        /// The calldata.
+        input: Bytes,
+        /// The access list.
+        access_list: AccessList,
+    },
+    /// An [EIP-1559] transaction.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    Eip1559 {
+        /// The value transferred.
+        value: U256,
+        /// The maximum priority fee per gas.
+        max_priority_fee_per_gas: u128,
+        /// The maximum fee per gas.
+        max_fee_per_gas: u128,
+        /// The calldata.
+        input: Bytes,
+        /// The access list.
+        access_list: AccessList,
+    },
+    /// An [EIP-7702] transaction, accepted since Isthmus.
+    ///
+    /// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+    Eip7702 {
+        /// The value transferred.
+        value: U256,
+        /// The maximum priority fee per gas.
+        max_priority_fee_per_gas: u128,
+        /// The maximum fee per gas.
+        max_fee_per_gas: u128,
+        /// The calldata.
+        input: Bytes,
+        /// The access list.
+        access_list: AccessList,
+        /// The authorization list.
+        authorization_list: Vec<SignedAuthorization>,
+    },
+}
+
+impl SpanTxData {
+    /// Returns `true` for legacy transactions.
+    pub const fn is_legacy(&self) -> bool {
+        matches!(self, Self::Legacy { .. })
+    }
+
+    fn rlp_encoded_fields_length(&self) -> usize {
+        match self {
+            Self::Legacy { value, gas_price, input } => {
+                value.length() + gas_price.length() + input.length()
+            }
+            Self::Eip2930 { value, gas_price, input, access_list } => {
+                value.length() + gas_price.length() + input.length() + access_list.length()
+            }
+            Self::Eip1559 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+            } => {
+                value.length()
+                    + max_priority_fee_per_gas.length()
+                    + max_fee_per_gas.length()
+                    + input.length()
+                    + access_list.length()
+            }
+            Self::Eip7702 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+                authorization_list,
+            } => {
+                value.length()
+                    + max_priority_fee_per_gas.length()
+                    + max_fee_per_gas.length()
+                    + input.length()
+                    + access_list.length()
+                    + authorization_list.length()
+            }
+        }
+    }
+
+    /// Appends the encoded `tx_data` to `out`.
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Legacy { .. } => {}
+            Self::Eip2930 { .. } => out.put_u8(EIP2930_TX_TYPE_ID),
+            Self::Eip1559 { .. } => out.put_u8(EIP1559_TX_TYPE_ID),
+            Self::Eip7702 { .. } => out.put_u8(EIP7702_TX_TYPE_ID),
+        }
+        Header { list: true, payload_length: self.rlp_encoded_fields_length() }.encode(out);
+        match self {
+            Self::Legacy { value, gas_price, input } => {
+                value.encode(out);
+                gas_price.encode(out);
+                input.encode(out);
+            }
+            Self::Eip2930 { value, gas_price, input, access_list } => {
+                value.encode(out);
+                gas_price.encode(out);
+                input.encode(out);
+                access_list.encode(out);
+            }
+            Self::Eip1559 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+            } => {
+                value.encode(out);
+                max_priority_fee_per_gas.encode(out);
+                max_fee_per_gas.encode(out);
+                input.encode(out);
+                access_list.encode(out);
+            }
+            Self::Eip7702 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+                authorization_list,
+            } => {
+                value.encode(out);
+                max_priority_fee_per_gas.encode(out);
+                max_fee_per_gas.encode(out);
+                input.encode(out);
+                access_list.encode(out);
+                authorization_list.encode(out);
+            }
+        }
+    }
+
+    /// Decodes a `tx_data` from the start of `buf`.
+    ///
+    /// Like any other span batch element, a single `tx_data` may not exceed
+    /// [`MAX_SPAN_BATCH_ELEMENT_COUNT`](super::MAX_SPAN_BATCH_ELEMENT_COUNT) bytes.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, SpanBatchError> {
+        let &first = buf.first().ok_or(SpanBatchError::Truncated)?;
+        // The RLP list header of a legacy transaction starts above `0x7f`.
+        let ty = if first <= 0x7f {
+            *buf = &buf[1..];
+            Some(first)
+        } else {
+            None
+        };
+
+        let mut payload = *buf;
+        let header = Header::decode(&mut payload).map_err(rlp_error)?;
+        if !header.list {
+            return Err(SpanBatchError::InvalidTransactionData(alloy_rlp::Error::UnexpectedString));
+        }
+        let len = (buf.len() - payload.len()).saturating_add(header.payload_length);
+        if len > super::MAX_SPAN_BATCH_ELEMENT_COUNT as usize {
+            return Err(SpanBatchError::TooBigSpanBatch);
+        }
+        if payload.len() < header.payload_length {
+            return Err(SpanBatchError::Truncated);
+        }
+        let (mut fields, rest) = payload.split_at(header.payload_length);
+
+        let data = Self::decode_fields(ty, &mut fields)?;
+        if !fields.is_empty() {
+            return Err(SpanBatchError::InvalidTransactionData(
+                alloy_rlp::Error::ListLengthMismatch {
+                    expected: header.payload_length,
+                    got: header.payload_length - fields.len(),
+                },
+            ));
+        }
+        *buf = rest;
+        Ok(data)
+    }
+
+    fn decode_fields(ty: Option<u8>, buf: &mut &[u8]) -> Result<Self, SpanBatchError> {
+        let data = match ty {
+            None => Self::Legacy {
+                value: Decodable::decode(buf)?,
+                gas_price: Decodable::decode(buf)?,
+                input: Decodable::decode(buf)?,
+            },
+            Some(EIP2930_TX_TYPE_ID) => Self::Eip2930 {
+                value: Decodable::decode(buf)?,
+                gas_price: Decodable::decode(buf)?,
+                input: Decodable::decode(buf)?,
+                access_list: Decodable::decode(buf)?,
+            },
+            Some(EIP1559_TX_TYPE_ID) => Self::Eip1559 {
+                value: Decodable::decode(buf)?,
+                max_priority_fee_per_gas: Decodable::decode(buf)?,
+                max_fee_per_gas: Decodable::decode(buf)?,
+                input: Decodable::decode(buf)?,
+                access_list: Decodable::decode(buf)?,
+            },
+            Some(EIP7702_TX_TYPE_ID) => Self::Eip7702 {
+                value: Decodable::decode(buf)?,
+                max_priority_fee_per_gas: Decodable::decode(buf)?,
+                max_fee_per_gas: Decodable::decode(buf)?,
+                input: Decodable::decode(buf)?,
+                access_list: Decodable::decode(buf)?,
+                authorization_list: Decodable::decode(buf)?,
+            },
+            Some(ty) => return Err(SpanBatchError::UnsupportedTransactionType(ty)),
+        };
+        Ok(data)
+    }
+}
+
+/// Maps an error decoding an RLP header, keeping truncation distinct from malformed data.
+fn rlp_error(error: alloy_rlp::Error) -> SpanBatchError {
+    match error {
+        alloy_rlp::Error::InputTooShort => SpanBatchError::Truncated,
+        error => SpanBatchError::InvalidTransactionData(error),
+    }
+}
+
+/// A transaction of a span batch, with the fields spread over the span batch columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanBatchTransaction {
+    /// The fields encoded in the `tx_data` column.
+    pub data: SpanTxData,
+    /// The recipient, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// The nonce.
+    pub nonce: u64,
+    /// The gas limit.
+    pub gas_limit: u64,
+    /// The signature.
+    pub signature: Signature,
+    /// Whether a legacy transaction is replay protected with [EIP-155]. Always `false` for typed
+    /// transactions, which always commit to the chain ID.
+    ///
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub protected: bool,
+}
+
+impl SpanBatchTransaction {
+    /// Converts a signed transaction of a chain with the given chain ID.
+    ///
+    /// Span batches do not encode the chain ID, so it must match the chain ID of every typed and
+    /// replay protected legacy transaction. Deposits may not be included in batches.
+    pub fn from_signed(
+        tx: &OpTypedTransaction,
+        signature: &Signature,
+        chain_id: u64,
+    ) -> Result<Self, SpanBatchError> {
+        let check_chain_id = |got| {
+            if got == chain_id {
+                Ok(())
+            } else {
+                Err(SpanBatchError::ChainIdMismatch { expected: chain_id, got })
+            }
+        };
+
+        let (data, to, nonce, gas_limit, protected) = match tx {
+            OpTypedTransaction::Legacy(tx) => {
+                if let Some(id) = tx.chain_id {
+                    check_chain_id(id)?;
+                }
+                let data = SpanTxData::Legacy {
+                    value: tx.value,
+                    gas_price: tx.gas_price,
+                    input: tx.input.clone(),
+                };
+                (data, tx.to, tx.nonce, tx.gas_limit, tx.chain_id.is_some())
+            }
+            OpTypedTransaction::Eip2930(tx) => {
+                check_chain_id(tx.chain_id)?;
+                let data = SpanTxData::Eip2930 {
+                    value: tx.value,
+                    gas_price: tx.gas_price,
+                    input: tx.input.clone(),
+                    access_list: tx.access_list.clone(),
+                };
+                (data, tx.to, tx.nonce, tx.gas_limit, false)
+            }
+            OpTypedTransaction::Eip1559(tx) => {
+                check_chain_id(tx.chain_id)?;
+                let data = SpanTxData::Eip1559 {
+                    value: tx.value,
+                    max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+                    max_fee_per_gas: tx.max_fee_per_gas,
+                    input: tx.input.clone(),
+                    access_list: tx.access_list.clone(),
+                };
+                (data, tx.to, tx.nonce, tx.gas_limit, false)
+            }
+            OpTypedTransaction::Eip7702(tx) => {
+                check_chain_id(tx.chain_id)?;
+                let data = SpanTxData::Eip7702 {
+                    value: tx.value,
+                    max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+                    max_fee_per_gas: tx.max_fee_per_gas,
+                    input: tx.input.clone(),
+                    access_list: tx.access_list.clone(),
+                    authorization_list: tx.authorization_list.clone(),
+                };
+                (data, TxKind::Call(tx.to), tx.nonce, tx.gas_limit, false)
+            }
+            OpTypedTransaction::Deposit(_) => return Err(SpanBatchError::DepositTransaction),
+        };
+        Ok(Self { data, to: to.to().copied(), nonce, gas_limit, signature: *signature, protected })
+    }
+
+    /// Converts the transaction back into a signed transaction of a chain with the given chain
+    /// ID.
+    ///
+    /// Replay protected legacy transactions get the chain ID back, which makes their signature
+    /// encode `v` as `y_parity + 35 + 2 * chain_id` again, and unprotected ones as
+    /// `y_parity + 27`.
+    pub fn to_signed(
+        &self,
+        chain_id: u64,
+    ) -> Result<(OpTypedTransaction, Signature), SpanBatchError> {
+        let to = self.to.map_or(TxKind::Create, TxKind::Call);
+        let (nonce, gas_limit) = (self.nonce, self.gas_limit);
+        let tx = match self.data.clone() {
+            SpanTxData::Legacy { value, gas_price, input } => {
+                OpTypedTransaction::Legacy(TxLegacy {
+                    chain_id: self.protected.then_some(chain_id),
+                    nonce,
+                    gas_price,
+                    gas_limit,
+                    to,
+                    value,
+                    input,
+                })
+            }
+            SpanTxData::Eip2930 { value, gas_price, input, access_list } => {
+                OpTypedTransaction::Eip2930(TxEip2930 {
+                    chain_id,
+                    nonce,
+                    gas_price,
+                    gas_limit,
+                    to,
+                    value,
+                    access_list,
+                    input,
+                })
+            }
+            SpanTxData::Eip1559 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+            } => OpTypedTransaction::Eip1559(TxEip1559 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                input,
+            }),
+            SpanTxData::Eip7702 {
+                value,
+                max_priority_fee_per_gas,
+                max_fee_per_gas,
+                input,
+                access_list,
+                authorization_list,
+            } => OpTypedTransaction::Eip7702(TxEip7702 {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to: self.to.ok_or(SpanBatchError::Eip7702ContractCreation)?,
+                value,
+                access_list,
+                authorization_list,
+                input,
+            }),
+        };
+        Ok((tx, self.signature))
+    }
+}
@@ -0,0 +1,415 @@
+//! Reassembly of channels from frames.
+//!
+//! See the [channel bank] in the derivation spec, and the [Holocene changes] to it.
+//!
+//! [channel bank]: https://specs.optimism.io/protocol/derivation.html#channel-bank
+//! [Holocene changes]: https://specs.optimism.io/protocol/holocene/derivation.html
+
+use crate::{
+    ChannelId, DecompressionError, FRAME_OVERHEAD, Frame, compression::decompress_channel_data,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use op_alloy_consensus::OpHardforks;
+
+/// The maximum size of the decompressed data of a channel before Fjord.
+pub const MAX_RLP_BYTES_PER_CHANNEL: usize = 10_000_000;
+
+/// The maximum size of the decompressed data of a channel since Fjord.
+pub const FJORD_MAX_RLP_BYTES_PER_CHANNEL: usize = 100_000_000;
+
+/// Returns the maximum size of the decompressed data of a channel read at the given L1 origin
+/// timestamp.
+///
+/// The same limit bounds the size of the frames of a channel since Holocene.
+pub fn max_rlp_bytes_per_channel(timestamp: u64, hardforks: &impl OpHardforks) -> usize {
+    if hardforks.is_fjord_active_at_timestamp(timestamp) {
+        FJORD_MAX_RLP_BYTES_PER_CHANNEL
+    } else {
+        MAX_RLP_BYTES_PER_CHANNEL
+    }
+}
+
+/// The frame ordering rules a [`Channel`] enforces.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChannelMode {
+    /// Frames may arrive in any order, and frames numbered above a later closing frame are
+    /// pruned.
+    #[default]
+    PreHolocene,
+    /// Frames must arrive in order, starting at `0`, and nothing may follow the closing frame.
+    ///
+    /// Since Holocene there is a single channel at a time: a first frame of another channel
+    /// replaces an unfinished channel, and frames of other channels are otherwise dropped. This
+    /// is left to the caller holding the channel.
+    Holocene,
+}
+
+impl ChannelMode {
+    /// Returns the mode of channels read at the given L1 origin timestamp.
+    pub fn new(timestamp: u64, hardforks: &impl OpHardforks) -> Self {
+        if hardforks.is_holocene_active_at_timestamp(timestamp) {
+            Self::Holocene
+        } else {
+            Self::PreHolocene
+        }
+    }
+}
+
+/// An error adding a frame to, or reading the data of, a [`Channel`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelError {
+    /// The frame belongs to another channel.
+    #[error("frame belongs to another channel")]
+    ChannelIdMismatch,
+    /// The channel is already closed.
+    #[error("channel is closed")]
+    Closed,
+    /// A frame with the same number was already added.
+    #[error("duplicate frame {0}")]
+    DuplicateFrame(u16),
+    /// The frame number is not below the number of the closing frame.
+    #[error("frame {number} is past the closing frame {last}")]
+    PastClosingFrame {
+        /// The number of the frame.
+        number: u16,
+        /// The number of the closing frame.
+        last: u16,
+    },
+    /// The frame is not the next frame of the channel, since Holocene.
+    #[error("out of order frame: expected {expected}, got {got}")]
+    OutOfOrderFrame {
+        /// The number of the next frame.
+        expected: u16,
+        /// The number of the frame.
+        got: u16,
+    },
+    /// The channel is not closed, or is missing frames.
+    #[error("channel is not ready")]
+    NotReady,
+    /// The channel data failed to decompress.
+    #[error(transparent)]
+    Decompression(#[from] DecompressionError),
+}
+
+/// A channel being reassembled from its frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    /// The channel id.
+    id: ChannelId,
+    /// The number of the L1 block the first frame was included in.
+    open_block: u64,
+    /// The frame ordering rules.
+    mode: ChannelMode,
+    /// The frames received so far, by number.
+    frames: BTreeMap<u16, Frame>,
+    /// The number of the closing frame, once received.
+    last_frame: Option<u16>,
+    /// The highest frame number received so far.
+    highest_frame: u16,
+    /// The total size of the frames, including the frame overhead.
+    size: usize,
+}
+
+impl Channel {
+    /// Creates an empty channel, opened in the given L1 block.
+    pub const fn new(id: ChannelId, open_block: u64, mode: ChannelMode) -> Self {
+        Self {
+            id,
+            open_block,
+            mode,
+            frames: BTreeMap::new(),
+            last_frame: None,
+            highest_frame: 0,
+            size: 0,
+        }
+    }
+
+    /// Returns the channel id.
+    pub const fn id(&self) -> ChannelId {
+        self.id
+    }
+
+    /// Returns the number of the L1 block the channel was opened in.
+    pub const fn open_block(&self) -> u64 {
+        self.open_block
+    }
+
+    /// Returns the frame ordering rules of the channel.
+    pub const fn mode(&self) -> ChannelMode {
+        self.mode
+    }
+
+    /// Returns the total size of the frames of the channel, including the frame overhead.
+    ///
+    /// Like op-node, frames received before a closing frame that prunes frames past it are no
+    /// longer counted, see [`Channel::add_frame`].
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of frames in the channel.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if the channel has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns `true` if the closing frame was received.
+    pub const fn is_closed(&self) -> bool {
+        self.last_frame.is_some()
+    }
+
+    /// Returns `true` if the channel is closed and all of its frames were received.
+    pub fn is_ready(&self) -> bool {
+        self.last_frame.is_some_and(|last| self.frames.len() == last as usize + 1)
+    }
+
+    /// Returns `true` if the channel timed out at the given L1 block, that is if more than
+    /// `channel_timeout` blocks passed since it was opened.
+    pub const fn is_timed_out(&self, l1_block: u64, channel_timeout: u64) -> bool {
+        self.open_block.saturating_add(channel_timeout) < l1_block
+    }
+
+    /// Returns `true` if the frames of the channel exceed `max_size`.
+    ///
+    /// Since Holocene, channels exceeding [`max_rlp_bytes_per_channel`] are dropped.
+    pub const fn is_oversized(&self, max_size: usize) -> bool {
+        self.size > max_size
+    }
+
+    /// Adds a frame to the channel.
+    ///
+    /// Rejected frames leave the channel unchanged, and are dropped by the derivation pipeline.
+    pub fn add_frame(&mut self, frame: Frame) -> Result<(), ChannelError> {
+        if frame.id != self.id {
+            return Err(ChannelError::ChannelIdMismatch);
+        }
+        match self.mode {
+            ChannelMode::PreHolocene => {
+                if frame.is_last && self.is_closed() {
+                    return Err(ChannelError::Closed);
+                }
+                if self.frames.contains_key(&frame.number) {
+                    return Err(ChannelError::DuplicateFrame(frame.number));
+                }
+                if let Some(last) = self.last_frame.filter(|&last| frame.number >= last) {
+                    return Err(ChannelError::PastClosingFrame { number: frame.number, last });
+                }
+            }
+            ChannelMode::Holocene => {
+                if self.is_closed() {
+                    return Err(ChannelError::Closed);
+                }
+                // Compared as `usize` so that a full channel does not wrap back to frame `0`.
+                let expected = self.frames.len();
+                if frame.number as usize != expected {
+                    return Err(ChannelError::OutOfOrderFrame {
+                        expected: expected as u16,
+                        got: frame.number,
+                    });
+                }
+            }
+        }
+
+        if frame.is_last {
+            self.last_frame = Some(frame.number);
+            // Frames after the closing frame can only exist before Holocene, and are pruned.
+            // op-node subtracts the size of every stored frame while pruning, not only of the
+            // pruned ones, and the channel bank prunes channels by that size, so it is matched.
+            if frame.number < self.highest_frame {
+                self.frames.split_off(&frame.number);
+                self.size = 0;
+                self.highest_frame = frame.number;
+            }
+        }
+        self.highest_frame = self.highest_frame.max(frame.number);
+        self.size += FRAME_OVERHEAD + frame.data.len();
+        self.frames.insert(frame.number, frame);
+        Ok(())
+    }
+
+    /// Returns the concatenated data of the frames, if the channel is ready.
+    pub fn frame_data(&self) -> Option<Vec<u8>> {
+        if !self.is_ready() {
+            return None;
+        }
+        let mut data = Vec::with_capacity(self.frames.values().map(|frame| frame.data.len()).sum());
+        for frame in self.frames.values() {
+            data.extend_from_slice(&frame.data);
+        }
+        Some(data)
+    }
+
+    /// Returns the decompressed data of the channel, read at the given L1 origin timestamp.
+    ///
+    /// The data is capped at [`max_rlp_bytes_per_channel`], see [`decompress_channel_data`].
+    pub fn decompress(
+        &self,
+        timestamp: u64,
+        hardforks: &impl OpHardforks,
+    ) -> Result<Vec<u8>, ChannelError> {
+        let data = self.frame_data().ok_or(ChannelError::NotReady)?;
+        Ok(decompress_channel_data(
+            &data,
+            max_rlp_bytes_per_channel(timestamp, hardforks),
+            hardforks.is_fjord_active_at_timestamp(timestamp),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CHANNEL_ID_LENGTH;
+    use alloc::vec;
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+    use op_alloy_consensus::{ForkCondition, OpHardfork, OpHardforkSchedule};
+
+    const ID: ChannelId = [0x11; CHANNEL_ID_LENGTH];
+
+    fn schedule(fjord_time: u64, holocene_time: u64) -> OpHardforkSchedule {
+        let forks = OpHardfork::ALL.into_iter().take_while(|fork| *fork != OpHardfork::Isthmus);
+        OpHardforkSchedule::new(forks.map(|fork| {
+            let condition = match fork {
+                OpHardfork::Bedrock => ForkCondition::Block(0),
+                OpHardfork::Fjord | OpHardfork::Granite => ForkCondition::Timestamp(fjord_time),
+                OpHardfork::Holocene => ForkCondition::Timestamp(holocene_time),
+                _ => ForkCondition::Timestamp(0),
+            };
+            (fork, condition)
+        }))
+        .unwrap()
+    }
+
+    fn frame(number: u16, data: &[u8], is_last: bool) -> Frame {
+        Frame::new(ID, number, data.to_vec(), is_last)
+    }
+
+    #[test]
+    fn test_pre_holocene_out_of_order() {
+        let mut channel = Channel::new(ID, 10, ChannelMode::PreHolocene);
+        channel.add_frame(frame(2, b"c", true)).unwrap();
+        channel.add_frame(frame(0, b"a", false)).unwrap();
+        assert!(channel.is_closed());
+        assert!(!channel.is_ready());
+        assert_eq!(channel.frame_data(), None);
+
+        assert_eq!(channel.add_frame(frame(0, b"x", false)), Err(ChannelError::DuplicateFrame(0)));
+        assert_eq!(channel.add_frame(frame(1, b"x", true)), Err(ChannelError::Closed));
+        assert_eq!(
+            channel.add_frame(frame(3, b"x", false)),
+            Err(ChannelError::PastClosingFrame { number: 3, last: 2 })
+        );
+        assert_eq!(
+            channel.add_frame(Frame::new([0; CHANNEL_ID_LENGTH], 1, vec![], false)),
+            Err(ChannelError::ChannelIdMismatch)
+        );
+
+        channel.add_frame(frame(1, b"b", false)).unwrap();
+        assert!(channel.is_ready());
+        assert_eq!(channel.len(), 3);
+        assert_eq!(channel.size(), 3 * (FRAME_OVERHEAD + 1));
+        assert_eq!(channel.frame_data(), Some(b"abc".to_vec()));
+    }
+
+    #[test]
+    fn test_pre_holocene_prunes_past_closing_frame() {
+        let mut channel = Channel::new(ID, 10, ChannelMode::PreHolocene);
+        channel.add_frame(frame(0, b"a", false)).unwrap();
+        channel.add_frame(frame(3, b"dd", false)).unwrap();
+        channel.add_frame(frame(2, b"ccc", false)).unwrap();
+        channel.add_frame(frame(1, b"b", true)).unwrap();
+
+        assert!(channel.is_ready());
+        // Only the closing frame is counted after pruning, as in op-node.
+        assert_eq!(channel.size(), FRAME_OVERHEAD + 1);
+        assert_eq!(channel.frame_data(), Some(b"ab".to_vec()));
+    }
+
+    #[test]
+    fn test_pre_holocene_prunes_out_of_order_frames() {
+        let mut channel = Channel::new(ID, 10, ChannelMode::PreHolocene);
+        channel.add_frame(frame(3, b"dd", false)).unwrap();
+        channel.add_frame(frame(0, b"a", false)).unwrap();
+        assert_eq!(channel.size(), 2 * FRAME_OVERHEAD + 3);
+
+        channel.add_frame(frame(2, b"c", true)).unwrap();
+        assert!(!channel.is_ready());
+        assert_eq!(channel.len(), 2);
+        assert_eq!(channel.size(), FRAME_OVERHEAD + 1);
+
+        channel.add_frame(frame(1, b"bb", false)).unwrap();
+        assert!(channel.is_ready());
+        assert_eq!(channel.size(), 2 * FRAME_OVERHEAD + 3);
+        assert_eq!(channel.frame_data(), Some(b"abbc".to_vec()));
+    }
+
+    #[test]
+    fn test_holocene_strict_ordering() {
+        let mut channel = Channel::new(ID, 10, ChannelMode::Holocene);
+        assert_eq!(
+            channel.add_frame(frame(1, b"b", false)),
+            Err(ChannelError::OutOfOrderFrame { expected: 0, got: 1 })
+        );
+        channel.add_frame(frame(0, b"a", false)).unwrap();
+        assert_eq!(
+            channel.add_frame(frame(0, b"a", false)),
+            Err(ChannelError::OutOfOrderFrame { expected: 1, got: 0 })
+        );
+        assert_eq!(
+            channel.add_frame(frame(2, b"c", true)),
+            Err(ChannelError::OutOfOrderFrame { expected: 1, got: 2 })
+        );
+        channel.add_frame(frame(1, b"b", true)).unwrap();
+        assert!(channel.is_ready());
+        assert_eq!(channel.add_frame(frame(2, b"c", false)), Err(ChannelError::Closed));
+        assert_eq!(channel.frame_data(), Some(b"ab".to_vec()));
+    }
+
+    #[test]
+    fn test_channel_timeout_and_size() {
+        let mut channel = Channel::new(ID, 100, ChannelMode::Holocene);
+        assert!(!channel.is_timed_out(150, 50));
+        assert!(channel.is_timed_out(151, 50));
+        assert!(!Channel::new(ID, u64::MAX, ChannelMode::Holocene).is_timed_out(u64::MAX, 50));
+
+        channel.add_frame(frame(0, &[0; 100], false)).unwrap();
+        assert!(!channel.is_oversized(FRAME_OVERHEAD + 100));
+        channel.add_frame(frame(1, &[0], false)).unwrap();
+        assert!(channel.is_oversized(FRAME_OVERHEAD + 100));
+    }
+
+    #[test]
+    fn test_channel_mode_and_limits() {
+        let schedule = schedule(100, 200);
+        assert_eq!(ChannelMode::new(199, &schedule), ChannelMode::PreHolocene);
+        assert_eq!(ChannelMode::new(200, &schedule), ChannelMode::Holocene);
+        assert_eq!(max_rlp_bytes_per_channel(99, &schedule), MAX_RLP_BYTES_PER_CHANNEL);
+        assert_eq!(max_rlp_bytes_per_channel(100, &schedule), FJORD_MAX_RLP_BYTES_PER_CHANNEL);
+    }
+
+    #[test]
+    fn test_channel_decompress() {
+        let schedule = schedule(100, 200);
+        let data = b"batch data".repeat(10);
+        let compressed = compress_to_vec_zlib(&data, 6);
+        let (head, tail) = compressed.split_at(compressed.len() / 2);
+
+        let mut channel = Channel::new(ID, 10, ChannelMode::PreHolocene);
+        channel.add_frame(frame(0, head, false)).unwrap();
+        assert_eq!(channel.decompress(0, &schedule), Err(ChannelError::NotReady));
+        channel.add_frame(frame(1, tail, true)).unwrap();
+        assert_eq!(channel.decompress(0, &schedule), Ok(data.clone()));
+        assert_eq!(channel.decompress(100, &schedule), Ok(data));
+
+        let mut channel = Channel::new(ID, 10, ChannelMode::PreHolocene);
+        channel.add_frame(frame(0, &[1, 2, 3], true)).unwrap();
+        assert_eq!(
+            channel.decompress(0, &schedule),
+            Err(ChannelError::Decompression(DecompressionError::BrotliBeforeFjord))
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Decompression of channel data.
+//!
+//! See the [channel format] in the derivation spec.
+//!
+//! [channel format]: https://specs.optimism.io/protocol/derivation.html#channel-format
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use brotli_decompressor::{
+    Allocator, BrotliDecompressStream, BrotliResult, BrotliState, SliceWrapper, SliceWrapperMut,
+};
+use miniz_oxide::inflate::{TINFLStatus, decompress_to_vec_zlib_with_limit};
+
+/// The zlib compression method (`CM`) for deflate.
+pub const ZLIB_DEFLATE_COMPRESSION_METHOD: u8 = 8;
+
+/// The reserved zlib compression method (`CM`), which also marks zlib channel data.
+pub const ZLIB_RESERVED_COMPRESSION_METHOD: u8 = 15;
+
+/// The channel version byte of brotli compressed channel data, accepted since Fjord.
+pub const CHANNEL_VERSION_BROTLI: u8 = 1;
+
+/// The size of the chunks the brotli output buffer grows by.
+const BROTLI_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The compression algorithm of channel data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressionAlgo {
+    /// A zlib stream, identified by the compression method in its first byte.
+    Zlib,
+    /// A brotli stream following the [`CHANNEL_VERSION_BROTLI`] version byte.
+    Brotli,
+}
+
+impl CompressionAlgo {
+    /// Identifies the compression algorithm from the first byte of channel data.
+    pub const fn from_version_byte(byte: u8) -> Option<Self> {
+        match byte {
+            CHANNEL_VERSION_BROTLI => Some(Self::Brotli),
+            _ if matches!(
+                byte & 0x0f,
+                ZLIB_DEFLATE_COMPRESSION_METHOD | ZLIB_RESERVED_COMPRESSION_METHOD
+            ) =>
+            {
+                Some(Self::Zlib)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An error decompressing channel data.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum DecompressionError {
+    /// The channel data is empty.
+    #[error("empty channel data")]
+    Empty,
+    /// The first byte of the channel data does not identify a known compression algorithm.
+    #[error("unknown channel compression type {0:#04x}")]
+    UnknownCompressionType(u8),
+    /// Brotli compressed channel data before Fjord.
+    #[error("brotli compressed channel data before Fjord")]
+    BrotliBeforeFjord,
+    /// The zlib stream is truncated or corrupt.
+    #[error("invalid zlib stream")]
+    Zlib,
+    /// The brotli stream is truncated or corrupt.
+    #[error("invalid brotli stream")]
+    Brotli,
+}
+
+/// Decompresses channel data, returning at most `max_len` bytes.
+///
+/// Zlib streams are accepted at any time, brotli streams only once Fjord is active. Output beyond
+/// `max_len` is discarded rather than rejected: per the spec, the batches within the limit are
+/// still derived, and bounding the output protects against zip bombs.
+pub fn decompress_channel_data(
+    data: &[u8],
+    max_len: usize,
+    fjord_active: bool,
+) -> Result<Vec<u8>, DecompressionError> {
+    let &version = data.first().ok_or(DecompressionError::Empty)?;
+    match CompressionAlgo::from_version_byte(version) {
+        Some(CompressionAlgo::Zlib) => decompress_zlib(data, max_len),
+        Some(CompressionAlgo::Brotli) if fjord_active => decompress_brotli(&data[1..], max_len),
+        Some(CompressionAlgo::Brotli) => Err(DecompressionError::BrotliBeforeFjord),
+        None => Err(DecompressionError::UnknownCompressionType(version)),
+    }
+}
+
+fn decompress_zlib(data: &[u8], max_len: usize) -> Result<Vec<u8>, DecompressionError> {
+    match decompress_to_vec_zlib_with_limit(data, max_len) {
+        Ok(output) => Ok(output),
+        Err(err) if err.status == TINFLStatus::HasMoreOutput => Ok(err.output),
+        Err(_) => Err(DecompressionError::Zlib),
+    }
+}
+
+fn decompress_brotli(data: &[u8], max_len: usize) -> Result<Vec<u8>, DecompressionError> {
+    // Large windows are not part of RFC 7932 and would let a stream request a 1 GiB ring buffer.
+    let mut state = BrotliState::new_strict(HeapAlloc, HeapAlloc, HeapAlloc);
+    let mut output = Vec::new();
+    let (mut available_in, mut input_offset, mut total_out) = (data.len(), 0, 0);
+
+    loop {
+        let written = output.len();
+        if written == max_len {
+            return Ok(output);
+        }
+        output.resize(written + BROTLI_CHUNK_SIZE.min(max_len - written), 0);
+        let (mut available_out, mut output_offset) = (output.len() - written, written);
+
+        let result = BrotliDecompressStream(
+            &mut available_in,
+            &mut input_offset,
+            data,
+            &mut available_out,
+            &mut output_offset,
+            &mut output,
+            &mut total_out,
+            &mut state,
+        );
+        output.truncate(output_offset);
+        match result {
+            BrotliResult::ResultSuccess => return Ok(output),
+            BrotliResult::NeedsMoreOutput => {}
+            BrotliResult::NeedsMoreInput | BrotliResult::ResultFailure => {
+                return Err(DecompressionError::Brotli);
+            }
+        }
+    }
+}
+
+/// A heap allocator for the brotli decoder, which does not require `std`.
+#[derive(Debug, Clone, Copy)]
+struct HeapAlloc;
+
+/// Memory allocated by [`HeapAlloc`].
+#[derive(Debug)]
+struct HeapCell<T>(Box<[T]>);
+
+impl<T> Default for HeapCell<T> {
+    fn default() -> Self {
+        Self(Box::default())
+    }
+}
+
+impl<T> SliceWrapper<T> for HeapCell<T> {
+    fn slice(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> SliceWrapperMut<T> for HeapCell<T> {
+    fn slice_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T: Clone + Default> Allocator<T> for HeapAlloc {
+    type AllocatedMemory = HeapCell<T>;
+
+    fn alloc_cell(&mut self, len: usize) -> Self::AllocatedMemory {
+        HeapCell(vec![T::default(); len].into_boxed_slice())
+    }
+
+    fn free_cell(&mut self, _cell: Self::AllocatedMemory) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+
+    fn brotli(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![CHANNEL_VERSION_BROTLI];
+        brotli::BrotliCompress(&mut &data[..], &mut out, &Default::default()).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_compression_algo() {
+        assert_eq!(CompressionAlgo::from_version_byte(0x78), Some(CompressionAlgo::Zlib));
+        assert_eq!(CompressionAlgo::from_version_byte(0x0f), Some(CompressionAlgo::Zlib));
+        assert_eq!(CompressionAlgo::from_version_byte(0x01), Some(CompressionAlgo::Brotli));
+        assert_eq!(CompressionAlgo::from_version_byte(0x00), None);
+        assert_eq!(CompressionAlgo::from_version_byte(0x02), None);
+    }
+
+    #[test]
+    fn test_decompress_zlib() {
+        let data = b"channel data".repeat(100);
+        let compressed = compress_to_vec_zlib(&data, 6);
+        assert_eq!(decompress_channel_data(&compressed, usize::MAX, false), Ok(data.clone()));
+        assert_eq!(decompress_channel_data(&compressed, usize::MAX, true), Ok(data));
+        assert_eq!(
+            decompress_channel_data(&compressed[..compressed.len() / 2], usize::MAX, false),
+            Err(DecompressionError::Zlib)
+        );
+    }
+
+    #[test]
+    fn test_decompress_brotli() {
+        let data = b"channel data".repeat(100);
+        let compressed = brotli(&data);
+        assert_eq!(decompress_channel_data(&compressed, usize::MAX, true), Ok(data));
+        assert_eq!(
+            decompress_channel_data(&compressed, usize::MAX, false),
+            Err(DecompressionError::BrotliBeforeFjord)
+        );
+        assert_eq!(
+            decompress_channel_data(&compressed[..compressed.len() / 2], usize::MAX, true),
+            Err(DecompressionError::Brotli)
+        );
+    }
+
+    #[test]
+    fn test_decompress_invalid() {
+        assert_eq!(decompress_channel_data(&[], 100, true), Err(DecompressionError::Empty));
+        assert_eq!(
+            decompress_channel_data(&[0x02, 0xff], 100, true),
+            Err(DecompressionError::UnknownCompressionType(0x02))
+        );
+    }
+
+    #[test]
+    fn test_decompress_limit() {
+        // A few kilobytes that inflate to far more than the limit.
+        let data = vec![0u8; 4 * 1024 * 1024];
+        let max_len = 1024 * 1024 + 1;
+
+        let compressed = compress_to_vec_zlib(&data, 9);
+        assert!(compressed.len() < 16 * 1024);
+        assert_eq!(
+            decompress_channel_data(&compressed, max_len, false),
+            Ok(data[..max_len].to_vec())
+        );
+
+        let compressed = brotli(&data);
+        assert!(compressed.len() < 16 * 1024);
+        assert_eq!(
+            decompress_channel_data(&compressed, max_len, true),
+            Ok(data[..max_len].to_vec())
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Channel frames carried by batcher transactions.
+//!
+//! See the [frame format] in the derivation spec.
+//!
+//! [frame format]: https://specs.optimism.io/protocol/derivation.html#frame-format
+
+use alloc::vec::Vec;
+
+/// The only supported version of the batcher transaction payload.
+pub const DERIVATION_VERSION_0: u8 = 0;
+
+/// The length of a [`ChannelId`] in bytes.
+pub const CHANNEL_ID_LENGTH: usize = 16;
+
+/// The number of bytes a frame adds on top of its data: the channel id, the frame number, the data
+/// length and the `is_last` flag.
+pub const FRAME_OVERHEAD: usize = CHANNEL_ID_LENGTH + 2 + 4 + 1;
+
+/// The maximum length of the data carried by a single frame.
+pub const MAX_FRAME_LEN: usize = 1_000_000;
+
+/// An identifier that groups the frames of a channel.
+pub type ChannelId = [u8; CHANNEL_ID_LENGTH];
+
+/// An error decoding a single [`Frame`].
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDecodingError {
+    /// The input ended before the frame did.
+    #[error("frame truncated: expected {expected} bytes, got {got}")]
+    Truncated {
+        /// The number of bytes required to decode the frame.
+        expected: usize,
+        /// The number of bytes available.
+        got: usize,
+    },
+    /// The frame data is longer than [`MAX_FRAME_LEN`].
+    #[error("frame data length {0} exceeds the maximum of {MAX_FRAME_LEN}")]
+    DataTooLarge(usize),
+    /// The `is_last` flag is neither `0` nor `1`.
+    #[error("invalid is_last flag {0}")]
+    InvalidIsLastFlag(u8),
+}
+
+/// An error parsing the [`Frame`]s of a batcher transaction.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+pub enum FrameParseError {
+    /// The batcher transaction carries no data, not even a version byte.
+    #[error("empty batcher transaction data")]
+    Empty,
+    /// The version byte is not [`DERIVATION_VERSION_0`].
+    #[error("unsupported derivation version {0}")]
+    UnsupportedVersion(u8),
+    /// The payload after the version byte contains no frames.
+    #[error("no frames in batcher transaction")]
+    NoFrames,
+    /// One of the frames failed to decode.
+    #[error("invalid frame at index {index}: {source}")]
+    InvalidFrame {
+        /// The index of the frame within the transaction.
+        index: usize,
+        /// The decoding error.
+        source: FrameDecodingError,
+    },
+}
+
+/// A frame of channel data, as posted in a batcher transaction.
+///
+/// Encoded as `channel_id ++ frame_number ++ frame_data_length ++ frame_data ++ is_last`, with the
+/// integers in big-endian.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Frame {
+    /// The channel this frame belongs to.
+    pub id: ChannelId,
+    /// The index of this frame within the channel.
+    pub number: u16,
+    /// The channel data carried by this frame.
+    pub data: Vec<u8>,
+    /// Whether this is the last frame of the channel.
+    pub is_last: bool,
+}
+
+impl Frame {
+    /// Creates a new [`Frame`].
+    pub const fn new(id: ChannelId, number: u16, data: Vec<u8>, is_last: bool) -> Self {
+        Self { id, number, data, is_last }
+    }
+
+    /// Returns the encoded length of the frame.
+    pub fn size(&self) -> usize {
+        FRAME_OVERHEAD + self.data.len()
+    }
+
+    /// Appends the encoded frame to `out`.
+    ///
+    /// This does not check the data length against [`MAX_FRAME_LEN`], callers building batcher
+    /// transactions are expected to split channel data into frames of a valid size.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.reserve(self.size());
+        out.extend_from_slice(&self.id);
+        out.extend_from_slice(&self.number.to_be_bytes());
+        out.extend_from_slice(&(self.data.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.data);
+        out.push(self.is_last as u8);
+    }
+
+    /// Decodes a frame from the start of `buf`, returning it along with the number of bytes read.
+    pub fn decode(buf: &[u8]) -> Result<(usize, Self), FrameDecodingError> {
+        let truncated = |expected| FrameDecodingError::Truncated { expected, got: buf.len() };
+
+        let header_len = FRAME_OVERHEAD - 1;
+        if buf.len() < header_len {
+            return Err(truncated(header_len));
+        }
+        let (id, rest) = buf.split_at(CHANNEL_ID_LENGTH);
+        let (number, rest) = rest.split_at(2);
+        let (data_len, rest) = rest.split_at(4);
+
+        let data_len = u32::from_be_bytes(data_len.try_into().unwrap()) as usize;
+        if data_len > MAX_FRAME_LEN {
+            return Err(FrameDecodingError::DataTooLarge(data_len));
+        }
+        let size = FRAME_OVERHEAD + data_len;
+        if buf.len() < size {
+            return Err(truncated(size));
+        }
+        let is_last = match rest[data_len] {
+            0 => false,
+            1 => true,
+            flag => return Err(FrameDecodingError::InvalidIsLastFlag(flag)),
+        };
+
+        let frame = Self {
+            id: id.try_into().unwrap(),
+            number: u16::from_be_bytes(number.try_into().unwrap()),
+            data: rest[..data_len].to_vec(),
+            is_last,
+        };
+        Ok((size, frame))
+    }
+
+    /// Encodes `frames` as the data of a batcher transaction, prefixed with
+    /// [`DERIVATION_VERSION_0`].
+    pub fn encode_frames(frames: &[Self]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + frames.iter().map(Self::size).sum::<usize>());
+        out.push(DERIVATION_VERSION_0);
+        for frame in frames {
+            frame.encode(&mut out);
+        }
+        out
+    }
+
+    /// Parses the frames from the data of a batcher transaction.
+    ///
+    /// Per the spec, the transaction is rejected as a whole if any of its frames is invalid or if
+    /// there are trailing bytes after the last frame.
+    pub fn parse_frames(data: &[u8]) -> Result<Vec<Self>, FrameParseError> {
+        let (&version, mut rest) = data.split_first().ok_or(FrameParseError::Empty)?;
+        if version != DERIVATION_VERSION_0 {
+            return Err(FrameParseError::UnsupportedVersion(version));
+        }
+        if rest.is_empty() {
+            return Err(FrameParseError::NoFrames);
+        }
+
+        let mut frames = Vec::new();
+        while !rest.is_empty() {
+            let (size, frame) = Self::decode(rest)
+                .map_err(|source| FrameParseError::InvalidFrame { index: frames.len(), source })?;
+            frames.push(frame);
+            rest = &rest[size..];
+        }
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn frame(number: u16, data: &[u8], is_last: bool) -> Frame {
+        Frame::new([0xaa; CHANNEL_ID_LENGTH], number, data.to_vec(), is_last)
+    }
+
+    #[test]
+    fn test_frame_encoding() {
+        let frame = frame(0x0102, &[0xde, 0xad], true);
+        let mut encoded = Vec::new();
+        frame.encode(&mut encoded);
+
+        let mut expected = vec![0xaa; CHANNEL_ID_LENGTH];
+        expected.extend_from_slice(&[0x01, 0x02, 0, 0, 0, 2, 0xde, 0xad, 1]);
+        assert_eq!(encoded, expected);
+        assert_eq!(encoded.len(), frame.size());
+        assert_eq!(Frame::decode(&encoded), Ok((frame.size(), frame)));
+    }
+
+    #[test]
+    fn test_parse_frames() {
+        let frames = vec![frame(0, b"hello", false), frame(1, &[], false), frame(2, b"!", true)];
+        let data = Frame::encode_frames(&frames);
+        assert_eq!(data[0], DERIVATION_VERSION_0);
+        assert_eq!(Frame::parse_frames(&data), Ok(frames));
+    }
+
+    #[test]
+    fn test_parse_frames_invalid_payload() {
+        assert_eq!(Frame::parse_frames(&[]), Err(FrameParseError::Empty));
+        assert_eq!(Frame::parse_frames(&[DERIVATION_VERSION_0]), Err(FrameParseError::NoFrames));
+
+        let mut data = Frame::encode_frames(&[frame(0, b"data", true)]);
+        data[0] = 1;
+        assert_eq!(Frame::parse_frames(&data), Err(FrameParseError::UnsupportedVersion(1)));
+
+        // A single bad frame rejects the whole transaction.
+        let mut data = Frame::encode_frames(&[frame(0, b"data", false), frame(1, b"data", true)]);
+        *data.last_mut().unwrap() = 2;
+        assert_eq!(
+            Frame::parse_frames(&data),
+            Err(FrameParseError::InvalidFrame {
+                index: 1,
+                source: FrameDecodingError::InvalidIsLastFlag(2)
+            })
+        );
+
+        // Trailing bytes that do not form a frame.
+        let mut data = Frame::encode_frames(&[frame(0, b"data", true)]);
+        data.push(0);
+        assert_eq!(
+            Frame::parse_frames(&data),
+            Err(FrameParseError::InvalidFrame {
+                index: 1,
+                source: FrameDecodingError::Truncated { expected: FRAME_OVERHEAD - 1, got: 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_frame_limits() {
+        let mut encoded = Vec::new();
+        frame(0, b"data", true).encode(&mut encoded);
+
+        assert_eq!(
+            Frame::decode(&encoded[..FRAME_OVERHEAD - 2]),
+            Err(FrameDecodingError::Truncated {
+                expected: FRAME_OVERHEAD - 1,
+                got: FRAME_OVERHEAD - 2
+            })
+        );
+        assert_eq!(
+            Frame::decode(&encoded[..encoded.len() - 1]),
+            Err(FrameDecodingError::Truncated { expected: encoded.len(), got: encoded.len() - 1 })
+        );
+
+        // The declared data length is checked before the data is read.
+        let len = (MAX_FRAME_LEN as u32 + 1).to_be_bytes();
+        encoded[CHANNEL_ID_LENGTH + 2..CHANNEL_ID_LENGTH + 6].copy_from_slice(&len);
+        assert_eq!(
+            Frame::decode(&encoded),
+            Err(FrameDecodingError::DataTooLarge(MAX_FRAME_LEN + 1))
+        );
+
+        let data = vec![0; MAX_FRAME_LEN];
+        let max = frame(0, &data, true);
+        let mut encoded = Vec::new();
+        max.encode(&mut encoded);
+        assert_eq!(Frame::decode(&encoded), Ok((max.size(), max)));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_frames_roundtrip() {
+        use arbitrary::Arbitrary;
+        use rand::Rng;
+
+        let mut bytes = [0u8; 4096];
+        rand::rng().fill(bytes.as_mut_slice());
+        let mut u = arbitrary::Unstructured::new(&bytes);
+        for _ in 0..8 {
+            let frames = Vec::<Frame>::arbitrary(&mut u).unwrap();
+            let data = Frame::encode_frames(&frames);
+            if frames.is_empty() {
+                assert_eq!(Frame::parse_frames(&data), Err(FrameParseError::NoFrames));
+            } else {
+                assert_eq!(Frame::parse_frames(&data).unwrap(), frames);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary_corrupted_frames() {
+        use arbitrary::Arbitrary;
+        use rand::Rng;
+
+        let mut bytes = [0u8; 4096];
+        rand::rng().fill(bytes.as_mut_slice());
+        let mut u = arbitrary::Unstructured::new(&bytes);
+        for _ in 0..64 {
+            let mut data = Frame::encode_frames(&Vec::<Frame>::arbitrary(&mut u).unwrap());
+            let index = u.choose_index(data.len()).unwrap();
+            if bool::arbitrary(&mut u).unwrap() {
+                data[index] = u8::arbitrary(&mut u).unwrap();
+            } else {
+                data.truncate(index);
+            }
+            // Parsing is strict, so any accepted input is the canonical encoding of its frames.
+            if let Ok(frames) = Frame::parse_frames(&data) {
+                assert_eq!(Frame::encode_frames(&frames), data);
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+#![doc = include_str!("../README.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/alloy.jpg",
+    html_favicon_url = "https://raw.githubusercontent.com/alloy-rs/core/main/assets/favicon.ico"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod frame;
+pub use frame::{
+    CHANNEL_ID_LENGTH, ChannelId, DERIVATION_VERSION_0, FRAME_OVERHEAD, Frame, FrameDecodingError,
+    FrameParseError, MAX_FRAME_LEN,
+};
+
+pub mod compression;
+pub use compression::{
+    CHANNEL_VERSION_BROTLI, CompressionAlgo, DecompressionError, ZLIB_DEFLATE_COMPRESSION_METHOD,
+    ZLIB_RESERVED_COMPRESSION_METHOD, decompress_channel_data,
+};
+
+pub mod channel;
+pub use channel::{
+    Channel, ChannelError, ChannelMode, FJORD_MAX_RLP_BYTES_PER_CHANNEL, MAX_RLP_BYTES_PER_CHANNEL,
+    max_rlp_bytes_per_channel,
+};
+
+pub mod batch;
+pub use batch::{
+    BatchDecodingError, BatchTransactionError, MAX_SPAN_BATCH_ELEMENT_COUNT, RawSpanBatch,
+    RawSpanBatchBlock, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE, SingleBatch, SpanBatch,
+    SpanBatchElement, SpanBatchError, SpanBatchTransaction, SpanTxData,
+};
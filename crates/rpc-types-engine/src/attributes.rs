@@ -11,6 +11,7 @@ use alloy_rlp::Result;
 use alloy_rpc_types_engine::PayloadAttributes;
 use op_alloy_consensus::{
     EIP1559ParamError, OpTxEnvelope, decode_eip_1559_params, encode_holocene_extra_data,
+    encode_jovian_extra_data,
 };
 
 /// Optimism Payload Attributes
@@ -39,6 +40,18 @@ pub struct OpPayloadAttributes {
     /// Prior to Holocene activation, this field should always be [None].
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub eip_1559_params: Option<B64>,
+    /// If set, this sets the minimum base fee for the block.
+    ///
+    /// Prior to Jovian activation, this field should always be [None].
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::quantity::opt"
+        )
+    )]
+    pub min_base_fee: Option<u64>,
 }
 
 impl OpPayloadAttributes {
@@ -52,6 +65,16 @@ impl OpPayloadAttributes {
             .ok_or(EIP1559ParamError::NoEIP1559Params)?
     }
 
+    /// Encodes the `eip1559` parameters and the minimum base fee for the payload after Jovian.
+    pub fn get_jovian_extra_data(
+        &self,
+        default_base_fee_params: BaseFeeParams,
+    ) -> Result<Bytes, EIP1559ParamError> {
+        let eip_1559_params = self.eip_1559_params.ok_or(EIP1559ParamError::NoEIP1559Params)?;
+        let min_base_fee = self.min_base_fee.ok_or(EIP1559ParamError::MinBaseFeeNotSet)?;
+        encode_jovian_extra_data(eip_1559_params, default_base_fee_params, min_base_fee)
+    }
+
     /// Extracts the Holocene 1599 parameters from the encoded form:
     /// <https://github.com/ethereum-optimism/specs/blob/main/specs/protocol/holocene/exec-engine.md#eip1559params-encoding>
     ///
@@ -152,6 +175,7 @@ mod test {
             no_tx_pool: Some(true),
             gas_limit: Some(42),
             eip_1559_params: None,
+            min_base_fee: None,
         };
 
         let ser = serde_json::to_string(&attributes).unwrap();
@@ -174,6 +198,7 @@ mod test {
             no_tx_pool: Some(true),
             gas_limit: Some(42),
             eip_1559_params: Some(b64!("0000dead0000beef")),
+            min_base_fee: None,
         };
 
         let ser = serde_json::to_string(&attributes).unwrap();
@@ -199,4 +224,48 @@ mod test {
         let extra_data = attributes.get_holocene_extra_data(BaseFeeParams::new(80, 60));
         assert_eq!(extra_data.unwrap(), Bytes::copy_from_slice(&[0, 0, 0, 0, 80, 0, 0, 0, 60]));
     }
+
+    #[test]
+    fn test_serde_roundtrip_attributes_post_jovian() {
+        let attributes = OpPayloadAttributes {
+            payload_attributes: PayloadAttributes {
+                timestamp: 0x1337,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: Default::default(),
+                parent_beacon_block_root: Some(B256::ZERO),
+            },
+            transactions: Some(vec![b"hello".to_vec().into()]),
+            no_tx_pool: Some(true),
+            gas_limit: Some(42),
+            eip_1559_params: Some(b64!("0000dead0000beef")),
+            min_base_fee: Some(0x1000),
+        };
+
+        let ser = serde_json::to_string(&attributes).unwrap();
+        assert!(ser.contains(r#""minBaseFee":"0x1000""#));
+        let de: OpPayloadAttributes = serde_json::from_str(&ser).unwrap();
+
+        assert_eq!(attributes, de);
+    }
+
+    #[test]
+    fn test_get_extra_data_post_jovian() {
+        let attributes = OpPayloadAttributes {
+            eip_1559_params: Some(B64::from_str("0x0000000800000008").unwrap()),
+            min_base_fee: Some(257),
+            ..Default::default()
+        };
+        let extra_data = attributes.get_jovian_extra_data(BaseFeeParams::new(80, 60));
+        assert_eq!(
+            extra_data.unwrap(),
+            Bytes::copy_from_slice(&[1, 0, 0, 0, 8, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 1, 1])
+        );
+
+        let attributes = OpPayloadAttributes { min_base_fee: None, ..attributes };
+        assert_eq!(
+            attributes.get_jovian_extra_data(BaseFeeParams::new(80, 60)),
+            Err(EIP1559ParamError::MinBaseFeeNotSet)
+        );
+    }
 }
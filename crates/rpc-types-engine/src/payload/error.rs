@@ -17,6 +17,20 @@ pub enum OpPayloadError {
     /// Non-empty list of blob versioned hashes.
     #[error("non-empty blob versioned hashes")]
     NonEmptyBlobVersionedHashes,
+    /// Blob gas used that is not zero before Jovian, or not the DA footprint since Jovian.
+    #[error("invalid blob gas used: expected {expected}, got {got}")]
+    InvalidBlobGasUsed {
+        /// The expected blob gas used.
+        expected: u64,
+        /// The blob gas used of the payload.
+        got: u64,
+    },
+    /// Missing blob gas used since Jovian.
+    #[error("missing blob gas used")]
+    MissingBlobGasUsed,
+    /// The first transaction is not a Jovian L1 attributes deposit.
+    #[error("missing Jovian L1 attributes deposit")]
+    MissingL1BlockInfo,
     /// L1 [`PayloadError`] that can also occur on L2.
     #[error(transparent)]
     Eth(#[from] PayloadError),
@@ -13,6 +13,10 @@ use alloy_rpc_types_engine::{
     ExecutionPayloadV3,
 };
 use error::OpPayloadError;
+use op_alloy_consensus::{
+    L1BlockInfoTx, TxDeposit, l1_block_info::DEFAULT_DA_FOOTPRINT_GAS_SCALAR,
+    l1_fee::block_da_footprint,
+};
 
 /// An execution payload, which can be either [`ExecutionPayloadV2`], [`ExecutionPayloadV3`], or
 /// [`OpExecutionPayloadV4`].
@@ -310,6 +314,9 @@ impl OpExecutionPayload {
     /// Conversion from [`alloy_consensus::Block`]. Also returns the
     /// [`OpExecutionPayloadSidecar`] extracted from the block.
     ///
+    /// The blob gas used of the header, which holds the DA footprint of the block after Jovian, is
+    /// copied to the payload as is.
+    ///
     /// See also [`ExecutionPayload::from_block_unchecked`].
     /// See also [`OpExecutionPayloadSidecar::from_block`].
     pub fn from_block_unchecked<T, H>(
@@ -467,6 +474,49 @@ impl OpExecutionPayload {
         self.as_v1().timestamp
     }
 
+    /// Returns the blob gas used for this payload, if any.
+    ///
+    /// OP Stack blocks contain no blobs, so this is zero before Jovian. Since Jovian, it holds the
+    /// DA footprint of the block.
+    pub fn blob_gas_used(&self) -> Option<u64> {
+        self.as_v3().map(|payload| payload.blob_gas_used)
+    }
+
+    /// Checks the blob gas used of the payload.
+    ///
+    /// Before Jovian, the blob gas used must be zero if present. Since Jovian, it must be the DA
+    /// footprint of the transactions, computed with the DA footprint gas scalar of the L1
+    /// attributes deposit that is the first transaction of the payload. The default scalar is used
+    /// if it is zero, or if the deposit has no scalar because it still uses the Isthmus layout in
+    /// the Jovian activation block.
+    pub fn validate_blob_gas_used(&self, is_jovian_active: bool) -> Result<(), OpPayloadError> {
+        let Some(blob_gas_used) = self.blob_gas_used() else {
+            if is_jovian_active {
+                return Err(OpPayloadError::MissingBlobGasUsed);
+            }
+            return Ok(());
+        };
+
+        let expected = if is_jovian_active {
+            let transactions = &self.as_v1().transactions;
+            let da_footprint_gas_scalar = transactions
+                .first()
+                .and_then(|tx| TxDeposit::decode_2718(&mut tx.as_ref()).ok())
+                .and_then(|tx| L1BlockInfoTx::decode_calldata(&tx.input).ok())
+                .ok_or(OpPayloadError::MissingL1BlockInfo)?
+                .da_footprint_gas_scalar()
+                .unwrap_or(DEFAULT_DA_FOOTPRINT_GAS_SCALAR);
+            block_da_footprint(transactions, da_footprint_gas_scalar)
+        } else {
+            0
+        };
+
+        if blob_gas_used != expected {
+            return Err(OpPayloadError::InvalidBlobGasUsed { expected, got: blob_gas_used });
+        }
+        Ok(())
+    }
+
     #[allow(rustdoc::broken_intra_doc_links)]
     /// Converts [`OpExecutionPayload`] to [`Block`].
     ///
@@ -478,6 +528,10 @@ impl OpExecutionPayload {
     /// [`OpExecutionPayloadSidecar`]:
     /// - parent_beacon_block_root
     ///
+    /// The blob gas used is copied to the header as is, since it holds the DA footprint of the
+    /// block after Jovian rather than the gas of blobs. It is checked by
+    /// [`OpExecutionPayload::validate_blob_gas_used`].
+    ///
     /// See also: [`OpExecutionPayload::try_into_block_with_sidecar`]
     pub fn try_into_block<T: Decodable2718 + Typed2718>(self) -> Result<Block<T>, OpPayloadError> {
         if let Some(payload) = self.as_v2() {
@@ -593,4 +647,56 @@ mod tests {
             serde_json::from_str(response_faulty);
         assert!(payload.is_err());
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn validate_blob_gas_used() {
+        use alloy_primitives::{Bytes, hex};
+        use op_alloy_consensus::{L1BlockInfoIsthmus, L1BlockInfoJovian};
+
+        let response_v3 = r#"{"parentHash":"0xe927a1448525fb5d32cb50ee1408461a945ba6c39bd5cf5621407d500ecc8de9","feeRecipient":"0x0000000000000000000000000000000000000000","stateRoot":"0x10f8a0830000e8edef6d00cc727ff833f064b1950afd591ae41357f97e543119","receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0xe0d8b4521a7da1582a713244ffb6a86aa1726932087386e2dc7973f43fc6cb24","blockNumber":"0x1","gasLimit":"0x2ffbd2","gasUsed":"0x0","timestamp":"0x1235","extraData":"0xd883010d00846765746888676f312e32312e30856c696e7578","baseFeePerGas":"0x342770c0","blockHash":"0x44d0fa5f2f73a938ebb96a2a21679eb8dea3e7b7dd8fd9f35aa756dda8bf0a8a","transactions":[],"withdrawals":[],"blobGasUsed":"0x0","excessBlobGas":"0x0"}"#;
+        let mut payload: OpExecutionPayload = serde_json::from_str(response_v3).unwrap();
+        assert_eq!(payload.blob_gas_used(), Some(0));
+        assert!(payload.validate_blob_gas_used(false).is_ok());
+        assert!(matches!(
+            payload.validate_blob_gas_used(true),
+            Err(OpPayloadError::MissingL1BlockInfo)
+        ));
+
+        let l1_info = L1BlockInfoTx::Jovian(L1BlockInfoJovian::from_isthmus(
+            L1BlockInfoIsthmus::default(),
+            400,
+        ));
+        payload.as_v1_mut().transactions = vec![
+            l1_info.to_deposit(true).encoded_2718().into(),
+            Bytes::from_static(&hex!(
+                "02f86f0a8083989680830f4240825208940000000000000000000000000000000000000000872386f26fc1000080c080a0ab7bfc5fd0f2c18a29b27e4a9f96c4fb6bb7ef0f5cdab3b9d1cfaa05a1b3b3dea031ad0db7b2c85de0d7e7d6f6b02cbd19b3cd6f09bb69d10ad7e1c1cb4c9b5fba"
+            )),
+        ];
+        // The deposit has no DA footprint, and the transaction is charged the minimum size.
+        assert!(matches!(
+            payload.validate_blob_gas_used(true),
+            Err(OpPayloadError::InvalidBlobGasUsed { expected: 40_000, got: 0 })
+        ));
+        payload.as_v3_mut().unwrap().blob_gas_used = 40_000;
+        assert!(payload.validate_blob_gas_used(true).is_ok());
+        assert!(matches!(
+            payload.validate_blob_gas_used(false),
+            Err(OpPayloadError::InvalidBlobGasUsed { expected: 0, got: 40_000 })
+        ));
+
+        // A zero DA footprint gas scalar falls back to the default scalar.
+        let unset = L1BlockInfoTx::Jovian(L1BlockInfoJovian::default());
+        payload.as_v1_mut().transactions[0] = unset.to_deposit(true).encoded_2718().into();
+        assert!(payload.validate_blob_gas_used(true).is_ok());
+
+        // The Jovian activation block still carries an Isthmus L1 attributes deposit.
+        let activation = L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::default());
+        payload.as_v1_mut().transactions[0] = activation.to_deposit(true).encoded_2718().into();
+        assert!(payload.validate_blob_gas_used(true).is_ok());
+
+        // Blob gas used is carried over to the block as is.
+        let block = payload.try_into_block::<op_alloy_consensus::OpTxEnvelope>().unwrap();
+        assert_eq!(block.header.blob_gas_used, Some(40_000));
+    }
 }
@@ -0,0 +1,697 @@
+//! Chain configurations in the format of the [superchain registry].
+//!
+//! [superchain registry]: https://github.com/ethereum-optimism/superchain-registry
+
+use crate::{OpBaseFeeInfo, OpChainInfo, OpGenesisInfo};
+use alloc::borrow::Cow;
+use alloy_consensus::Header;
+use alloy_eips::{BlockNumHash, eip1559::BaseFeeParams};
+use alloy_primitives::{Address, B256, address, b256};
+use op_alloy_consensus::{
+    EIP1559ParamError, ForkCondition, OpHardfork, OpHardforkSchedule, OpHardforkScheduleError,
+    OpHardforks,
+};
+
+/// The configuration of an OP Stack chain, as listed in the superchain registry.
+///
+/// Fields of the registry files that are not part of this type are ignored when parsing.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChainConfig {
+    /// The name of the chain.
+    pub name: Cow<'static, str>,
+    /// The L2 chain ID.
+    pub chain_id: u64,
+    /// The L1 address batches are submitted to.
+    pub batch_inbox_addr: Address,
+    /// The L2 block time in seconds.
+    pub block_time: u64,
+    /// The sequencing window size in L1 blocks.
+    pub seq_window_size: u64,
+    /// The maximum timestamp drift of the sequencer in seconds.
+    pub max_sequencer_drift: u64,
+    /// The hardfork activation timestamps.
+    #[serde(default)]
+    pub hardforks: HardforkConfig,
+    /// The EIP-1559 base fee parameters.
+    pub optimism: BaseFeeConfig,
+    /// The genesis anchors of the chain.
+    pub genesis: ChainGenesis,
+    /// The privileged roles of the chain.
+    #[serde(default)]
+    pub roles: ChainRoles,
+    /// The L1 contract addresses of the chain.
+    #[serde(default)]
+    pub addresses: ChainAddresses,
+}
+
+impl ChainConfig {
+    /// Parses a chain configuration from a registry JSON file.
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Parses a chain configuration from a registry TOML file.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Returns the embedded configuration of the chain with the given chain ID, if any.
+    pub fn by_chain_id(chain_id: u64) -> Option<&'static Self> {
+        CHAIN_CONFIGS.iter().find(|config| config.chain_id == chain_id)
+    }
+
+    /// Returns the address that signs unsafe blocks gossiped by the sequencer.
+    pub const fn unsafe_block_signer(&self) -> Option<Address> {
+        self.roles.unsafe_block_signer
+    }
+
+    /// Returns the hardfork activation fields in their genesis file representation.
+    ///
+    /// Bedrock activates with the L2 genesis block of the registry config. Chains in the registry
+    /// activate Regolith at genesis unless they specify otherwise.
+    pub const fn genesis_info(&self) -> OpGenesisInfo {
+        let forks = &self.hardforks;
+        OpGenesisInfo {
+            bedrock_block: Some(self.genesis.l2.number),
+            regolith_time: match forks.regolith_time {
+                Some(time) => Some(time),
+                None => Some(0),
+            },
+            canyon_time: forks.canyon_time,
+            delta_time: forks.delta_time,
+            ecotone_time: forks.ecotone_time,
+            fjord_time: forks.fjord_time,
+            granite_time: forks.granite_time,
+            holocene_time: forks.holocene_time,
+            isthmus_time: forks.isthmus_time,
+            jovian_time: forks.jovian_time,
+            interop_time: forks.interop_time,
+        }
+    }
+
+    /// Returns the base fee parameters in their genesis file representation.
+    pub const fn base_fee_info(&self) -> OpBaseFeeInfo {
+        OpBaseFeeInfo {
+            eip1559_elasticity: Some(self.optimism.eip1559_elasticity),
+            eip1559_denominator: Some(self.optimism.eip1559_denominator),
+            eip1559_denominator_canyon: self.optimism.eip1559_denominator_canyon,
+        }
+    }
+
+    /// Returns the Optimism specific fields of the genesis file of the chain.
+    pub const fn chain_info(&self) -> OpChainInfo {
+        OpChainInfo {
+            genesis_info: Some(self.genesis_info()),
+            base_fee_info: Some(self.base_fee_info()),
+        }
+    }
+
+    /// Builds the validated hardfork schedule of the chain.
+    pub fn hardfork_schedule(&self) -> Result<OpHardforkSchedule, OpHardforkScheduleError> {
+        self.genesis_info().hardfork_schedule()
+    }
+
+    /// Computes the base fee of the block following `parent` at `timestamp`.
+    ///
+    /// See [`OpBaseFeeInfo::next_block_base_fee`].
+    pub fn next_block_base_fee(
+        &self,
+        parent: &Header,
+        timestamp: u64,
+    ) -> Result<u64, EIP1559ParamError> {
+        self.base_fee_info().next_block_base_fee(self, parent, timestamp)
+    }
+}
+
+impl OpHardforks for ChainConfig {
+    fn op_fork_activation(&self, fork: OpHardfork) -> ForkCondition {
+        self.genesis_info().op_fork_activation(fork)
+    }
+}
+
+/// The hardfork activation timestamps of a [`ChainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct HardforkConfig {
+    /// Regolith activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regolith_time: Option<u64>,
+    /// Canyon activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canyon_time: Option<u64>,
+    /// Delta activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta_time: Option<u64>,
+    /// Ecotone activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecotone_time: Option<u64>,
+    /// Fjord activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fjord_time: Option<u64>,
+    /// Granite activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granite_time: Option<u64>,
+    /// Holocene activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holocene_time: Option<u64>,
+    /// Isthmus activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isthmus_time: Option<u64>,
+    /// Jovian activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jovian_time: Option<u64>,
+    /// Interop activation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interop_time: Option<u64>,
+}
+
+/// The EIP-1559 parameters of a [`ChainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BaseFeeConfig {
+    /// EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// EIP-1559 base fee max change denominator after Canyon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eip1559_denominator_canyon: Option<u64>,
+}
+
+impl BaseFeeConfig {
+    /// Returns the base fee parameters before Canyon.
+    pub const fn base_fee_params(&self) -> BaseFeeParams {
+        BaseFeeParams::new(self.eip1559_denominator as u128, self.eip1559_elasticity as u128)
+    }
+
+    /// Returns the base fee parameters after Canyon.
+    pub const fn canyon_base_fee_params(&self) -> BaseFeeParams {
+        let denominator = match self.eip1559_denominator_canyon {
+            Some(denominator) => denominator,
+            None => self.eip1559_denominator,
+        };
+        BaseFeeParams::new(denominator as u128, self.eip1559_elasticity as u128)
+    }
+}
+
+/// The genesis anchors of a [`ChainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ChainGenesis {
+    /// The L1 block the chain starts deriving from.
+    pub l1: BlockNumHash,
+    /// The first L2 block of the chain.
+    pub l2: BlockNumHash,
+    /// The timestamp of the first L2 block.
+    pub l2_time: u64,
+    /// The system config at genesis.
+    pub system_config: Option<GenesisSystemConfig>,
+}
+
+/// The system config of a chain at genesis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisSystemConfig {
+    /// The batch submitter address.
+    pub batcher_address: Address,
+    /// The pre-Ecotone L1 fee overhead.
+    pub overhead: B256,
+    /// The pre-Ecotone L1 fee scalar.
+    pub scalar: B256,
+    /// The L2 block gas limit.
+    pub gas_limit: u64,
+    /// The Ecotone base fee scalar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee_scalar: Option<u64>,
+    /// The Ecotone blob base fee scalar.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee_scalar: Option<u64>,
+}
+
+/// The privileged roles of a [`ChainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ChainRoles {
+    /// The owner of the `SystemConfig` contract.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_config_owner: Option<Address>,
+    /// The owner of the `ProxyAdmin` contract.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_admin_owner: Option<Address>,
+    /// The guardian, who can pause withdrawals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guardian: Option<Address>,
+    /// The permissioned challenger of the fault proof system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenger: Option<Address>,
+    /// The permissioned output proposer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposer: Option<Address>,
+    /// The signer of unsafe blocks gossiped by the sequencer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsafe_block_signer: Option<Address>,
+    /// The batch submitter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_submitter: Option<Address>,
+}
+
+/// The L1 contract addresses of a [`ChainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct ChainAddresses {
+    /// The `SystemConfig` proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_config_proxy: Option<Address>,
+    /// The `OptimismPortal` proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimism_portal_proxy: Option<Address>,
+    /// The `L1CrossDomainMessenger` proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l1_cross_domain_messenger_proxy: Option<Address>,
+    /// The `L1StandardBridge` proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l1_standard_bridge_proxy: Option<Address>,
+    /// The `L1ERC721Bridge` proxy.
+    #[serde(rename = "L1ERC721BridgeProxy", skip_serializing_if = "Option::is_none")]
+    pub l1_erc721_bridge_proxy: Option<Address>,
+    /// The `OptimismMintableERC20Factory` proxy.
+    #[serde(rename = "OptimismMintableERC20FactoryProxy", skip_serializing_if = "Option::is_none")]
+    pub optimism_mintable_erc20_factory_proxy: Option<Address>,
+    /// The `L2OutputOracle` proxy, used before fault proofs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l2_output_oracle_proxy: Option<Address>,
+    /// The `DisputeGameFactory` proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dispute_game_factory_proxy: Option<Address>,
+}
+
+/// The chain ID of OP Mainnet.
+pub const OP_MAINNET_CHAIN_ID: u64 = 10;
+
+/// The chain ID of Base Mainnet.
+pub const BASE_MAINNET_CHAIN_ID: u64 = 8453;
+
+/// The chain ID of OP Sepolia.
+pub const OP_SEPOLIA_CHAIN_ID: u64 = 11155420;
+
+/// The chain ID of Base Sepolia.
+pub const BASE_SEPOLIA_CHAIN_ID: u64 = 84532;
+
+/// The hardfork activation timestamps shared by the mainnet superchain.
+const MAINNET_HARDFORKS: HardforkConfig = HardforkConfig {
+    regolith_time: None,
+    canyon_time: Some(1704992401),
+    delta_time: Some(1708560000),
+    ecotone_time: Some(1710374401),
+    fjord_time: Some(1720627201),
+    granite_time: Some(1726070401),
+    holocene_time: Some(1736445601),
+    isthmus_time: Some(1746806401),
+    jovian_time: Some(1764691201),
+    interop_time: None,
+};
+
+/// The hardfork activation timestamps shared by the Sepolia superchain.
+const SEPOLIA_HARDFORKS: HardforkConfig = HardforkConfig {
+    regolith_time: None,
+    canyon_time: Some(1699981200),
+    delta_time: Some(1703203200),
+    ecotone_time: Some(1708534800),
+    fjord_time: Some(1716998400),
+    granite_time: Some(1723478400),
+    holocene_time: Some(1732633200),
+    isthmus_time: Some(1744905600),
+    jovian_time: Some(1763568001),
+    interop_time: None,
+};
+
+/// The EIP-1559 parameters shared by the mainnet superchain.
+const MAINNET_BASE_FEE: BaseFeeConfig = BaseFeeConfig {
+    eip1559_elasticity: 6,
+    eip1559_denominator: 50,
+    eip1559_denominator_canyon: Some(250),
+};
+
+/// The embedded configuration of OP Mainnet.
+pub const OP_MAINNET_CONFIG: ChainConfig = ChainConfig {
+    name: Cow::Borrowed("OP Mainnet"),
+    chain_id: OP_MAINNET_CHAIN_ID,
+    batch_inbox_addr: address!("0xff00000000000000000000000000000000000010"),
+    block_time: 2,
+    seq_window_size: 3600,
+    max_sequencer_drift: 600,
+    hardforks: MAINNET_HARDFORKS,
+    optimism: MAINNET_BASE_FEE,
+    genesis: ChainGenesis {
+        l1: BlockNumHash {
+            number: 17422590,
+            hash: b256!("0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108"),
+        },
+        l2: BlockNumHash {
+            number: 105235063,
+            hash: b256!("0xdbf6a80fef073de06add9b0d14026d6e5a86c85f6d102c36d3d8e9cf89c2afd3"),
+        },
+        l2_time: 1686068903,
+        system_config: Some(GenesisSystemConfig {
+            batcher_address: address!("0x6887246668a3b87F54DeB3b94Ba47a6f63F32985"),
+            overhead: b256!("0x00000000000000000000000000000000000000000000000000000000000000bc"),
+            scalar: b256!("0x00000000000000000000000000000000000000000000000000000000000a6fe0"),
+            gas_limit: 30_000_000,
+            base_fee_scalar: None,
+            blob_base_fee_scalar: None,
+        }),
+    },
+    roles: ChainRoles {
+        system_config_owner: Some(address!("0x847B5c174615B1B7fDF770882256e2D3E95b9D92")),
+        proxy_admin_owner: Some(address!("0x5a0Aae59D09fccBdDb6C6CcEB07B7279367C3d2A")),
+        guardian: Some(address!("0x09f7150D8c019BeF34450d6920f6B3608ceFdAf2")),
+        challenger: Some(address!("0x9BA6e03D8B90dE867373Db8cF1A58d2F7F006b3A")),
+        proposer: Some(address!("0x473300df21D047806A082244b417f96b32f13A33")),
+        unsafe_block_signer: Some(address!("0xAAAA45d9549EDA09E70937013520214382Ffc4A2")),
+        batch_submitter: Some(address!("0x6887246668a3b87F54DeB3b94Ba47a6f63F32985")),
+    },
+    addresses: ChainAddresses {
+        system_config_proxy: Some(address!("0x229047fed2591dbec1eF1118d64F7aF3dB9EB290")),
+        optimism_portal_proxy: Some(address!("0xbEb5Fc579115071764c7423A4f12eDde41f106Ed")),
+        l1_cross_domain_messenger_proxy: Some(address!(
+            "0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1"
+        )),
+        l1_standard_bridge_proxy: Some(address!("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1")),
+        l1_erc721_bridge_proxy: Some(address!("0x5a7749f83b81B301cAb5f48EB8516B986DAef23D")),
+        optimism_mintable_erc20_factory_proxy: Some(address!(
+            "0x75505a97BD334E7BD3C476893285569C4136Fa0F"
+        )),
+        l2_output_oracle_proxy: Some(address!("0xdfe97868233d1aa22e815a266982f2cf17685a27")),
+        dispute_game_factory_proxy: Some(address!("0xe5965Ab5962eDc7477C8520243A95517CD252fA9")),
+    },
+};
+
+/// The embedded configuration of Base Mainnet.
+pub const BASE_MAINNET_CONFIG: ChainConfig = ChainConfig {
+    name: Cow::Borrowed("Base"),
+    chain_id: BASE_MAINNET_CHAIN_ID,
+    batch_inbox_addr: address!("0xff00000000000000000000000000000000008453"),
+    block_time: 2,
+    seq_window_size: 3600,
+    max_sequencer_drift: 600,
+    hardforks: MAINNET_HARDFORKS,
+    optimism: MAINNET_BASE_FEE,
+    genesis: ChainGenesis {
+        l1: BlockNumHash {
+            number: 17481768,
+            hash: b256!("0x5c13d307623a926cd31415036c8b7fa14572f9dac64528e857a470511fc30771"),
+        },
+        l2: BlockNumHash {
+            number: 0,
+            hash: b256!("0xf712aa9241cc24369b143cf6dce85f0902a9731e70d66818a3a5845b296c73dd"),
+        },
+        l2_time: 1686789347,
+        system_config: Some(GenesisSystemConfig {
+            batcher_address: address!("0x5050F69a9786F081509234F1a7F4684b5E5b76C9"),
+            overhead: b256!("0x00000000000000000000000000000000000000000000000000000000000000bc"),
+            scalar: b256!("0x00000000000000000000000000000000000000000000000000000000000a6fe0"),
+            gas_limit: 30_000_000,
+            base_fee_scalar: None,
+            blob_base_fee_scalar: None,
+        }),
+    },
+    roles: ChainRoles {
+        system_config_owner: Some(address!("0x14536667Cd30e52C0b458BaACcB9faDA7046E056")),
+        proxy_admin_owner: Some(address!("0x7bB41C3008B3f03FE483B28b8DB90e19Cf07595c")),
+        guardian: Some(address!("0x09f7150D8c019BeF34450d6920f6B3608ceFdAf2")),
+        challenger: Some(address!("0x6F8C5bA3F59ea3E76300E3BEcDC231D656017824")),
+        proposer: Some(address!("0x642229f238fb9dE03374Be34B0eD8D9De80752c5")),
+        unsafe_block_signer: Some(address!("0xAf6E19BE0F9cE7f8afd49a1824851023A8249e8a")),
+        batch_submitter: Some(address!("0x5050F69a9786F081509234F1a7F4684b5E5b76C9")),
+    },
+    addresses: ChainAddresses {
+        system_config_proxy: Some(address!("0x73a79Fab69143498Ed3712e519A88a918e1f4072")),
+        optimism_portal_proxy: Some(address!("0x49048044D57e1C92A77f79988d21Fa8fAF74E97e")),
+        l1_cross_domain_messenger_proxy: Some(address!(
+            "0x866E82a600A1414e583f7F13623F1aC5d58b0Afa"
+        )),
+        l1_standard_bridge_proxy: Some(address!("0x3154Cf16ccdb4C6d922629664174b904d80F2C35")),
+        l1_erc721_bridge_proxy: Some(address!("0x608d94945A64503E642E6370Ec598e519a2C1E53")),
+        optimism_mintable_erc20_factory_proxy: Some(address!(
+            "0x05cc379EBD9B30BbA19C6fA282AB29218EC61D84"
+        )),
+        l2_output_oracle_proxy: Some(address!("0x56315b90c40730925ec5485cf004d835058518A0")),
+        dispute_game_factory_proxy: Some(address!("0x43edB88C4B80fDD2AdFF2412A7BebF9dF42cB40e")),
+    },
+};
+
+/// The embedded configuration of OP Sepolia.
+pub const OP_SEPOLIA_CONFIG: ChainConfig = ChainConfig {
+    name: Cow::Borrowed("OP Sepolia Testnet"),
+    chain_id: OP_SEPOLIA_CHAIN_ID,
+    batch_inbox_addr: address!("0xff00000000000000000000000000000011155420"),
+    block_time: 2,
+    seq_window_size: 3600,
+    max_sequencer_drift: 600,
+    hardforks: SEPOLIA_HARDFORKS,
+    optimism: MAINNET_BASE_FEE,
+    genesis: ChainGenesis {
+        l1: BlockNumHash {
+            number: 4071408,
+            hash: b256!("0x48f520cf4ddaf34c8336e6e490632ea3cf1e5e93b0b2bc6e917557e31845371b"),
+        },
+        l2: BlockNumHash {
+            number: 0,
+            hash: b256!("0x102de6ffb001480cc9b8b548fd05c34cd4f46ae4aa91759393db90ea0409887d"),
+        },
+        l2_time: 1691802540,
+        system_config: Some(GenesisSystemConfig {
+            batcher_address: address!("0x8F23BB38F531600e5d8FDDaAEC41F13FaB46E98c"),
+            overhead: b256!("0x00000000000000000000000000000000000000000000000000000000000000bc"),
+            scalar: b256!("0x00000000000000000000000000000000000000000000000000000000000a6fe0"),
+            gas_limit: 30_000_000,
+            base_fee_scalar: None,
+            blob_base_fee_scalar: None,
+        }),
+    },
+    roles: ChainRoles {
+        system_config_owner: Some(address!("0xfd1D2e729aE8eEe2E146c033bf4400fE75284301")),
+        proxy_admin_owner: Some(address!("0x1Eb2fFc903729a0F03966B917003800b145F56E2")),
+        guardian: Some(address!("0x7a50f00e8D05b95F98fE38d8BeE366a7324dCf7E")),
+        challenger: Some(address!("0xfd1D2e729aE8eEe2E146c033bf4400fE75284301")),
+        proposer: Some(address!("0x49277EE36A024120Ee218127354c4a3591dc90A9")),
+        unsafe_block_signer: Some(address!("0x57CACBB0d30b01eb2462e5dC940c161aff3230D3")),
+        batch_submitter: Some(address!("0x8F23BB38F531600e5d8FDDaAEC41F13FaB46E98c")),
+    },
+    addresses: ChainAddresses {
+        system_config_proxy: Some(address!("0x034edD2A225f7f429A63E0f1D2084B9E0A93b538")),
+        optimism_portal_proxy: Some(address!("0x16Fc5058F25648194471939df75CF27A2fdC48BC")),
+        l1_cross_domain_messenger_proxy: Some(address!(
+            "0x58Cc85b8D04EA49cC6DBd3CbFFd00B4B8D6cb3ef"
+        )),
+        l1_standard_bridge_proxy: Some(address!("0xFBb0621E0B23b5478B630BD55a5f21f67730B0F1")),
+        l1_erc721_bridge_proxy: Some(address!("0xd83e03D576d23C9AEab8cC44Fa98d058D2176D1f")),
+        optimism_mintable_erc20_factory_proxy: Some(address!(
+            "0x868D59fF9710159C2B330Cc0fBDF57144dD7A13b"
+        )),
+        l2_output_oracle_proxy: Some(address!("0x90E9c4f8a994a250F6aEfd61CAFb4F2e895D458F")),
+        dispute_game_factory_proxy: Some(address!("0x05F9613aDB30026FFd634f38e5C4dFd30a197Fa1")),
+    },
+};
+
+/// The embedded configuration of Base Sepolia.
+pub const BASE_SEPOLIA_CONFIG: ChainConfig = ChainConfig {
+    name: Cow::Borrowed("Base Sepolia Testnet"),
+    chain_id: BASE_SEPOLIA_CHAIN_ID,
+    batch_inbox_addr: address!("0xff00000000000000000000000000000000084532"),
+    block_time: 2,
+    seq_window_size: 3600,
+    max_sequencer_drift: 600,
+    hardforks: SEPOLIA_HARDFORKS,
+    optimism: BaseFeeConfig {
+        eip1559_elasticity: 10,
+        eip1559_denominator: 50,
+        eip1559_denominator_canyon: Some(250),
+    },
+    genesis: ChainGenesis {
+        l1: BlockNumHash {
+            number: 4370868,
+            hash: b256!("0xcac9a83291d4dec146d6f7f69ab2304f23f5be87b1789119a0c5b1e4482444ed"),
+        },
+        l2: BlockNumHash {
+            number: 0,
+            hash: b256!("0x0dcc9e089e30b90ddfc55be9a37dd15bc551aeee999d2e2b51414c54eaf934e4"),
+        },
+        l2_time: 1695768288,
+        system_config: Some(GenesisSystemConfig {
+            batcher_address: address!("0x6CDEbe940BC0F26850285cacA097C11c33103E47"),
+            overhead: b256!("0x00000000000000000000000000000000000000000000000000000000000000bc"),
+            scalar: b256!("0x00000000000000000000000000000000000000000000000000000000000a6fe0"),
+            gas_limit: 25_000_000,
+            base_fee_scalar: None,
+            blob_base_fee_scalar: None,
+        }),
+    },
+    roles: ChainRoles {
+        system_config_owner: Some(address!("0x0fe884546476dDd290eC46318785046ef68a0BA9")),
+        proxy_admin_owner: Some(address!("0x0fe884546476dDd290eC46318785046ef68a0BA9")),
+        guardian: Some(address!("0xA9FF930151130fd19DA1F03E5077AFB7C78F8503")),
+        challenger: Some(address!("0xDa3037Ff70Ac92CD867c683BD807e5A484857405")),
+        proposer: Some(address!("0x037637067c1DbE6d2430616d8f54Cb774Daa5999")),
+        unsafe_block_signer: Some(address!("0xb830b99c95Ea32300039624Cb567d324D4b1D83C")),
+        batch_submitter: Some(address!("0x6CDEbe940BC0F26850285cacA097C11c33103E47")),
+    },
+    addresses: ChainAddresses {
+        system_config_proxy: Some(address!("0xf272670eb55e895584501d564AfEB048bEd26194")),
+        optimism_portal_proxy: Some(address!("0x49f53e41452C74589E85cA1677426Ba426459e85")),
+        l1_cross_domain_messenger_proxy: Some(address!(
+            "0xC34855F4De64F1840e5686e64278da901e261f20"
+        )),
+        l1_standard_bridge_proxy: Some(address!("0xfd0Bf71F60660E2f608ed56e1659C450eB113120")),
+        l1_erc721_bridge_proxy: Some(address!("0x21eFD066e581FA55Ef105170Cc04d74386a09190")),
+        optimism_mintable_erc20_factory_proxy: Some(address!(
+            "0xb1efB9650aD6d0CC1ed3Ac4a0B7f1D5732696D37"
+        )),
+        l2_output_oracle_proxy: Some(address!("0x84457ca9D0163FbC4bbfe4Dfbb20ba46e48DF254")),
+        dispute_game_factory_proxy: Some(address!("0xd6E6dBf4F7EA0ac412fD8b65ED297e64BB7a06E1")),
+    },
+};
+
+/// The embedded chain configurations, see [`ChainConfig::by_chain_id`].
+pub const CHAIN_CONFIGS: &[ChainConfig] =
+    &[OP_MAINNET_CONFIG, BASE_MAINNET_CONFIG, OP_SEPOLIA_CONFIG, BASE_SEPOLIA_CONFIG];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_chain_id() {
+        assert_eq!(ChainConfig::by_chain_id(10), Some(&OP_MAINNET_CONFIG));
+        assert_eq!(ChainConfig::by_chain_id(8453), Some(&BASE_MAINNET_CONFIG));
+        assert_eq!(ChainConfig::by_chain_id(11155420), Some(&OP_SEPOLIA_CONFIG));
+        assert_eq!(ChainConfig::by_chain_id(84532), Some(&BASE_SEPOLIA_CONFIG));
+        assert_eq!(ChainConfig::by_chain_id(1), None);
+    }
+
+    #[test]
+    fn test_embedded_hardfork_schedules() {
+        for config in CHAIN_CONFIGS {
+            let schedule = config.hardfork_schedule().unwrap();
+            assert!(schedule.is_bedrock_active_at_block(config.genesis.l2.number));
+            assert!(schedule.is_regolith_active_at_timestamp(config.genesis.l2_time));
+        }
+
+        for config in [OP_MAINNET_CONFIG, BASE_MAINNET_CONFIG] {
+            assert!(!config.is_canyon_active_at_timestamp(1704992400));
+            assert!(!config.is_delta_active_at_timestamp(1708559999));
+            assert!(config.is_delta_active_at_timestamp(1708560000));
+            assert!(config.is_holocene_active_at_timestamp(1736445601));
+            assert!(!config.is_jovian_active_at_timestamp(1764691200));
+            assert!(config.is_jovian_active_at_timestamp(1764691201));
+        }
+
+        for config in [OP_SEPOLIA_CONFIG, BASE_SEPOLIA_CONFIG] {
+            assert!(!config.is_canyon_active_at_timestamp(1699981199));
+            assert!(config.is_delta_active_at_timestamp(1703203200));
+            assert!(!config.is_ecotone_active_at_timestamp(1708534799));
+            assert!(config.is_isthmus_active_at_timestamp(1744905600));
+            assert!(!config.is_jovian_active_at_timestamp(1763568000));
+            assert!(config.is_jovian_active_at_timestamp(1763568001));
+        }
+    }
+
+    #[test]
+    fn test_base_fee_params() {
+        let config = OP_MAINNET_CONFIG.optimism;
+        assert_eq!(config.base_fee_params(), BaseFeeParams::new(50, 6));
+        assert_eq!(config.canyon_base_fee_params(), BaseFeeParams::new(250, 6));
+        assert_eq!(
+            BASE_SEPOLIA_CONFIG.optimism.canyon_base_fee_params(),
+            BaseFeeParams::new(250, 10)
+        );
+        assert_eq!(
+            OP_MAINNET_CONFIG.base_fee_info(),
+            OpBaseFeeInfo {
+                eip1559_elasticity: Some(6),
+                eip1559_denominator: Some(50),
+                eip1559_denominator_canyon: Some(250),
+            }
+        );
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let json = serde_json::to_string(&BASE_MAINNET_CONFIG).unwrap();
+        assert_eq!(ChainConfig::from_json(&json).unwrap(), BASE_MAINNET_CONFIG);
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_parse_registry_toml() {
+        let config = r#"
+name = "OP Mainnet"
+public_rpc = "https://mainnet.optimism.io"
+sequencer_rpc = "https://mainnet-sequencer.optimism.io"
+explorer = "https://explorer.optimism.io"
+superchain_level = 1
+governed_by_optimism = true
+superchain_time = 0
+data_availability_type = "eth-da"
+chain_id = 10
+batch_inbox_addr = "0xFF00000000000000000000000000000000000010"
+block_time = 2
+seq_window_size = 3600
+max_sequencer_drift = 600
+
+[hardforks]
+  canyon_time = 1704992401 # Thu 11 Jan 2024 17:00:01 UTC
+  delta_time = 1708560000 # Thu 22 Feb 2024 00:00:00 UTC
+  ecotone_time = 1710374401 # Thu 14 Mar 2024 00:00:01 UTC
+  fjord_time = 1720627201 # Wed 10 Jul 2024 16:00:01 UTC
+  granite_time = 1726070401 # Wed 11 Sep 2024 16:00:01 UTC
+  holocene_time = 1736445601 # Thu 9 Jan 2025 18:00:01 UTC
+  isthmus_time = 1746806401 # Fri 9 May 2025 16:00:01 UTC
+  jovian_time = 1764691201 # Tue 2 Dec 2025 16:00:01 UTC
+
+[optimism]
+  eip1559_elasticity = 6
+  eip1559_denominator = 50
+  eip1559_denominator_canyon = 250
+
+[genesis]
+  l2_time = 1686068903
+  [genesis.l1]
+    hash = "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108"
+    number = 17422590
+  [genesis.l2]
+    hash = "0xdbf6a80fef073de06add9b0d14026d6e5a86c85f6d102c36d3d8e9cf89c2afd3"
+    number = 105235063
+  [genesis.system_config]
+    batcherAddress = "0x6887246668a3b87F54DeB3b94Ba47a6f63F32985"
+    overhead = "0x00000000000000000000000000000000000000000000000000000000000000bc"
+    scalar = "0x00000000000000000000000000000000000000000000000000000000000a6fe0"
+    gasLimit = 30000000
+
+[roles]
+  SystemConfigOwner = "0x847B5c174615B1B7fDF770882256e2D3E95b9D92"
+  ProxyAdminOwner = "0x5a0Aae59D09fccBdDb6C6CcEB07B7279367C3d2A"
+  Guardian = "0x09f7150D8c019BeF34450d6920f6B3608ceFdAf2"
+  Challenger = "0x9BA6e03D8B90dE867373Db8cF1A58d2F7F006b3A"
+  Proposer = "0x473300df21D047806A082244b417f96b32f13A33"
+  UnsafeBlockSigner = "0xAAAA45d9549EDA09E70937013520214382Ffc4A2"
+  BatchSubmitter = "0x6887246668a3b87F54DeB3b94Ba47a6f63F32985"
+
+[addresses]
+  L1CrossDomainMessengerProxy = "0x25ace71c97B33Cc4729CF772ae268934F7ab5fA1"
+  L1ERC721BridgeProxy = "0x5a7749f83b81B301cAb5f48EB8516B986DAef23D"
+  L1StandardBridgeProxy = "0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"
+  L2OutputOracleProxy = "0xdfe97868233d1aa22e815a266982f2cf17685a27"
+  OptimismMintableERC20FactoryProxy = "0x75505a97BD334E7BD3C476893285569C4136Fa0F"
+  OptimismPortalProxy = "0xbEb5Fc579115071764c7423A4f12eDde41f106Ed"
+  SystemConfigProxy = "0x229047fed2591dbec1eF1118d64F7aF3dB9EB290"
+  DisputeGameFactoryProxy = "0xe5965Ab5962eDc7477C8520243A95517CD252fA9"
+"#;
+
+        let config = ChainConfig::from_toml(config).unwrap();
+        assert_eq!(config, OP_MAINNET_CONFIG);
+        assert_eq!(
+            config.unsafe_block_signer(),
+            Some(address!("0xAAAA45d9549EDA09E70937013520214382Ffc4A2"))
+        );
+    }
+}
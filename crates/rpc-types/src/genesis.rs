@@ -1,6 +1,12 @@
 //! OP types for genesis data.
 
+use alloy_consensus::Header;
+use alloy_eips::eip1559::BaseFeeParams;
 use alloy_serde::OtherFields;
+use op_alloy_consensus::{
+    EIP1559ParamError, ForkCondition, OpHardfork, OpHardforkSchedule, OpHardforkScheduleError,
+    OpHardforks, calc_next_block_base_fee,
+};
 use serde::de::Error;
 
 /// Container type for all Optimism specific fields in a genesis file.
@@ -42,6 +48,8 @@ pub struct OpGenesisInfo {
     pub regolith_time: Option<u64>,
     /// canyon hardfork timestamp
     pub canyon_time: Option<u64>,
+    /// delta hardfork timestamp
+    pub delta_time: Option<u64>,
     /// ecotone hardfork timestamp
     pub ecotone_time: Option<u64>,
     /// fjord hardfork timestamp
@@ -52,6 +60,8 @@ pub struct OpGenesisInfo {
     pub holocene_time: Option<u64>,
     /// isthmus hardfork timestamp
     pub isthmus_time: Option<u64>,
+    /// jovian hardfork timestamp
+    pub jovian_time: Option<u64>,
     /// interop hardfork timestamp
     pub interop_time: Option<u64>,
 }
@@ -61,6 +71,11 @@ impl OpGenesisInfo {
     pub fn extract_from(others: &OtherFields) -> Option<Self> {
         Self::try_from(others).ok()
     }
+
+    /// Builds the validated hardfork schedule from the activation fields.
+    pub fn hardfork_schedule(&self) -> Result<OpHardforkSchedule, OpHardforkScheduleError> {
+        OpHardforkSchedule::new(OpHardfork::ALL.map(|fork| (fork, self.op_fork_activation(fork))))
+    }
 }
 
 impl TryFrom<&OtherFields> for OpGenesisInfo {
@@ -71,6 +86,35 @@ impl TryFrom<&OtherFields> for OpGenesisInfo {
     }
 }
 
+impl OpHardforks for OpGenesisInfo {
+    fn op_fork_activation(&self, fork: OpHardfork) -> ForkCondition {
+        let timestamp = match fork {
+            OpHardfork::Bedrock => {
+                return self.bedrock_block.map_or(ForkCondition::Never, ForkCondition::Block);
+            }
+            OpHardfork::Regolith => self.regolith_time,
+            OpHardfork::Canyon => self.canyon_time,
+            OpHardfork::Delta => self.delta_time,
+            OpHardfork::Ecotone => self.ecotone_time,
+            OpHardfork::Fjord => self.fjord_time,
+            OpHardfork::Granite => self.granite_time,
+            OpHardfork::Holocene => self.holocene_time,
+            OpHardfork::Isthmus => self.isthmus_time,
+            OpHardfork::Jovian => self.jovian_time,
+            OpHardfork::Interop => self.interop_time,
+        };
+        timestamp.map_or(ForkCondition::Never, ForkCondition::Timestamp)
+    }
+}
+
+impl TryFrom<OpGenesisInfo> for OpHardforkSchedule {
+    type Error = OpHardforkScheduleError;
+
+    fn try_from(info: OpGenesisInfo) -> Result<Self, Self::Error> {
+        info.hardfork_schedule()
+    }
+}
+
 /// The Optimism-specific base fee specification.
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -89,6 +133,48 @@ impl OpBaseFeeInfo {
     pub fn extract_from(others: &OtherFields) -> Option<Self> {
         Self::try_from(others).ok()
     }
+
+    /// Returns the base fee parameters before Canyon.
+    ///
+    /// Missing fields default to the OP Mainnet parameters.
+    pub fn base_fee_params(&self) -> BaseFeeParams {
+        let defaults = BaseFeeParams::optimism();
+        BaseFeeParams::new(
+            self.eip1559_denominator.map_or(defaults.max_change_denominator, u128::from),
+            self.eip1559_elasticity.map_or(defaults.elasticity_multiplier, u128::from),
+        )
+    }
+
+    /// Returns the base fee parameters after Canyon.
+    ///
+    /// Falls back to the pre-Canyon denominator if no Canyon denominator is set.
+    pub fn canyon_base_fee_params(&self) -> BaseFeeParams {
+        let params = self.base_fee_params();
+        self.eip1559_denominator_canyon.map_or(params, |denominator| {
+            BaseFeeParams::new(denominator.into(), params.elasticity_multiplier)
+        })
+    }
+
+    /// Computes the base fee of the block following `parent` at `timestamp`.
+    ///
+    /// After Holocene, the EIP-1559 parameters are read from the `extra_data` of the parent.
+    /// Before Holocene, the parameters of this base fee info are used, with the Canyon
+    /// denominator if Canyon is active at `timestamp`.
+    ///
+    /// See [`calc_next_block_base_fee`].
+    pub fn next_block_base_fee(
+        &self,
+        hardforks: &impl OpHardforks,
+        parent: &Header,
+        timestamp: u64,
+    ) -> Result<u64, EIP1559ParamError> {
+        let default_base_fee_params = if hardforks.is_canyon_active_at_timestamp(timestamp) {
+            self.canyon_base_fee_params()
+        } else {
+            self.base_fee_params()
+        };
+        calc_next_block_base_fee(hardforks, parent, default_base_fee_params)
+    }
 }
 
 impl TryFrom<&OtherFields> for OpBaseFeeInfo {
@@ -106,6 +192,7 @@ impl TryFrom<&OtherFields> for OpBaseFeeInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::Bytes;
 
     #[test]
     fn test_extract_optimism_genesis_info() {
@@ -127,16 +214,83 @@ mod tests {
                 bedrock_block: Some(10),
                 regolith_time: Some(12),
                 canyon_time: Some(0),
+                delta_time: None,
                 ecotone_time: Some(0),
                 fjord_time: None,
                 granite_time: None,
                 holocene_time: None,
                 isthmus_time: None,
+                jovian_time: None,
                 interop_time: None,
             }
         );
     }
 
+    #[test]
+    fn test_genesis_info_hardfork_schedule() {
+        let genesis_info = OpGenesisInfo {
+            bedrock_block: Some(105235063),
+            regolith_time: Some(0),
+            canyon_time: Some(1704992401),
+            ecotone_time: Some(1710374401),
+            fjord_time: Some(1720627201),
+            granite_time: Some(1726070401),
+            holocene_time: Some(1736445601),
+            ..Default::default()
+        };
+
+        assert!(genesis_info.is_bedrock_active_at_block(105235063));
+        assert!(!genesis_info.is_holocene_active_at_timestamp(1736445600));
+        assert!(genesis_info.is_holocene_active_at_timestamp(1736445601));
+
+        let schedule = OpHardforkSchedule::try_from(genesis_info).unwrap();
+        for fork in OpHardfork::ALL {
+            assert_eq!(schedule.op_fork_activation(fork), genesis_info.op_fork_activation(fork));
+        }
+
+        let genesis_info = OpGenesisInfo { fjord_time: Some(1710374400), ..genesis_info };
+        assert_eq!(
+            genesis_info.hardfork_schedule(),
+            Err(OpHardforkScheduleError::OutOfOrder {
+                fork: OpHardfork::Fjord,
+                activation: 1710374400,
+                previous: OpHardfork::Ecotone,
+                previous_activation: 1710374401,
+            })
+        );
+    }
+
+    #[test]
+    fn test_extract_jovian_time() {
+        let others: OtherFields =
+            serde_json::from_str(r#"{"isthmusTime": 10, "jovianTime": 20}"#).unwrap();
+        let genesis_info = OpGenesisInfo::extract_from(&others).unwrap();
+
+        assert_eq!(genesis_info.jovian_time, Some(20));
+        assert!(!genesis_info.is_jovian_active_at_timestamp(19));
+        assert!(genesis_info.is_jovian_active_at_timestamp(20));
+    }
+
+    #[test]
+    fn test_delta_time() {
+        let others: OtherFields =
+            serde_json::from_str(r#"{"canyonTime": 10, "deltaTime": 15, "ecotoneTime": 20}"#)
+                .unwrap();
+        let genesis_info = OpGenesisInfo::extract_from(&others).unwrap();
+        assert_eq!(genesis_info.delta_time, Some(15));
+        assert!(!genesis_info.is_delta_active_at_timestamp(14));
+        assert!(genesis_info.is_delta_active_at_timestamp(15));
+
+        // Delta only changed derivation, so execution layer genesis files usually omit it.
+        let genesis_info = OpGenesisInfo { delta_time: None, ..genesis_info };
+        assert_eq!(genesis_info.op_fork_activation(OpHardfork::Delta), ForkCondition::Never);
+        assert!(!genesis_info.is_delta_active_at_timestamp(u64::MAX));
+        let genesis_info =
+            OpGenesisInfo { bedrock_block: Some(0), regolith_time: Some(0), ..genesis_info };
+        let schedule = genesis_info.hardfork_schedule().unwrap();
+        assert!(schedule.is_ecotone_active_at_timestamp(20));
+    }
+
     #[test]
     fn test_extract_optimism_base_fee_info() {
         let base_fee_info = r#"
@@ -187,11 +341,13 @@ mod tests {
                     bedrock_block: Some(10),
                     regolith_time: Some(12),
                     canyon_time: Some(0),
+                    delta_time: None,
                     ecotone_time: Some(0),
                     fjord_time: None,
                     granite_time: None,
                     holocene_time: None,
                     isthmus_time: None,
+                    jovian_time: None,
                     interop_time: None,
                 }),
                 base_fee_info: Some(OpBaseFeeInfo {
@@ -211,11 +367,13 @@ mod tests {
                     bedrock_block: Some(10),
                     regolith_time: Some(12),
                     canyon_time: Some(0),
+                    delta_time: None,
                     ecotone_time: Some(0),
                     fjord_time: None,
                     granite_time: None,
                     holocene_time: None,
                     isthmus_time: None,
+                    jovian_time: None,
                     interop_time: None,
                 }),
                 base_fee_info: Some(OpBaseFeeInfo {
@@ -251,15 +409,75 @@ mod tests {
                     bedrock_block: Some(10),
                     regolith_time: Some(12),
                     canyon_time: Some(0),
+                    delta_time: None,
                     ecotone_time: Some(0),
                     fjord_time: Some(0),
                     granite_time: Some(0),
                     holocene_time: Some(0),
                     isthmus_time: None,
+                    jovian_time: None,
                     interop_time: None,
                 }),
                 base_fee_info: None,
             }
         );
     }
+
+    #[test]
+    fn test_next_block_base_fee() {
+        let genesis_info = OpGenesisInfo {
+            bedrock_block: Some(0),
+            regolith_time: Some(0),
+            canyon_time: Some(10),
+            ecotone_time: Some(10),
+            fjord_time: Some(10),
+            granite_time: Some(10),
+            holocene_time: Some(20),
+            ..Default::default()
+        };
+        let base_fee_info = OpBaseFeeInfo {
+            eip1559_elasticity: Some(6),
+            eip1559_denominator: Some(50),
+            eip1559_denominator_canyon: Some(250),
+        };
+        let parent = |timestamp, extra_data: &[u8]| Header {
+            timestamp,
+            gas_limit: 30_000_000,
+            gas_used: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            extra_data: Bytes::copy_from_slice(extra_data),
+            ..Default::default()
+        };
+
+        // Bedrock: 1e9 + 1e9 * (30e6 - 5e6) / 5e6 / 50
+        let next = base_fee_info.next_block_base_fee(&genesis_info, &parent(6, &[]), 8);
+        assert_eq!(next, Ok(1_100_000_000));
+        // Canyon activation block: 1e9 + 1e9 * (30e6 - 5e6) / 5e6 / 250
+        let next = base_fee_info.next_block_base_fee(&genesis_info, &parent(8, &[]), 10);
+        assert_eq!(next, Ok(1_020_000_000));
+        // Holocene activation block: the pre-Holocene parent has no parameters in its extra data.
+        let next = base_fee_info.next_block_base_fee(&genesis_info, &parent(18, &[]), 20);
+        assert_eq!(next, Ok(1_020_000_000));
+        // Holocene: 1e9 + 1e9 * (30e6 - 15e6) / 15e6 / 8
+        let holocene_parent = parent(20, &[0, 0, 0, 0, 8, 0, 0, 0, 2]);
+        let next = base_fee_info.next_block_base_fee(&genesis_info, &holocene_parent, 22);
+        assert_eq!(next, Ok(1_125_000_000));
+        let next = base_fee_info.next_block_base_fee(&genesis_info, &parent(20, &[]), 22);
+        assert_eq!(next, Err(EIP1559ParamError::NoEIP1559Params));
+    }
+
+    #[test]
+    fn test_base_fee_info_params() {
+        let info = OpBaseFeeInfo::default();
+        assert_eq!(info.base_fee_params(), BaseFeeParams::optimism());
+        assert_eq!(info.canyon_base_fee_params(), BaseFeeParams::optimism());
+
+        let info = OpBaseFeeInfo {
+            eip1559_elasticity: Some(10),
+            eip1559_denominator: Some(50),
+            eip1559_denominator_canyon: Some(250),
+        };
+        assert_eq!(info.base_fee_params(), BaseFeeParams::new(50, 10));
+        assert_eq!(info.canyon_base_fee_params(), BaseFeeParams::new(250, 10));
+    }
 }
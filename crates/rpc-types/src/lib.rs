@@ -12,11 +12,19 @@ extern crate alloc;
 mod genesis;
 pub use genesis::{OpBaseFeeInfo, OpChainInfo, OpGenesisInfo};
 
+pub mod chain_config;
+pub use chain_config::ChainConfig;
+
 mod receipt;
-pub use receipt::{L1BlockInfo, OpTransactionReceipt, OpTransactionReceiptFields};
+pub use receipt::{
+    L1BlockInfo, OpReceiptVerificationError, OpTransactionReceipt, OpTransactionReceiptFields,
+};
 
 mod transaction;
 pub use transaction::{OpTransactionFields, OpTransactionRequest, Transaction};
 
+pub mod withdrawal;
+pub use withdrawal::{WithdrawalProofError, verify_withdrawal_proof};
+
 pub mod error;
 pub use error::SuperchainDAError;
@@ -1,8 +1,14 @@
 //! Receipt types for RPC
 
 use alloy_consensus::{Receipt, ReceiptWithBloom};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_network_primitives::ReceiptResponse;
+use alloy_primitives::{TxHash, U256};
 use alloy_serde::OtherFields;
-use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
+use op_alloy_consensus::{
+    L1BlockInfoIsthmus, L1BlockInfoJovian, L1BlockInfoTx, OpDepositReceipt,
+    OpDepositReceiptWithBloom, OpHardfork, OpReceiptEnvelope, OpTxEnvelope, l1_fee::l1_gas_used,
+};
 use serde::{Deserialize, Serialize};
 
 /// OP Transaction Receipt type
@@ -76,6 +82,54 @@ impl alloy_network_primitives::ReceiptResponse for OpTransactionReceipt {
     }
 }
 
+impl OpTransactionReceipt {
+    /// Returns the operator fee paid by the transaction, computed from the gas used and the
+    /// operator fee parameters reported by the receipt, with the formula of `fork`.
+    ///
+    /// Returns `None` if the receipt has no operator fee parameters, i.e. before Isthmus and for
+    /// deposit transactions, or if they are out of range of the L1 attributes fields.
+    ///
+    /// See [`L1BlockInfoTx::operator_fee`].
+    pub fn operator_fee(&self, fork: OpHardfork) -> Option<U256> {
+        let operator_fee_scalar = self.l1_block_info.operator_fee_scalar?.try_into().ok()?;
+        let operator_fee_constant = self.l1_block_info.operator_fee_constant?.try_into().ok()?;
+        let l1_info = if fork >= OpHardfork::Jovian {
+            L1BlockInfoTx::Jovian(L1BlockInfoJovian {
+                operator_fee_scalar,
+                operator_fee_constant,
+                ..Default::default()
+            })
+        } else {
+            L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus {
+                operator_fee_scalar,
+                operator_fee_constant,
+                ..Default::default()
+            })
+        };
+        Some(l1_info.operator_fee(self.gas_used()))
+    }
+
+    /// Verifies the L1 fee fields of the receipt of `tx` against the values recomputed from the
+    /// L1 attributes of its block, active at `fork`.
+    ///
+    /// Returns the first mismatching field.
+    pub fn verify_l1_block_info(
+        &self,
+        tx: &OpTxEnvelope,
+        l1_info: &L1BlockInfoTx,
+        fork: OpHardfork,
+    ) -> Result<(), OpReceiptVerificationError> {
+        let tx_hash = tx.tx_hash();
+        if self.transaction_hash() != tx_hash {
+            return Err(OpReceiptVerificationError::TransactionHashMismatch {
+                expected: tx_hash,
+                got: self.transaction_hash(),
+            });
+        }
+        self.l1_block_info.verify(&L1BlockInfo::from_l1_info(tx, l1_info, fork))
+    }
+}
+
 /// Additional fields for Optimism transaction receipts: <https://github.com/ethereum-optimism/op-geth/blob/f2e69450c6eec9c35d56af91389a1c47737206ca/core/types/receipt.go#L87-L87>
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -191,6 +245,99 @@ pub struct L1BlockInfo {
 
 impl Eq for L1BlockInfo {}
 
+impl L1BlockInfo {
+    /// Returns the L1 block info reported in the receipt of `tx`, given the L1 attributes of its
+    /// block and the active `fork`.
+    ///
+    /// Deposit transactions don't pay L1 fees, so their receipts carry no L1 block info.
+    pub fn from_l1_info(tx: &OpTxEnvelope, l1_info: &L1BlockInfoTx, fork: OpHardfork) -> Self {
+        if tx.is_deposit() {
+            return Self::default();
+        }
+
+        let encoded = tx.encoded_2718();
+        let mut info = Self {
+            l1_gas_price: Some(l1_info.base_fee().into()),
+            l1_gas_used: Some(l1_gas_used(&encoded, l1_info, fork).saturating_to()),
+            l1_fee: Some(l1_info.l1_data_fee(&encoded, fork).saturating_to()),
+            ..Default::default()
+        };
+        if let L1BlockInfoTx::Bedrock(bedrock) = l1_info {
+            info.l1_fee_scalar = Some(f64::from(bedrock.l1_fee_scalar) / 1e6);
+        }
+        if let Some(ecotone) = l1_info.as_ecotone() {
+            info.l1_base_fee_scalar = Some(ecotone.base_fee_scalar.into());
+            info.l1_blob_base_fee = Some(ecotone.blob_base_fee);
+            info.l1_blob_base_fee_scalar = Some(ecotone.blob_base_fee_scalar.into());
+        }
+        if let Some((scalar, constant)) = l1_info.operator_fee_params() {
+            info.operator_fee_scalar = Some(scalar.into());
+            info.operator_fee_constant = Some(constant.into());
+        }
+        info
+    }
+
+    /// Verifies that all fields match the `expected` L1 block info.
+    ///
+    /// Returns the first mismatching field.
+    pub fn verify(&self, expected: &Self) -> Result<(), OpReceiptVerificationError> {
+        let fields = [
+            ("l1GasPrice", expected.l1_gas_price, self.l1_gas_price),
+            ("l1GasUsed", expected.l1_gas_used, self.l1_gas_used),
+            ("l1Fee", expected.l1_fee, self.l1_fee),
+            ("l1BaseFeeScalar", expected.l1_base_fee_scalar, self.l1_base_fee_scalar),
+            ("l1BlobBaseFee", expected.l1_blob_base_fee, self.l1_blob_base_fee),
+            ("l1BlobBaseFeeScalar", expected.l1_blob_base_fee_scalar, self.l1_blob_base_fee_scalar),
+            ("operatorFeeScalar", expected.operator_fee_scalar, self.operator_fee_scalar),
+            ("operatorFeeConstant", expected.operator_fee_constant, self.operator_fee_constant),
+        ];
+        for (field, expected, got) in fields {
+            if expected != got {
+                return Err(OpReceiptVerificationError::FieldMismatch { field, expected, got });
+            }
+        }
+        if expected.l1_fee_scalar != self.l1_fee_scalar {
+            return Err(OpReceiptVerificationError::L1FeeScalarMismatch {
+                expected: expected.l1_fee_scalar,
+                got: self.l1_fee_scalar,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when the fields of an [`OpTransactionReceipt`] don't match the values
+/// recomputed from its transaction and the L1 attributes of its block.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq)]
+pub enum OpReceiptVerificationError {
+    /// The receipt belongs to another transaction.
+    #[error("receipt of transaction {got}, expected {expected}")]
+    TransactionHashMismatch {
+        /// Hash of the verified transaction.
+        expected: TxHash,
+        /// Transaction hash reported by the receipt.
+        got: TxHash,
+    },
+    /// An L1 fee field of the receipt doesn't match the recomputed value.
+    #[error("{field} mismatch: expected {expected:?}, got {got:?}")]
+    FieldMismatch {
+        /// JSON name of the field.
+        field: &'static str,
+        /// Recomputed value.
+        expected: Option<u128>,
+        /// Value reported by the receipt.
+        got: Option<u128>,
+    },
+    /// The Bedrock L1 fee scalar of the receipt doesn't match the recomputed value.
+    #[error("l1FeeScalar mismatch: expected {expected:?}, got {got:?}")]
+    L1FeeScalarMismatch {
+        /// Recomputed value.
+        expected: Option<f64>,
+        /// Value reported by the receipt.
+        got: Option<f64>,
+    },
+}
+
 impl From<OpTransactionReceipt> for OpReceiptEnvelope<alloy_primitives::Log> {
     fn from(value: OpTransactionReceipt) -> Self {
         let inner_envelope = value.inner.inner;
@@ -243,8 +390,14 @@ impl From<OpTransactionReceipt> for OpReceiptEnvelope<alloy_primitives::Log> {
 mod tests {
     use super::*;
     use alloc::string::ToString;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{Bloom, Sealed};
+    use op_alloy_consensus::{L1BlockInfoEcotone, TxDeposit};
     use serde_json::{Value, json};
 
+    /// An EIP-1559 contract call on OP Mainnet, EIP-2718 encoded.
+    const SAMPLE_CONTRACT_CALL: &[u8] = include_bytes!("../testdata/sample_contract_call.bin");
+
     // <https://github.com/alloy-rs/op-alloy/issues/18>
     #[test]
     fn parse_rpc_receipt() {
@@ -278,6 +431,108 @@ mod tests {
         assert_eq!(value, expected_value);
     }
 
+    #[test]
+    fn verify_receipt_l1_block_info() {
+        let tx = OpTxEnvelope::decode_2718(&mut &SAMPLE_CONTRACT_CALL[..]).unwrap();
+        let ecotone = L1BlockInfoEcotone {
+            base_fee: 1_000,
+            base_fee_scalar: 1_000,
+            blob_base_fee: 1_000,
+            blob_base_fee_scalar: 1_000,
+            ..Default::default()
+        };
+        let l1_info =
+            L1BlockInfoTx::Isthmus(L1BlockInfoIsthmus::from_ecotone(ecotone, 2_000_000, 500));
+
+        let expected = L1BlockInfo::from_l1_info(&tx, &l1_info, OpHardfork::Isthmus);
+        assert_eq!(
+            expected,
+            L1BlockInfo {
+                l1_gas_price: Some(1_000),
+                l1_gas_used: Some(2022),
+                l1_fee: Some(2148),
+                l1_fee_scalar: None,
+                l1_base_fee_scalar: Some(1_000),
+                l1_blob_base_fee: Some(1_000),
+                l1_blob_base_fee_scalar: Some(1_000),
+                operator_fee_scalar: Some(2_000_000),
+                operator_fee_constant: Some(500),
+            }
+        );
+
+        let mut receipt: OpTransactionReceipt = serde_json::from_value(json!({
+            "blockHash": "0x9e6a0fb7e22159d943d760608cc36a0fb596d1ab3c997146f5b7c55c8c718c67",
+            "blockNumber": "0x6cfef89",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x75",
+            "from": "0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001",
+            "gasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": Bloom::ZERO,
+            "status": "0x1",
+            "to": "0xf80e51afb613d764fa61751affd3313c190a86bb",
+            "transactionHash": tx.tx_hash(),
+            "transactionIndex": "0x1",
+            "type": "0x2",
+            "l1GasPrice": "0x3e8",
+            "l1GasUsed": "0x7e6",
+            "l1Fee": "0x864",
+            "l1BaseFeeScalar": "0x3e8",
+            "l1BlobBaseFee": "0x3e8",
+            "l1BlobBaseFeeScalar": "0x3e8",
+            "operatorFeeScalar": "0x1e8480",
+            "operatorFeeConstant": "0x1f4"
+        }))
+        .unwrap();
+        assert_eq!(receipt.verify_l1_block_info(&tx, &l1_info, OpHardfork::Isthmus), Ok(()));
+        // 21000 * 2e6 / 1e6 + 500
+        assert_eq!(receipt.operator_fee(OpHardfork::Isthmus), Some(U256::from(42_500)));
+        // 21000 * 2e6 * 100 + 500
+        assert_eq!(
+            receipt.operator_fee(OpHardfork::Jovian),
+            Some(U256::from(4_200_000_000_500u64))
+        );
+
+        receipt.l1_block_info.l1_fee = Some(2147);
+        assert_eq!(
+            receipt.verify_l1_block_info(&tx, &l1_info, OpHardfork::Isthmus),
+            Err(OpReceiptVerificationError::FieldMismatch {
+                field: "l1Fee",
+                expected: Some(2148),
+                got: Some(2147)
+            })
+        );
+
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit::default()));
+        assert_eq!(
+            receipt.verify_l1_block_info(&deposit, &l1_info, OpHardfork::Isthmus),
+            Err(OpReceiptVerificationError::TransactionHashMismatch {
+                expected: deposit.tx_hash(),
+                got: tx.tx_hash()
+            })
+        );
+        assert_eq!(
+            L1BlockInfo::from_l1_info(&deposit, &l1_info, OpHardfork::Isthmus),
+            L1BlockInfo::default()
+        );
+    }
+
+    #[test]
+    fn verify_bedrock_l1_fee_scalar() {
+        let expected = L1BlockInfo { l1_fee_scalar: Some(0.684), ..Default::default() };
+        let info = L1BlockInfo { l1_fee_scalar: Some(0.684), ..Default::default() };
+        assert_eq!(info.verify(&expected), Ok(()));
+        let info = L1BlockInfo { l1_fee_scalar: Some(1.0), ..Default::default() };
+        assert_eq!(
+            info.verify(&expected),
+            Err(OpReceiptVerificationError::L1FeeScalarMismatch {
+                expected: Some(0.684),
+                got: Some(1.0)
+            })
+        );
+    }
+
     #[test]
     fn serialize_empty_optimism_transaction_receipt_fields_struct() {
         let op_fields = OpTransactionReceiptFields::default();
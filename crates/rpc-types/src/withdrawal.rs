@@ -0,0 +1,246 @@
+//! Verification of withdrawal proofs against output roots.
+
+use alloy_primitives::{Address, B256, U256, keccak256};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use alloy_trie::{
+    Nibbles, TrieAccount,
+    proof::{ProofVerificationError, verify_proof},
+};
+use op_alloy_consensus::{
+    OutputRootV0, predeploys::L2_TO_L1_MESSAGE_PASSER_ADDRESS, withdrawal::sent_message_slot,
+};
+
+/// An error that can occur when verifying a withdrawal proof.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum WithdrawalProofError {
+    /// The output root preimage does not hash to the output root.
+    #[error("output root mismatch: expected {expected}, got {got}")]
+    OutputRootMismatch {
+        /// The output root.
+        expected: B256,
+        /// The hash of the output root preimage.
+        got: B256,
+    },
+    /// The proof is not a proof of the `L2ToL1MessagePasser` account.
+    #[error("proof is not for the L2ToL1MessagePasser: {0}")]
+    InvalidAddress(Address),
+    /// The storage root of the proof does not match the message passer storage root of the
+    /// output root.
+    #[error("storage root mismatch: expected {expected}, got {got}")]
+    StorageRootMismatch {
+        /// The message passer storage root of the output root.
+        expected: B256,
+        /// The storage root of the proof.
+        got: B256,
+    },
+    /// The proof has no storage proof for the sent message slot of the withdrawal.
+    #[error("missing storage proof for slot {0}")]
+    MissingStorageProof(B256),
+    /// The sent message slot of the withdrawal is not set to `true`, i.e. `1`.
+    #[error("withdrawal is not sent")]
+    WithdrawalNotSent,
+    /// The account proof is invalid.
+    #[error("invalid account proof: {0}")]
+    InvalidAccountProof(ProofVerificationError),
+    /// The storage proof is invalid.
+    #[error("invalid storage proof: {0}")]
+    InvalidStorageProof(ProofVerificationError),
+}
+
+/// Verifies that the withdrawal with the given hash is sent in the L2 state committed to by
+/// `output_root`, using an `eth_getProof` response for the `L2ToL1MessagePasser` and the sent
+/// message slot of the withdrawal.
+///
+/// The `preimage` must hash to `output_root`. The account proof is verified against its state
+/// root, and the storage proof against its message passer storage root, which must be the storage
+/// root of the proven account.
+pub fn verify_withdrawal_proof(
+    withdrawal_hash: B256,
+    proof: &EIP1186AccountProofResponse,
+    output_root: B256,
+    preimage: &OutputRootV0,
+) -> Result<(), WithdrawalProofError> {
+    let got = preimage.hash();
+    if got != output_root {
+        return Err(WithdrawalProofError::OutputRootMismatch { expected: output_root, got });
+    }
+    if proof.address != L2_TO_L1_MESSAGE_PASSER_ADDRESS {
+        return Err(WithdrawalProofError::InvalidAddress(proof.address));
+    }
+    if proof.storage_hash != preimage.message_passer_storage_root {
+        return Err(WithdrawalProofError::StorageRootMismatch {
+            expected: preimage.message_passer_storage_root,
+            got: proof.storage_hash,
+        });
+    }
+
+    let account = TrieAccount {
+        nonce: proof.nonce,
+        balance: proof.balance,
+        storage_root: proof.storage_hash,
+        code_hash: proof.code_hash,
+    };
+    verify_proof(
+        preimage.state_root,
+        Nibbles::unpack(keccak256(proof.address)),
+        Some(alloy_rlp::encode(account)),
+        &proof.account_proof,
+    )
+    .map_err(WithdrawalProofError::InvalidAccountProof)?;
+
+    let slot = sent_message_slot(withdrawal_hash);
+    let storage_proof = proof
+        .storage_proof
+        .iter()
+        .find(|storage_proof| storage_proof.key.as_b256() == slot)
+        .ok_or(WithdrawalProofError::MissingStorageProof(slot))?;
+    // The `OptimismPortal` proves the slot to hold exactly `true`.
+    if storage_proof.value != U256::from(1) {
+        return Err(WithdrawalProofError::WithdrawalNotSent);
+    }
+    verify_proof(
+        proof.storage_hash,
+        Nibbles::unpack(keccak256(slot)),
+        Some(alloy_rlp::encode(storage_proof.value)),
+        &storage_proof.proof,
+    )
+    .map_err(WithdrawalProofError::InvalidStorageProof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use alloy_rpc_types_eth::EIP1186StorageProof;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+
+    /// Builds a trie from the given leaves and returns its root and the proof of `target`.
+    fn trie_proof(mut leaves: Vec<(B256, Vec<u8>)>, target: B256) -> (B256, Vec<Bytes>) {
+        leaves.sort();
+        let target = Nibbles::unpack(target);
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target]));
+        for (key, value) in leaves {
+            builder.add_leaf(Nibbles::unpack(key), &value);
+        }
+        let root = builder.root();
+        let proof = builder.take_proof_nodes().matching_nodes_sorted(&target);
+        (root, proof.into_iter().map(|(_, node)| node).collect())
+    }
+
+    /// Builds an output root and a proof of the sent message slot of `withdrawal_hash`.
+    fn withdrawal_proof(withdrawal_hash: B256) -> (OutputRootV0, EIP1186AccountProofResponse) {
+        withdrawal_proof_with_value(withdrawal_hash, U256::from(1))
+    }
+
+    /// Builds an output root and a proof of the sent message slot of `withdrawal_hash`, which
+    /// holds `value`.
+    fn withdrawal_proof_with_value(
+        withdrawal_hash: B256,
+        value: U256,
+    ) -> (OutputRootV0, EIP1186AccountProofResponse) {
+        let slot = sent_message_slot(withdrawal_hash);
+        let (storage_root, storage_proof) = trie_proof(
+            vec![
+                (keccak256(slot), alloy_rlp::encode(value)),
+                (keccak256(B256::repeat_byte(1)), alloy_rlp::encode(U256::from(1))),
+                (keccak256(B256::repeat_byte(2)), alloy_rlp::encode(U256::from(1))),
+            ],
+            keccak256(slot),
+        );
+
+        let account = TrieAccount { storage_root, ..Default::default() };
+        let account_key = keccak256(L2_TO_L1_MESSAGE_PASSER_ADDRESS);
+        let (state_root, account_proof) = trie_proof(
+            vec![
+                (account_key, alloy_rlp::encode(account)),
+                (keccak256(Address::ZERO), alloy_rlp::encode(TrieAccount::default())),
+            ],
+            account_key,
+        );
+
+        let output_root = OutputRootV0::new(state_root, storage_root, B256::repeat_byte(3));
+        let proof = EIP1186AccountProofResponse {
+            address: L2_TO_L1_MESSAGE_PASSER_ADDRESS,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: account.nonce,
+            storage_hash: storage_root,
+            account_proof,
+            storage_proof: vec![EIP1186StorageProof {
+                key: slot.into(),
+                value,
+                proof: storage_proof,
+            }],
+        };
+        (output_root, proof)
+    }
+
+    #[test]
+    fn test_verify_withdrawal_proof() {
+        let withdrawal_hash = B256::repeat_byte(0xaa);
+        let (preimage, proof) = withdrawal_proof(withdrawal_hash);
+        let output_root = preimage.hash();
+        assert_eq!(
+            verify_withdrawal_proof(withdrawal_hash, &proof, output_root, &preimage),
+            Ok(())
+        );
+
+        assert_eq!(
+            verify_withdrawal_proof(withdrawal_hash, &proof, B256::ZERO, &preimage),
+            Err(WithdrawalProofError::OutputRootMismatch {
+                expected: B256::ZERO,
+                got: output_root
+            })
+        );
+        assert_eq!(
+            verify_withdrawal_proof(B256::ZERO, &proof, output_root, &preimage),
+            Err(WithdrawalProofError::MissingStorageProof(sent_message_slot(B256::ZERO)))
+        );
+    }
+
+    #[test]
+    fn test_verify_withdrawal_proof_invalid() {
+        let withdrawal_hash = B256::repeat_byte(0xaa);
+        let (preimage, proof) = withdrawal_proof(withdrawal_hash);
+        let output_root = preimage.hash();
+
+        let mut invalid = proof.clone();
+        invalid.storage_hash = B256::ZERO;
+        assert_eq!(
+            verify_withdrawal_proof(withdrawal_hash, &invalid, output_root, &preimage),
+            Err(WithdrawalProofError::StorageRootMismatch {
+                expected: preimage.message_passer_storage_root,
+                got: B256::ZERO
+            })
+        );
+
+        let mut invalid = proof.clone();
+        invalid.nonce = 1;
+        assert!(matches!(
+            verify_withdrawal_proof(withdrawal_hash, &invalid, output_root, &preimage),
+            Err(WithdrawalProofError::InvalidAccountProof(_))
+        ));
+
+        let mut invalid = proof.clone();
+        invalid.storage_proof[0].value = U256::ZERO;
+        assert_eq!(
+            verify_withdrawal_proof(withdrawal_hash, &invalid, output_root, &preimage),
+            Err(WithdrawalProofError::WithdrawalNotSent)
+        );
+
+        let mut invalid = proof;
+        invalid.storage_proof[0].proof.pop();
+        assert!(matches!(
+            verify_withdrawal_proof(withdrawal_hash, &invalid, output_root, &preimage),
+            Err(WithdrawalProofError::InvalidStorageProof(_))
+        ));
+
+        // A valid proof of any value other than `1` is rejected, as the portal would.
+        let (preimage, proof) = withdrawal_proof_with_value(withdrawal_hash, U256::from(2));
+        assert_eq!(
+            verify_withdrawal_proof(withdrawal_hash, &proof, preimage.hash(), &preimage),
+            Err(WithdrawalProofError::WithdrawalNotSent)
+        );
+    }
+}
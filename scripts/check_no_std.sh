@@ -6,6 +6,7 @@ no_std_packages=(
   op-alloy-consensus
   op-alloy-rpc-types
   op-alloy-rpc-types-engine
+  op-alloy-protocol
 )
 
 for package in "${no_std_packages[@]}"; do